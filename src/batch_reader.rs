@@ -0,0 +1,206 @@
+use std::fs::File;
+use std::io::{self, BufReader, Cursor, Read};
+use std::path::Path;
+use anyhow::{Result, anyhow};
+use crate::compression::{self, Compression, STDIO_PATH};
+use crate::encoding::{self, TextEncoding};
+use crate::number_format::{self, NumberFormat};
+use crate::dates::{self, DateOptions};
+use crate::read_file::{self, CsvOptions, CsvStream, DataSet};
+
+pub const DEFAULT_BATCH_SIZE: usize = 10_000;
+
+const SNIFF_SAMPLE_SIZE: usize = 64 * 1024;
+
+/// CSV 파일을 batch_size 행씩 나눠 읽는 리더. 배치마다 열 타입을 새로 추론한 DataSet을 반환하며
+/// 파일 전체를 메모리에 올리지 않음 (gzip/zstd/bzip2 압축 파일도 풀면서 읽음)
+pub struct CsvBatchReader {
+    reader: CsvStream,
+    headers: Vec<String>,
+    options: CsvOptions,
+    number_format: NumberFormat,
+    date_options: DateOptions,
+    batch_size: usize,
+    file_path: String,
+    /// 헤더가 없는 파일에서 열 개수를 알기 위해 먼저 읽은 첫 행과 그 줄 번호
+    pending: Option<(usize, Vec<String>)>,
+    /// 지금까지 배치로 내보낸 행 수
+    rows_read: usize,
+    finished: bool,
+}
+
+impl CsvBatchReader {
+    pub fn open<P: AsRef<Path>>(file_path: P, options: &CsvOptions, batch_size: usize) -> Result<Self> {
+        let path = file_path.as_ref();
+        if batch_size == 0 {
+            return Err(anyhow!("Batch size must be at least 1"));
+        }
+
+        let mut stream: Box<dyn Read> = if path == Path::new(STDIO_PATH) {
+            // Peek at the magic bytes, then put them back in front of the rest of stdin
+            let mut stdin: Box<dyn Read> = Box::new(io::stdin());
+            let mut magic = Vec::new();
+            stdin.by_ref().take(4).read_to_end(&mut magic)?;
+            let stdin = Box::new(Cursor::new(magic.clone()).chain(stdin));
+            match Compression::from_magic(&magic) {
+                Some(Compression::Zip) => return Err(anyhow!("Zip archives cannot be streamed from stdin")),
+                Some(compression) => compression::decoder(stdin, compression)?,
+                None => stdin,
+            }
+        } else {
+            match compression::detect_compression(path)? {
+                // Zip entries borrow the archive, so the single entry is read into memory
+                Some(Compression::Zip) => {
+                    Box::new(Cursor::new(compression::decompress_file(path, Compression::Zip)?.bytes))
+                }
+                Some(compression) => compression::open_decompressed(path, compression)?,
+                None => Box::new(BufReader::new(File::open(path)?)),
+            }
+        };
+
+        // Sniff the encoding and dialect from the first chunk, then replay it in front of the rest
+        let mut sample = Vec::with_capacity(SNIFF_SAMPLE_SIZE);
+        stream.by_ref().take(SNIFF_SAMPLE_SIZE as u64).read_to_end(&mut sample)?;
+        let encoding = options.encoding.unwrap_or_else(|| encoding::detect_encoding(&sample));
+        if matches!(encoding, TextEncoding::Utf16Le | TextEncoding::Utf16Be) {
+            return Err(anyhow!("Streaming is not supported for {} files; use read_csv_file", encoding));
+        }
+        let reader = CsvStream::open(sample, stream, encoding, options);
+        let has_headers = reader.has_headers;
+
+        let mut batch_reader = Self {
+            reader,
+            headers: Vec::new(),
+            options: options.clone(),
+            number_format: NumberFormat::default(),
+            date_options: DateOptions::default(),
+            batch_size,
+            file_path: path.to_string_lossy().to_string(),
+            pending: None,
+            rows_read: 0,
+            finished: false,
+        };
+
+        // Read headers, or generate column names from the width of the first row
+        let first = batch_reader.reader.next_row()?;
+        batch_reader.headers = match first {
+            Some((_, row)) if has_headers => row,
+            Some((line, row)) => {
+                let headers = (1..=row.len()).map(|i| format!("column_{}", i)).collect();
+                batch_reader.pending = Some((line, row));
+                headers
+            }
+            None => Vec::new(),
+        };
+
+        Ok(batch_reader)
+    }
+
+    pub fn headers(&self) -> &[String] {
+        &self.headers
+    }
+
+    /// 배치마다 서식 있는 숫자를 해석할 규칙 (기본: 천 단위 구분자, 통화 기호, 퍼센트, 괄호 음수)
+    pub fn with_number_format(mut self, number_format: NumberFormat) -> Self {
+        self.number_format = number_format;
+        self
+    }
+
+    /// 배치마다 적용할 열별 날짜 형식과 시간대
+    pub fn with_date_options(mut self, date_options: DateOptions) -> Self {
+        self.date_options = date_options;
+        self
+    }
+
+    fn next_batch(&mut self) -> Result<Option<DataSet>> {
+        let mut rows = Vec::with_capacity(self.batch_size);
+        let mut lines = Vec::with_capacity(self.batch_size);
+        if let Some((line, row)) = self.pending.take() {
+            lines.push(line);
+            rows.push(row);
+        }
+
+        while rows.len() < self.batch_size {
+            match self.reader.next_row()? {
+                Some((line, row)) => {
+                    lines.push(line);
+                    rows.push(row);
+                }
+                None => break,
+            }
+        }
+
+        if rows.is_empty() {
+            return Ok(None);
+        }
+
+        // Strict mode can only list the problems of the batch where reading stops
+        let malformed_rows = read_file::fit_rows(
+            &mut rows,
+            &lines,
+            self.headers.len(),
+            self.options.ragged_rows,
+            self.rows_read,
+            &self.file_path,
+        )?;
+        self.rows_read += rows.len();
+
+        let mut batch = DataSet::with_null_values(
+            self.headers.clone(),
+            rows,
+            self.file_path.clone(),
+            &self.options.null_values,
+        );
+        batch.load_report.malformed_rows = malformed_rows;
+        batch.load_report.number_formats = number_format::apply_number_format(&mut batch, &self.number_format);
+        batch.load_report.date_formats = dates::apply_date_options(&mut batch, &self.date_options)?;
+        Ok(Some(batch))
+    }
+}
+
+impl Iterator for CsvBatchReader {
+    type Item = Result<DataSet>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let batch = self.next_batch();
+        if !matches!(batch, Ok(Some(_))) {
+            self.finished = true;
+        }
+        batch.transpose()
+    }
+}
+
+/// CSV 파일을 배치 단위로 읽는 리더 생성. 경로가 "-"이면 표준 입력에서 읽음
+pub fn read_csv_batches<P: AsRef<Path>>(file_path: P, options: &CsvOptions, batch_size: usize) -> Result<CsvBatchReader> {
+    CsvBatchReader::open(file_path, options, batch_size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_file::RaggedRows;
+
+    #[test]
+    fn malformed_rows_keep_their_position_across_batches() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ragged.csv");
+        std::fs::write(&path, "a,b\n1,2\n3\n5,6\n7,8,9\n").unwrap();
+
+        let options = CsvOptions { ragged_rows: RaggedRows::Lenient, ..CsvOptions::default() };
+        let batches: Vec<DataSet> = read_csv_batches(&path, &options, 2).unwrap().collect::<Result<_>>().unwrap();
+        assert_eq!(batches.iter().map(DataSet::row_count).collect::<Vec<_>>(), [2, 2]);
+
+        let malformed: Vec<(usize, usize)> = batches
+            .iter()
+            .flat_map(|batch| batch.load_report.malformed_rows.iter().map(|m| (m.row, m.line)))
+            .collect();
+        assert_eq!(malformed, [(1, 3), (3, 5)]);
+
+        let strict = read_csv_batches(&path, &CsvOptions::default(), 2).unwrap().collect::<Result<Vec<_>>>();
+        assert!(strict.is_err());
+    }
+}
@@ -0,0 +1,582 @@
+use std::fmt;
+use chrono::{NaiveDate, NaiveDateTime};
+
+// Month and day may be written without a leading zero ("2024.3.1", "2024년 3월 1일")
+pub(crate) const DATE_FORMATS: &[&str] = &["%Y-%m-%d", "%Y.%m.%d", "%Y/%m/%d", "%Y. %m. %d.", "%Y년 %m월 %d일", "%Y년%m월%d일"];
+pub(crate) const DATETIME_FORMATS: &[&str] = &[
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%d %H:%M",
+    "%Y-%m-%dT%H:%M",
+    "%Y.%m.%d %H:%M:%S",
+    "%Y.%m.%d %H:%M",
+    "%Y/%m/%d %H:%M:%S",
+    "%Y/%m/%d %H:%M",
+    "%Y년 %m월 %d일 %H시 %M분 %S초",
+    "%Y년 %m월 %d일 %H:%M:%S",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnType {
+    Integer,
+    Float,
+    Boolean,
+    String,
+    Date,
+    DateTime,
+}
+
+impl fmt::Display for ColumnType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ColumnType::Integer => "integer",
+            ColumnType::Float => "float",
+            ColumnType::Boolean => "boolean",
+            ColumnType::String => "string",
+            ColumnType::Date => "date",
+            ColumnType::DateTime => "datetime",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// 단일 셀 값
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+    String(String),
+    Date(NaiveDate),
+    DateTime(NaiveDateTime),
+}
+
+impl Value {
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Integer(i) => Some(*i as f64),
+            Value::Float(f) => Some(*f),
+            _ => None,
+        }
+    }
+
+    /// 숫자 값, 또는 숫자로 읽히는 문자열 값 (한 칸의 오타로 문자열 열이 된 숫자 열을 숫자로 비교할 때)
+    pub fn as_number(&self) -> Option<f64> {
+        match self {
+            Value::String(s) => parse_number_text(s),
+            value => value.as_f64(),
+        }
+    }
+
+    fn column_type(&self) -> Option<ColumnType> {
+        match self {
+            Value::Null => None,
+            Value::Integer(_) => Some(ColumnType::Integer),
+            Value::Float(_) => Some(ColumnType::Float),
+            Value::Boolean(_) => Some(ColumnType::Boolean),
+            Value::String(_) => Some(ColumnType::String),
+            Value::Date(_) => Some(ColumnType::Date),
+            Value::DateTime(_) => Some(ColumnType::DateTime),
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Null => Ok(()),
+            Value::Integer(i) => write!(f, "{}", i),
+            Value::Float(v) => write!(f, "{}", v),
+            Value::Boolean(b) => write!(f, "{}", b),
+            Value::String(s) => write!(f, "{}", s),
+            Value::Date(d) => write!(f, "{}", d.format("%Y-%m-%d")),
+            Value::DateTime(dt) => write!(f, "{}", dt.format("%Y-%m-%d %H:%M:%S")),
+        }
+    }
+}
+
+/// 숫자로 읽히는 문자열의 값. "inf", "NaN"처럼 f64로만 읽히는 값은 숫자로 보지 않음
+pub(crate) fn parse_number_text(text: &str) -> Option<f64> {
+    text.trim().parse::<f64>().ok().filter(|number| number.is_finite())
+}
+
+/// 결측으로 취급할 문자열 목록 (앞뒤 공백 무시, 대소문자 구분 없음)
+#[derive(Debug, Clone)]
+pub struct NullValues {
+    tokens: Vec<String>,
+}
+
+impl NullValues {
+    pub fn new<S: AsRef<str>>(tokens: &[S]) -> Self {
+        Self {
+            tokens: tokens.iter().map(|t| t.as_ref().trim().to_lowercase()).collect(),
+        }
+    }
+
+    pub fn tokens(&self) -> &[String] {
+        &self.tokens
+    }
+
+    pub fn is_null(&self, text: &str) -> bool {
+        let text = text.trim();
+        self.tokens.iter().any(|token| token.eq_ignore_ascii_case(text))
+    }
+}
+
+impl Default for NullValues {
+    fn default() -> Self {
+        Self::new(&["", "NA", "N/A", "null", "-"])
+    }
+}
+
+/// 결측이 아니지만 숫자로 해석할 수 없는 셀
+#[derive(Debug, Clone)]
+pub struct InvalidCell {
+    pub row: usize,
+    pub value: String,
+}
+
+/// 열별 유효/결측/오류 값 개수
+#[derive(Debug, Clone)]
+pub struct MissingValueReport {
+    pub valid: usize,
+    pub missing: usize,
+    pub invalid: usize,
+    pub invalid_cells: Vec<InvalidCell>,
+}
+
+/// 열의 결측 여부를 비트 단위로 저장
+#[derive(Debug, Clone, Default)]
+pub struct NullBitmap {
+    bits: Vec<u64>,
+    len: usize,
+    null_count: usize,
+}
+
+impl NullBitmap {
+    pub fn push(&mut self, is_null: bool) {
+        if self.len.is_multiple_of(64) {
+            self.bits.push(0);
+        }
+        if is_null {
+            self.bits[self.len / 64] |= 1 << (self.len % 64);
+            self.null_count += 1;
+        }
+        self.len += 1;
+    }
+
+    pub fn is_null(&self, index: usize) -> bool {
+        index < self.len && self.bits[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn null_count(&self) -> usize {
+        self.null_count
+    }
+}
+
+/// 타입별로 저장된 열 데이터 (결측 칸에는 기본값이 들어감)
+#[derive(Debug, Clone)]
+pub enum ColumnData {
+    Integer(Vec<i64>),
+    Float(Vec<f64>),
+    Boolean(Vec<bool>),
+    String(Vec<String>),
+    Date(Vec<NaiveDate>),
+    DateTime(Vec<NaiveDateTime>),
+}
+
+impl ColumnData {
+    fn with_capacity(column_type: ColumnType, capacity: usize) -> Self {
+        match column_type {
+            ColumnType::Integer => ColumnData::Integer(Vec::with_capacity(capacity)),
+            ColumnType::Float => ColumnData::Float(Vec::with_capacity(capacity)),
+            ColumnType::Boolean => ColumnData::Boolean(Vec::with_capacity(capacity)),
+            ColumnType::String => ColumnData::String(Vec::with_capacity(capacity)),
+            ColumnType::Date => ColumnData::Date(Vec::with_capacity(capacity)),
+            ColumnType::DateTime => ColumnData::DateTime(Vec::with_capacity(capacity)),
+        }
+    }
+
+    fn push_default(&mut self) {
+        match self {
+            ColumnData::Integer(v) => v.push(0),
+            ColumnData::Float(v) => v.push(0.0),
+            ColumnData::Boolean(v) => v.push(false),
+            ColumnData::String(v) => v.push(String::new()),
+            ColumnData::Date(v) => v.push(NaiveDate::default()),
+            ColumnData::DateTime(v) => v.push(NaiveDateTime::default()),
+        }
+    }
+
+    /// 값을 열 타입에 맞게 변환해 추가. 변환할 수 없으면 false
+    fn push_value(&mut self, value: Value) -> bool {
+        match (self, value) {
+            (ColumnData::Integer(v), Value::Integer(i)) => v.push(i),
+            (ColumnData::Float(v), Value::Float(f)) => v.push(f),
+            (ColumnData::Float(v), Value::Integer(i)) => v.push(i as f64),
+            (ColumnData::Boolean(v), Value::Boolean(b)) => v.push(b),
+            (ColumnData::Date(v), Value::Date(d)) => v.push(d),
+            (ColumnData::DateTime(v), Value::DateTime(dt)) => v.push(dt),
+            (ColumnData::DateTime(v), Value::Date(d)) => v.push(d.and_hms_opt(0, 0, 0).unwrap()),
+            (ColumnData::String(v), value) => v.push(value.to_string()),
+            _ => return false,
+        }
+        true
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Column {
+    data: ColumnData,
+    nulls: NullBitmap,
+}
+
+impl Column {
+    /// 값 목록에서 열 타입을 추론해 생성
+    pub fn from_values(values: Vec<Value>) -> Self {
+        let column_type = infer_value_type(&values);
+        Self::from_values_as(values, column_type)
+    }
+
+    /// 지정한 타입으로 열 생성 (타입이 맞지 않는 값은 결측 처리)
+    pub fn from_values_as(values: Vec<Value>, column_type: ColumnType) -> Self {
+        let mut data = ColumnData::with_capacity(column_type, values.len());
+        let mut nulls = NullBitmap::default();
+
+        for value in values {
+            let is_null = value.is_null() || !data.push_value(value);
+            if is_null {
+                data.push_default();
+            }
+            nulls.push(is_null);
+        }
+
+        Self { data, nulls }
+    }
+
+    /// 문자열 셀에서 열 타입을 추론해 생성 (null_values에 해당하는 셀은 결측)
+    pub fn from_strings<S: AsRef<str>>(cells: &[S], null_values: &NullValues) -> Self {
+        let values = cells
+            .iter()
+            .map(|cell| Value::String(cell.as_ref().to_string()))
+            .collect();
+        Self::from_cells(values, null_values)
+    }
+
+    /// 타입이 있는 값과 문자열이 섞인 셀에서 열 생성.
+    /// 문자열은 먼저 결측 여부를 확인한 뒤 문자열끼리 추론한 타입으로 변환하고,
+    /// 그 결과를 나머지 값과 합쳐 최종 타입을 정함
+    pub fn from_cells(values: Vec<Value>, null_values: &NullValues) -> Self {
+        let values: Vec<Value> = values
+            .into_iter()
+            .map(|value| match value {
+                Value::String(s) if null_values.is_null(&s) => Value::Null,
+                other => other,
+            })
+            .collect();
+
+        let text_type = infer_string_type(&values);
+        let values = values
+            .into_iter()
+            .map(|value| match value {
+                Value::String(s) => parse_as(&s, text_type),
+                other => other,
+            })
+            .collect();
+        Self::from_values(values)
+    }
+
+    pub fn data(&self) -> &ColumnData {
+        &self.data
+    }
+
+    pub fn nulls(&self) -> &NullBitmap {
+        &self.nulls
+    }
+
+    pub fn column_type(&self) -> ColumnType {
+        match self.data {
+            ColumnData::Integer(_) => ColumnType::Integer,
+            ColumnData::Float(_) => ColumnType::Float,
+            ColumnData::Boolean(_) => ColumnType::Boolean,
+            ColumnData::String(_) => ColumnType::String,
+            ColumnData::Date(_) => ColumnType::Date,
+            ColumnData::DateTime(_) => ColumnType::DateTime,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nulls.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nulls.is_empty()
+    }
+
+    pub fn null_count(&self) -> usize {
+        self.nulls.null_count()
+    }
+
+    pub fn is_null(&self, index: usize) -> bool {
+        self.nulls.is_null(index)
+    }
+
+    pub fn get(&self, index: usize) -> Value {
+        if index >= self.len() || self.is_null(index) {
+            return Value::Null;
+        }
+
+        match &self.data {
+            ColumnData::Integer(v) => Value::Integer(v[index]),
+            ColumnData::Float(v) => Value::Float(v[index]),
+            ColumnData::Boolean(v) => Value::Boolean(v[index]),
+            ColumnData::String(v) => Value::String(v[index].clone()),
+            ColumnData::Date(v) => Value::Date(v[index]),
+            ColumnData::DateTime(v) => Value::DateTime(v[index]),
+        }
+    }
+
+    /// 셀 값을 문자열로 반환 (결측은 빈 문자열)
+    pub fn get_string(&self, index: usize) -> String {
+        self.get(index).to_string()
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = Value> + '_ {
+        (0..self.len()).map(move |i| self.get(i))
+    }
+
+    /// 결측이 아닌 숫자 값만 반환. 숫자 타입 열이 아니면 None
+    pub fn numeric_values(&self) -> Option<Vec<f64>> {
+        let values = match &self.data {
+            ColumnData::Integer(v) => v
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| !self.is_null(*i))
+                .map(|(_, &x)| x as f64)
+                .collect(),
+            ColumnData::Float(v) => v
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| !self.is_null(*i))
+                .map(|(_, &x)| x)
+                .collect(),
+            _ => return None,
+        };
+        Some(values)
+    }
+
+    /// 결측/오류 값 보고서. 문자열 열은 결측이 아닌 값의 과반이 숫자일 때만
+    /// 숫자 열로 보고 나머지 값을 오류로 분류
+    pub fn missing_value_report(&self) -> MissingValueReport {
+        let missing = self.null_count();
+        let present = self.len() - missing;
+
+        let ColumnData::String(cells) = &self.data else {
+            return MissingValueReport {
+                valid: present,
+                missing,
+                invalid: 0,
+                invalid_cells: Vec::new(),
+            };
+        };
+
+        let invalid_cells: Vec<InvalidCell> = cells
+            .iter()
+            .enumerate()
+            .filter(|(row, cell)| !self.is_null(*row) && parse_number_text(cell).is_none())
+            .map(|(row, cell)| InvalidCell {
+                row,
+                value: cell.clone(),
+            })
+            .collect();
+
+        if invalid_cells.len() * 2 >= present {
+            return MissingValueReport {
+                valid: present,
+                missing,
+                invalid: 0,
+                invalid_cells: Vec::new(),
+            };
+        }
+
+        MissingValueReport {
+            valid: present - invalid_cells.len(),
+            missing,
+            invalid: invalid_cells.len(),
+            invalid_cells,
+        }
+    }
+
+    /// 지정한 행만 뽑아 새 열 생성 (범위를 벗어난 행은 결측)
+    pub fn take(&self, indices: &[usize]) -> Column {
+        let values = indices.iter().map(|&i| self.get(i)).collect();
+        Self::from_values_as(values, self.column_type())
+    }
+}
+
+fn infer_value_type(values: &[Value]) -> ColumnType {
+    let mut column_type: Option<ColumnType> = None;
+
+    for value_type in values.iter().filter_map(Value::column_type) {
+        column_type = Some(match (column_type, value_type) {
+            (None, t) => t,
+            (Some(a), b) if a == b => a,
+            (Some(ColumnType::Integer), ColumnType::Float)
+            | (Some(ColumnType::Float), ColumnType::Integer) => ColumnType::Float,
+            (Some(ColumnType::Date), ColumnType::DateTime)
+            | (Some(ColumnType::DateTime), ColumnType::Date) => ColumnType::DateTime,
+            _ => return ColumnType::String,
+        });
+    }
+
+    column_type.unwrap_or(ColumnType::String)
+}
+
+fn infer_string_type(values: &[Value]) -> ColumnType {
+    let mut candidates = [
+        ColumnType::Integer,
+        ColumnType::Float,
+        ColumnType::Boolean,
+        ColumnType::Date,
+        ColumnType::DateTime,
+    ]
+    .to_vec();
+    let mut seen_value = false;
+
+    for value in values {
+        if let Value::String(s) = value {
+            seen_value = true;
+            candidates.retain(|&t| !parse_as(s, t).is_null());
+            if candidates.is_empty() {
+                return ColumnType::String;
+            }
+        }
+    }
+
+    if seen_value {
+        candidates[0]
+    } else {
+        ColumnType::String
+    }
+}
+
+/// 문자열을 지정한 타입으로 변환. 실패하면 Value::Null
+pub fn parse_as(raw: &str, column_type: ColumnType) -> Value {
+    let text = raw.trim();
+    match column_type {
+        ColumnType::Integer => {
+            if has_leading_zero(text) {
+                return Value::Null;
+            }
+            text.parse::<i64>().map(Value::Integer).unwrap_or(Value::Null)
+        }
+        ColumnType::Float => {
+            if has_leading_zero(text) || !text.chars().all(|c| c.is_ascii_digit() || "+-.eE".contains(c)) {
+                return Value::Null;
+            }
+            // "1e999" overflows to infinity, which is not a usable number
+            parse_number_text(text).map(Value::Float).unwrap_or(Value::Null)
+        }
+        ColumnType::Boolean => match text.to_lowercase().as_str() {
+            "true" => Value::Boolean(true),
+            "false" => Value::Boolean(false),
+            _ => Value::Null,
+        },
+        ColumnType::Date => DATE_FORMATS
+            .iter()
+            .find_map(|fmt| NaiveDate::parse_from_str(text, fmt).ok())
+            .map(Value::Date)
+            .unwrap_or(Value::Null),
+        ColumnType::DateTime => DATETIME_FORMATS
+            .iter()
+            .find_map(|fmt| NaiveDateTime::parse_from_str(text, fmt).ok())
+            .map(Value::DateTime)
+            .unwrap_or(Value::Null),
+        ColumnType::String => Value::String(raw.to_string()),
+    }
+}
+
+// Identifiers such as zip codes ("01234") must stay text to round-trip unchanged
+fn has_leading_zero(text: &str) -> bool {
+    let digits = text.strip_prefix(['-', '+']).unwrap_or(text);
+    digits.len() > 1 && digits.starts_with('0') && !digits.starts_with("0.")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(cells: &[&str]) -> Column {
+        let values = cells.iter().map(|cell| Value::String(cell.to_string())).collect();
+        Column::from_cells(values, &NullValues::default())
+    }
+
+    #[test]
+    fn number_text_must_be_finite() {
+        assert_eq!(parse_number_text(" 1.5 "), Some(1.5));
+        assert_eq!(parse_number_text("-2e3"), Some(-2000.0));
+        for text in ["NaN", "nan", "inf", "-inf", "infinity", "1e999", "", "abc"] {
+            assert_eq!(parse_number_text(text), None, "{}", text);
+        }
+        assert!(parse_as("1e999", ColumnType::Float).is_null());
+        assert!(parse_as("NaN", ColumnType::Float).is_null());
+    }
+
+    #[test]
+    fn infers_column_types_from_text() {
+        assert_eq!(column(&["1", "2", "-3"]).column_type(), ColumnType::Integer);
+        assert_eq!(column(&["1", "2.5", ""]).column_type(), ColumnType::Float);
+        assert_eq!(column(&["true", "FALSE"]).column_type(), ColumnType::Boolean);
+        assert_eq!(column(&["2024-03-01", "2024-03-02"]).column_type(), ColumnType::Date);
+        assert_eq!(column(&["01234", "56789"]).column_type(), ColumnType::String);
+        assert_eq!(column(&["1", "NaN", "3"]).column_type(), ColumnType::String);
+        assert_eq!(column(&["1.5", "inf"]).column_type(), ColumnType::String);
+
+        let numbers = column(&["1", "NA", "3"]);
+        assert_eq!(numbers.null_count(), 1);
+        assert_eq!(numbers.get(2), Value::Integer(3));
+    }
+
+    #[test]
+    fn missing_and_invalid_cells_are_counted_apart() {
+        let cells = ["10", "", " na ", "-", "20", "오류", "30", "40"];
+        let report = column(&cells).missing_value_report();
+        assert_eq!((report.valid, report.missing, report.invalid), (4, 3, 1));
+        assert_eq!((report.invalid_cells[0].row, report.invalid_cells[0].value.as_str()), (5, "오류"));
+
+        // Custom tokens replace the defaults, so "-" becomes an invalid cell
+        let values = cells.iter().map(|cell| Value::String(cell.to_string())).collect();
+        let custom = Column::from_cells(values, &NullValues::new(&["", "NA", "오류"]));
+        let report = custom.missing_value_report();
+        assert_eq!((report.valid, report.missing, report.invalid), (4, 3, 1));
+        assert_eq!(report.invalid_cells[0].value, "-");
+
+        // A mostly textual column is text, not a numeric column full of errors
+        let report = column(&["서울", "부산", "1"]).missing_value_report();
+        assert_eq!((report.valid, report.invalid), (3, 0));
+    }
+
+    #[test]
+    fn non_finite_text_is_reported_as_invalid() {
+        let cells: Vec<String> = (1..=9).map(|i| i.to_string()).chain(["NaN".to_string(), "inf".to_string()]).collect();
+        let cells: Vec<&str> = cells.iter().map(String::as_str).collect();
+        let report = column(&cells).missing_value_report();
+
+        assert_eq!((report.valid, report.missing, report.invalid), (9, 0, 2));
+        let invalid: Vec<&str> = report.invalid_cells.iter().map(|cell| cell.value.as_str()).collect();
+        assert_eq!(invalid, ["NaN", "inf"]);
+    }
+}
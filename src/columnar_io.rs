@@ -0,0 +1,298 @@
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+use anyhow::{Result, anyhow};
+use arrow::array::{
+    Array, ArrayRef, AsArray, BooleanArray, Date32Array, Float64Array, Int64Array, RecordBatch, RecordBatchOptions, RecordBatchReader,
+    StringArray, TimestampMicrosecondArray,
+};
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, Date32Type, Field, Float64Type, Int64Type, Schema, TimeUnit, TimestampMicrosecondType};
+use arrow::ipc::reader::{FileReader, StreamReader};
+use arrow::ipc::writer::FileWriter;
+use arrow::util::display::{ArrayFormatter, FormatOptions};
+use chrono::{DateTime, NaiveDate};
+use parquet::arrow::ArrowWriter;
+use parquet::arrow::ProjectionMask;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use crate::column::{Column, ColumnData, ColumnType, Value};
+use crate::compression::CompressedWriter;
+use crate::read_file::DataSet;
+
+/// Parquet 파일 읽기. columns를 지정하면 해당 열만 디코딩
+pub fn read_parquet_file<P: AsRef<Path>>(file_path: P, columns: Option<&[String]>) -> Result<DataSet> {
+    let path = file_path.as_ref();
+    let builder = ParquetRecordBatchReaderBuilder::try_new(File::open(path)?)?;
+
+    let builder = match columns {
+        Some(columns) => {
+            let indices = projection_indices(builder.schema(), columns)?;
+            let mask = ProjectionMask::roots(builder.parquet_schema(), indices);
+            builder.with_projection(mask)
+        }
+        None => builder,
+    };
+
+    let reader = builder.build()?;
+    let schema = reader.schema();
+    let batches = reader.collect::<std::result::Result<Vec<_>, _>>()?;
+    let dataset = batches_to_dataset(&schema, &batches, path)?;
+
+    // Parquet projection keeps file order; return columns in the requested order
+    match columns {
+        Some(columns) => reorder_columns(dataset, columns),
+        None => Ok(dataset),
+    }
+}
+
+/// Arrow IPC(.arrow/.feather) 파일 또는 스트림 읽기. columns를 지정하면 해당 열만 디코딩
+pub fn read_arrow_file<P: AsRef<Path>>(file_path: P, columns: Option<&[String]>) -> Result<DataSet> {
+    let path = file_path.as_ref();
+
+    let (schema, batches) = match FileReader::try_new(File::open(path)?, None) {
+        Ok(reader) => {
+            let projection = match columns {
+                Some(columns) => Some(projection_indices(&reader.schema(), columns)?),
+                None => None,
+            };
+            let reader = FileReader::try_new(File::open(path)?, projection)?;
+            let schema = reader.schema();
+            (schema, reader.collect::<std::result::Result<Vec<_>, _>>()?)
+        }
+        // Not an IPC file; fall back to the IPC stream format
+        Err(_) => {
+            let projection = columns.map(|columns| {
+                let reader = StreamReader::try_new(File::open(path)?, None)?;
+                projection_indices(&reader.schema(), columns)
+            });
+            let projection = projection.transpose()?;
+            let reader = StreamReader::try_new(File::open(path)?, projection)?;
+            let schema = reader.schema();
+            (schema, reader.collect::<std::result::Result<Vec<_>, _>>()?)
+        }
+    };
+
+    let dataset = batches_to_dataset(&schema, &batches, path)?;
+    match columns {
+        Some(columns) => reorder_columns(dataset, columns),
+        None => Ok(dataset),
+    }
+}
+
+/// 데이터셋을 Parquet 파일로 저장 (날짜/시간/문자열은 해당 논리 타입으로 저장). 경로가 "-"이면 표준 출력에 씀
+pub fn save_dataset_to_parquet<P: AsRef<Path>>(dataset: &DataSet, output_path: P) -> Result<()> {
+    let batch = dataset_to_batch(dataset)?;
+    let mut writer = ArrowWriter::try_new(CompressedWriter::create(output_path, None, "parquet")?, batch.schema(), None)?;
+    writer.write(&batch)?;
+    writer.into_inner()?.finish()
+}
+
+/// 데이터셋을 Arrow IPC 파일(.arrow/.feather)로 저장. 경로가 "-"이면 표준 출력에 씀
+pub fn save_dataset_to_arrow<P: AsRef<Path>>(dataset: &DataSet, output_path: P) -> Result<()> {
+    let batch = dataset_to_batch(dataset)?;
+    let mut writer = FileWriter::try_new(CompressedWriter::create(output_path, None, "arrow")?, &batch.schema())?;
+    writer.write(&batch)?;
+    writer.finish()?;
+    writer.into_inner()?.finish()
+}
+
+fn projection_indices(schema: &Schema, columns: &[String]) -> Result<Vec<usize>> {
+    columns
+        .iter()
+        .map(|name| schema.index_of(name).map_err(|_| anyhow!("Column '{}' not found", name)))
+        .collect()
+}
+
+fn reorder_columns(dataset: DataSet, columns: &[String]) -> Result<DataSet> {
+    let mut reordered = Vec::with_capacity(columns.len());
+    for name in columns {
+        reordered.push(dataset.column(name)?.clone());
+    }
+    Ok(DataSet::from_columns(columns.to_vec(), reordered, dataset.file_path))
+}
+
+fn batches_to_dataset(schema: &Schema, batches: &[RecordBatch], path: &Path) -> Result<DataSet> {
+    // Readers may report the file schema even when a projection was applied
+    let batch_schema = batches.first().map(RecordBatch::schema);
+    let schema = batch_schema.as_deref().unwrap_or(schema);
+    let mut headers = Vec::new();
+    let mut columns = Vec::new();
+
+    for (col_idx, field) in schema.fields().iter().enumerate() {
+        let mut values = Vec::new();
+        let mut column_type = ColumnType::String;
+        for batch in batches {
+            let (batch_type, batch_values) = array_to_values(batch.column(col_idx).as_ref())?;
+            column_type = batch_type;
+            values.extend(batch_values);
+        }
+        headers.push(field.name().clone());
+        columns.push(Column::from_values_as(values, column_type));
+    }
+
+    Ok(DataSet::from_columns(headers, columns, path.to_string_lossy().to_string()))
+}
+
+/// Arrow 배열을 값 목록으로 변환. 정수/실수/불리언/날짜/시간 외의 타입은 문자열로 표시
+fn array_to_values(array: &dyn Array) -> Result<(ColumnType, Vec<Value>)> {
+    let data_type = array.data_type();
+    let null_or = |i: usize, value: Value| if array.is_null(i) { Value::Null } else { value };
+
+    let converted = match data_type {
+        DataType::Int8 | DataType::Int16 | DataType::Int32 | DataType::Int64
+        | DataType::UInt8 | DataType::UInt16 | DataType::UInt32 => {
+            let ints = cast(array, &DataType::Int64)?;
+            let ints = ints.as_primitive::<Int64Type>();
+            (ColumnType::Integer, (0..ints.len()).map(|i| null_or(i, Value::Integer(ints.value(i)))).collect())
+        }
+        DataType::UInt64 | DataType::Float16 | DataType::Float32 | DataType::Float64
+        | DataType::Decimal128(_, _) | DataType::Decimal256(_, _) => {
+            let floats = cast(array, &DataType::Float64)?;
+            let floats = floats.as_primitive::<Float64Type>();
+            (ColumnType::Float, (0..floats.len()).map(|i| null_or(i, Value::Float(floats.value(i)))).collect())
+        }
+        DataType::Boolean => {
+            let bools = array.as_boolean();
+            (ColumnType::Boolean, (0..bools.len()).map(|i| null_or(i, Value::Boolean(bools.value(i)))).collect())
+        }
+        DataType::Date32 | DataType::Date64 => {
+            let dates = cast(array, &DataType::Date32)?;
+            let dates = dates.as_primitive::<Date32Type>();
+            let values = (0..dates.len())
+                .map(|i| match dates.value_as_date(i) {
+                    Some(date) => null_or(i, Value::Date(date)),
+                    None => Value::Null,
+                })
+                .collect();
+            (ColumnType::Date, values)
+        }
+        // Time zone aware timestamps are stored as UTC instants; keep them as naive UTC
+        DataType::Timestamp(_, time_zone) => {
+            let timestamps = cast(array, &DataType::Timestamp(TimeUnit::Microsecond, time_zone.clone()))?;
+            let timestamps = timestamps.as_primitive::<TimestampMicrosecondType>();
+            let values = (0..timestamps.len())
+                .map(|i| match DateTime::from_timestamp_micros(timestamps.value(i)) {
+                    Some(datetime) => null_or(i, Value::DateTime(datetime.naive_utc())),
+                    None => Value::Null,
+                })
+                .collect();
+            (ColumnType::DateTime, values)
+        }
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View => {
+            let strings = cast(array, &DataType::Utf8)?;
+            let strings = strings.as_string::<i32>();
+            let values = (0..strings.len())
+                .map(|i| null_or(i, Value::String(strings.value(i).to_string())))
+                .collect();
+            (ColumnType::String, values)
+        }
+        _ => {
+            let formatter = ArrayFormatter::try_new(array, &FormatOptions::default())?;
+            let values = (0..array.len())
+                .map(|i| null_or(i, Value::String(formatter.value(i).to_string())))
+                .collect();
+            (ColumnType::String, values)
+        }
+    };
+
+    Ok(converted)
+}
+
+fn dataset_to_batch(dataset: &DataSet) -> Result<RecordBatch> {
+    let mut fields = Vec::new();
+    let mut arrays: Vec<ArrayRef> = Vec::new();
+
+    for (header, column) in dataset.headers.iter().zip(&dataset.columns) {
+        let valid = |i: usize| !column.is_null(i);
+        let (data_type, array): (DataType, ArrayRef) = match column.data() {
+            ColumnData::Integer(v) => (
+                DataType::Int64,
+                Arc::new(Int64Array::from_iter(v.iter().enumerate().map(|(i, &x)| valid(i).then_some(x)))),
+            ),
+            ColumnData::Float(v) => (
+                DataType::Float64,
+                Arc::new(Float64Array::from_iter(v.iter().enumerate().map(|(i, &x)| valid(i).then_some(x)))),
+            ),
+            ColumnData::Boolean(v) => (
+                DataType::Boolean,
+                Arc::new(BooleanArray::from_iter(v.iter().enumerate().map(|(i, &x)| valid(i).then_some(x)))),
+            ),
+            ColumnData::String(v) => (
+                DataType::Utf8,
+                Arc::new(StringArray::from_iter(v.iter().enumerate().map(|(i, x)| valid(i).then_some(x.as_str())))),
+            ),
+            ColumnData::Date(v) => {
+                let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+                let days = v
+                    .iter()
+                    .enumerate()
+                    .map(|(i, d)| valid(i).then(|| (*d - epoch).num_days() as i32));
+                (DataType::Date32, Arc::new(Date32Array::from_iter(days)))
+            }
+            ColumnData::DateTime(v) => {
+                let micros = v
+                    .iter()
+                    .enumerate()
+                    .map(|(i, dt)| valid(i).then(|| dt.and_utc().timestamp_micros()));
+                (
+                    DataType::Timestamp(TimeUnit::Microsecond, None),
+                    Arc::new(TimestampMicrosecondArray::from_iter(micros)),
+                )
+            }
+        };
+
+        fields.push(Field::new(header, data_type, true));
+        arrays.push(array);
+    }
+
+    // A batch without columns cannot tell its length from the arrays, so the row count is given explicitly
+    let schema = Arc::new(Schema::new(fields));
+    let options = RecordBatchOptions::new().with_row_count(Some(dataset.row_count()));
+    Ok(RecordBatch::try_new_with_options(schema, arrays, &options)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn typed_columns_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let original = DataSet::new(
+            ["이름", "점수", "평균", "합격", "일자", "시각"].iter().map(|h| h.to_string()).collect(),
+            vec![
+                ["가", "90", "1.5", "true", "2024-03-01", "2024-03-01 09:30:00"].iter().map(|c| c.to_string()).collect(),
+                ["", "", "2", "false", "", "2024-03-02 18:00:00"].iter().map(|c| c.to_string()).collect(),
+            ],
+            "test".to_string(),
+        );
+        let types = |dataset: &DataSet| dataset.columns.iter().map(Column::column_type).collect::<Vec<_>>();
+        let select = ["시각".to_string(), "점수".to_string()];
+        assert_eq!(types(&original)[1..], [ColumnType::Integer, ColumnType::Float, ColumnType::Boolean, ColumnType::Date, ColumnType::DateTime]);
+
+        for name in ["data.parquet", "data.arrow"] {
+            let path = dir.path().join(name);
+            crate::read_file::save_dataset(&original, &path, None).unwrap();
+            let read = crate::read_file::read_file(&path).unwrap();
+            assert_eq!(types(&read), types(&original), "{}", name);
+            assert_eq!(read.rows().collect::<Vec<_>>(), original.rows().collect::<Vec<_>>(), "{}", name);
+
+            let projected = match name {
+                "data.parquet" => read_parquet_file(&path, Some(&select)).unwrap(),
+                _ => read_arrow_file(&path, Some(&select)).unwrap(),
+            };
+            assert_eq!(projected.headers, select, "{}", name);
+        }
+    }
+
+    #[test]
+    fn dataset_without_columns_is_written() {
+        let dir = tempfile::tempdir().unwrap();
+        let dataset = DataSet::new(Vec::new(), vec![Vec::new(); 3], "test".to_string());
+        save_dataset_to_arrow(&dataset, dir.path().join("empty.arrow")).unwrap();
+        save_dataset_to_parquet(&dataset, dir.path().join("empty.parquet")).unwrap();
+
+        assert!(read_arrow_file(dir.path().join("empty.arrow"), None).unwrap().headers.is_empty());
+        assert!(read_parquet_file(dir.path().join("empty.parquet"), None).unwrap().headers.is_empty());
+    }
+}
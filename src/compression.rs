@@ -0,0 +1,406 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Seek, Write};
+use std::path::Path;
+use std::str::FromStr;
+use anyhow::{Result, anyhow};
+use bzip2::read::BzDecoder;
+use bzip2::write::BzEncoder;
+use flate2::read::MultiGzDecoder;
+use flate2::write::GzEncoder;
+use zip::ZipArchive;
+use zip::write::{SimpleFileOptions, ZipWriter};
+use crate::read_file::FileFormat;
+
+const GZIP_MAGIC: &[u8] = b"\x1F\x8B";
+const ZSTD_MAGIC: &[u8] = b"\x28\xB5\x2F\xFD";
+const BZIP2_MAGIC: &[u8] = b"BZh";
+const ZIP_MAGIC: &[u8] = b"PK\x03\x04";
+
+/// 표준 입력/출력을 뜻하는 경로
+pub const STDIO_PATH: &str = "-";
+
+/// 압축 형식
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    Gzip,
+    Zstd,
+    Bzip2,
+    Zip,
+}
+
+impl Compression {
+    /// 압축 형식을 나타내는 확장자(.gz, .zst, .bz2, .zip)로 판별
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_lowercase().as_str() {
+            "gz" | "gzip" => Some(Compression::Gzip),
+            "zst" | "zstd" => Some(Compression::Zstd),
+            "bz2" | "bzip2" => Some(Compression::Bzip2),
+            "zip" => Some(Compression::Zip),
+            _ => None,
+        }
+    }
+
+    /// 파일 앞부분의 매직 바이트로 판별
+    pub fn from_magic(bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(GZIP_MAGIC) {
+            Some(Compression::Gzip)
+        } else if bytes.starts_with(ZSTD_MAGIC) {
+            Some(Compression::Zstd)
+        } else if bytes.starts_with(BZIP2_MAGIC) {
+            Some(Compression::Bzip2)
+        } else if bytes.starts_with(ZIP_MAGIC) {
+            Some(Compression::Zip)
+        } else {
+            None
+        }
+    }
+
+    /// 경로의 마지막 확장자로 판별 (sales.csv.gz → Gzip)
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Option<Self> {
+        path.as_ref()
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(Compression::from_extension)
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Compression::Gzip => "gz",
+            Compression::Zstd => "zst",
+            Compression::Bzip2 => "bz2",
+            Compression::Zip => "zip",
+        }
+    }
+}
+
+impl fmt::Display for Compression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Compression::Gzip => "gzip",
+            Compression::Zstd => "zstd",
+            Compression::Bzip2 => "bzip2",
+            Compression::Zip => "zip",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Compression {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Compression::from_extension(s).ok_or_else(|| anyhow!("Unsupported compression: {}", s))
+    }
+}
+
+/// 압축을 푼 파일 내용과 안쪽 파일 이름 (sales.csv.gz → sales.csv, zip은 항목 이름)
+#[derive(Debug, Clone)]
+pub struct DecompressedFile {
+    pub name: String,
+    pub bytes: Vec<u8>,
+}
+
+impl DecompressedFile {
+    /// 안쪽 파일의 확장자 (소문자, 없으면 빈 문자열)
+    pub fn extension(&self) -> String {
+        Path::new(&self.name)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_lowercase)
+            .unwrap_or_default()
+    }
+}
+
+/// 확장자로 파일의 압축 형식 판별. 데이터 형식 확장자(.csv, .json, .xlsx 등)면 압축 파일로 보지 않고,
+/// 확장자가 없거나 모르는 확장자일 때만 매직 바이트로 판별
+pub fn detect_compression<P: AsRef<Path>>(file_path: P) -> Result<Option<Compression>> {
+    let path = file_path.as_ref();
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_lowercase)
+        .unwrap_or_default();
+
+    if let Some(compression) = Compression::from_extension(&extension) {
+        return Ok(Some(compression));
+    }
+
+    // A text file may start with "PK" or "BZh" by chance, and workbooks are zip containers themselves
+    if FileFormat::from_extension(&extension).is_some() {
+        return Ok(None);
+    }
+
+    let mut magic = [0u8; 4];
+    let mut file = File::open(path)?;
+    let read = read_up_to(&mut file, &mut magic)?;
+    Ok(Compression::from_magic(&magic[..read]))
+}
+
+/// 압축 파일을 끝까지 풀어서 메모리에 읽음. zip은 데이터 파일이 하나인 압축만 지원.
+/// gzip/zstd/bzip2는 open_decompressed로 풀면서 읽을 수 있음
+pub fn decompress_file<P: AsRef<Path>>(file_path: P, compression: Compression) -> Result<DecompressedFile> {
+    let path = file_path.as_ref();
+    decompress(BufReader::new(File::open(path)?), compression, &inner_name(path))
+}
+
+/// 압축을 푼 내용의 이름 (sales.csv.gz → sales.csv). 압축 확장자가 없으면 파일 이름 그대로
+pub fn inner_name<P: AsRef<Path>>(file_path: P) -> String {
+    let path = file_path.as_ref();
+    let file_name = path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+    match Compression::from_path(file_name) {
+        Some(_) => Path::new(file_name).file_stem().and_then(|stem| stem.to_str()).unwrap_or_default().to_string(),
+        None => file_name.to_string(),
+    }
+}
+
+/// 압축 파일을 열어 풀면서 읽는 리더 (gzip/zstd/bzip2)
+pub fn open_decompressed<P: AsRef<Path>>(file_path: P, compression: Compression) -> Result<Box<dyn Read>> {
+    decoder(BufReader::new(File::open(file_path)?), compression)
+}
+
+/// 읽는 대로 압축을 푸는 리더. zip은 항목이 압축 파일 전체를 빌려 쓰므로 decompress로 읽어야 함
+pub fn decoder<R: Read + 'static>(reader: R, compression: Compression) -> Result<Box<dyn Read>> {
+    let reader: Box<dyn Read> = match compression {
+        Compression::Gzip => Box::new(MultiGzDecoder::new(reader)),
+        Compression::Zstd => Box::new(zstd::Decoder::new(reader)?),
+        Compression::Bzip2 => Box::new(BzDecoder::new(reader)),
+        Compression::Zip => return Err(anyhow!("Zip archives cannot be decompressed as a stream")),
+    };
+    Ok(reader)
+}
+
+/// 압축된 내용을 끝까지 풀어서 메모리에 읽음 (zip, 표준 입력의 통합 문서 등). name은 압축을 푼 내용의 이름으로 쓰이며 zip은 항목 이름을 씀
+pub fn decompress<R: Read + Seek>(mut reader: R, compression: Compression, name: &str) -> Result<DecompressedFile> {
+    let mut bytes = Vec::new();
+    match compression {
+        Compression::Gzip => {
+            MultiGzDecoder::new(reader).read_to_end(&mut bytes)?;
+        }
+        Compression::Zstd => {
+            zstd::Decoder::new(&mut reader)?.read_to_end(&mut bytes)?;
+        }
+        Compression::Bzip2 => {
+            BzDecoder::new(reader).read_to_end(&mut bytes)?;
+        }
+        Compression::Zip => return read_single_zip_entry(reader),
+    }
+
+    Ok(DecompressedFile {
+        name: name.to_string(),
+        bytes,
+    })
+}
+
+fn read_single_zip_entry<R: Read + Seek>(reader: R) -> Result<DecompressedFile> {
+    let mut archive = ZipArchive::new(reader)?;
+
+    // Skip directories and the metadata folders macOS adds to archives
+    let entries: Vec<String> = archive
+        .file_names()
+        .filter(|name| !name.ends_with('/'))
+        .filter(|name| !name.starts_with("__MACOSX/"))
+        .filter(|name| !Path::new(name).file_name().and_then(|n| n.to_str()).unwrap_or("").starts_with('.'))
+        .map(str::to_string)
+        .collect();
+
+    let name = match entries.as_slice() {
+        [name] => name.clone(),
+        [] => return Err(anyhow!("Zip archive contains no files")),
+        _ => {
+            return Err(anyhow!(
+                "Zip archive must contain a single data file (found: {})",
+                entries.join(", ")
+            ));
+        }
+    };
+
+    let mut bytes = Vec::new();
+    archive.by_name(&name)?.read_to_end(&mut bytes)?;
+    Ok(DecompressedFile { name, bytes })
+}
+
+/// 지정한 형식으로 압축하며 쓰는 파일. 경로가 "-"이면 표준 출력에 씀.
+/// 모두 쓴 뒤 finish를 호출해야 압축이 마무리됨
+pub struct CompressedWriter {
+    inner: WriterKind,
+}
+
+type Sink = Box<dyn Write + Send>;
+
+enum WriterKind {
+    Plain(BufWriter<Sink>),
+    Gzip(GzEncoder<BufWriter<Sink>>),
+    Zstd(zstd::Encoder<'static, BufWriter<Sink>>),
+    Bzip2(BzEncoder<BufWriter<Sink>>),
+    // Zip needs to seek back to patch entry headers, so it only writes to files
+    Zip(ZipWriter<BufWriter<File>>),
+}
+
+impl CompressedWriter {
+    /// 파일(또는 표준 출력)을 열고 압축 형식에 맞는 인코더를 붙임. zip은 압축 확장자를 뗀 이름의 항목 하나를 만들고,
+    /// 남은 이름에 확장자가 없으면 extension을 붙임 (out.zip → out.csv)
+    pub fn create<P: AsRef<Path>>(output_path: P, compression: Option<Compression>, extension: &str) -> Result<Self> {
+        let path = output_path.as_ref();
+        let is_stdout = path == Path::new(STDIO_PATH);
+
+        // Zip needs a seekable file, so it gets its own writer instead of a sink
+        if compression == Some(Compression::Zip) && is_stdout {
+            return Err(anyhow!("Zip output cannot be written to stdout"));
+        }
+
+        let sink = || -> Result<BufWriter<Sink>> {
+            let sink: Sink = if is_stdout {
+                Box::new(io::stdout())
+            } else {
+                Box::new(File::create(path)?)
+            };
+            Ok(BufWriter::new(sink))
+        };
+
+        let inner = match compression {
+            None => WriterKind::Plain(sink()?),
+            Some(Compression::Gzip) => WriterKind::Gzip(GzEncoder::new(sink()?, flate2::Compression::default())),
+            Some(Compression::Zstd) => WriterKind::Zstd(zstd::Encoder::new(sink()?, 0)?),
+            Some(Compression::Bzip2) => WriterKind::Bzip2(BzEncoder::new(sink()?, bzip2::Compression::default())),
+            Some(Compression::Zip) => {
+                let mut writer = ZipWriter::new(BufWriter::new(File::create(path)?));
+                writer.start_file(zip_entry_name(path, extension), SimpleFileOptions::default())?;
+                WriterKind::Zip(writer)
+            }
+        };
+
+        Ok(Self { inner })
+    }
+
+    pub fn finish(self) -> Result<()> {
+        match self.inner {
+            WriterKind::Plain(mut sink) => sink.flush()?,
+            WriterKind::Gzip(encoder) => encoder.finish()?.flush()?,
+            WriterKind::Zstd(encoder) => encoder.finish()?.flush()?,
+            WriterKind::Bzip2(encoder) => encoder.finish()?.flush()?,
+            WriterKind::Zip(writer) => writer.finish()?.flush()?,
+        }
+        Ok(())
+    }
+}
+
+impl Write for CompressedWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match &mut self.inner {
+            WriterKind::Plain(w) => w.write(buf),
+            WriterKind::Gzip(w) => w.write(buf),
+            WriterKind::Zstd(w) => w.write(buf),
+            WriterKind::Bzip2(w) => w.write(buf),
+            WriterKind::Zip(w) => w.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match &mut self.inner {
+            WriterKind::Plain(w) => w.flush(),
+            WriterKind::Gzip(w) => w.flush(),
+            WriterKind::Zstd(w) => w.flush(),
+            WriterKind::Bzip2(w) => w.flush(),
+            WriterKind::Zip(w) => w.flush(),
+        }
+    }
+}
+
+/// zip 안에 만들 항목 이름. 압축 확장자를 떼고, 데이터 확장자가 없으면 붙임
+fn zip_entry_name(path: &Path, extension: &str) -> String {
+    let name = inner_name(path);
+    if Path::new(&name).extension().is_some() {
+        name
+    } else {
+        format!("{}.{}", name, extension)
+    }
+}
+
+fn read_up_to(reader: &mut impl Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..])? {
+            0 => break,
+            n => filled += n,
+        }
+    }
+    Ok(filled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_file::{self, CsvWriteOptions, DataSet};
+
+    fn sample() -> DataSet {
+        DataSet::new(
+            vec!["이름".to_string(), "점수".to_string(), "일자".to_string()],
+            (0..500).map(|i| vec![format!("학생{}", i), (i % 97).to_string(), format!("2024-03-{:02}", i % 28 + 1)]).collect(),
+            "sample".to_string(),
+        )
+    }
+
+    fn rows(dataset: &DataSet) -> Vec<Vec<String>> {
+        dataset.rows().collect()
+    }
+
+    #[test]
+    fn compressed_csv_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let original = sample();
+        for name in ["data.csv.gz", "data.csv.zst", "data.csv.bz2", "data.csv.zip"] {
+            let path = dir.path().join(name);
+            read_file::save_dataset_to_csv_with_options(&original, &path, &CsvWriteOptions::default()).unwrap();
+            assert_eq!(detect_compression(&path).unwrap(), Compression::from_path(&path), "{}", name);
+
+            let read = read_file::read_file(&path).unwrap();
+            assert_eq!(read.headers, original.headers, "{}", name);
+            assert_eq!(rows(&read), rows(&original), "{}", name);
+            assert_eq!(read.column("점수").unwrap().column_type(), original.column("점수").unwrap().column_type());
+        }
+    }
+
+    #[test]
+    fn compressed_file_without_suffix_is_recognised_by_magic_bytes() {
+        let dir = tempfile::tempdir().unwrap();
+        let original = sample();
+        let options = CsvWriteOptions { compression: Some(Compression::Gzip), ..CsvWriteOptions::default() };
+        let path = dir.path().join("export");
+        read_file::save_dataset_to_csv_with_options(&original, &path, &options).unwrap();
+
+        assert_eq!(detect_compression(&path).unwrap(), Some(Compression::Gzip));
+        let read = read_file::read_file_with_options(&path, &read_file::ReadOptions::default()).unwrap();
+        assert_eq!(rows(&read), rows(&original));
+    }
+
+    #[test]
+    fn data_files_starting_with_magic_bytes_are_not_treated_as_compressed() {
+        let dir = tempfile::tempdir().unwrap();
+        for (name, text) in [("codes.csv", "BZh,PK\n1,2\n"), ("zip.tsv", "PK\u{3}\u{4}\tname\n1\ta\n")] {
+            let path = dir.path().join(name);
+            std::fs::write(&path, text).unwrap();
+            assert_eq!(detect_compression(&path).unwrap(), None, "{}", name);
+            assert_eq!(read_file::read_file(&path).unwrap().row_count(), 1, "{}", name);
+        }
+    }
+
+    #[test]
+    fn zip_entries_keep_a_data_extension() {
+        let dir = tempfile::tempdir().unwrap();
+        let original = sample();
+        for (name, entry) in [("out.zip", "out.csv"), ("out.csv.zip", "out.csv"), ("out.tsv.zip", "out.tsv")] {
+            let path = dir.path().join(name);
+            read_file::save_dataset_to_csv_with_options(&original, &path, &CsvWriteOptions::default()).unwrap();
+            assert_eq!(decompress_file(&path, Compression::Zip).unwrap().name, entry);
+            assert_eq!(rows(&read_file::read_file(&path).unwrap()), rows(&original), "{}", name);
+        }
+    }
+
+    #[test]
+    fn inner_name_drops_only_the_compression_suffix() {
+        assert_eq!(inner_name("dir/sales.csv.gz"), "sales.csv");
+        assert_eq!(inner_name("sales.json.zst"), "sales.json");
+        assert_eq!(inner_name("export"), "export");
+    }
+}
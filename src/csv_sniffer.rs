@@ -0,0 +1,187 @@
+const DELIMITER_CANDIDATES: &[u8] = b",\t;|";
+const QUOTE_CANDIDATES: &[u8] = b"\"'";
+const MAX_SAMPLE_LINES: usize = 50;
+
+/// 파일 앞부분에서 추정한 CSV 형식
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CsvDialect {
+    pub delimiter: u8,
+    pub quote: u8,
+    pub has_headers: bool,
+}
+
+impl Default for CsvDialect {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            quote: b'"',
+            has_headers: true,
+        }
+    }
+}
+
+/// 구분자, 따옴표 문자, 헤더 여부 추정
+pub fn sniff(sample: &[u8]) -> CsvDialect {
+    let text = String::from_utf8_lossy(sample);
+    let mut lines: Vec<&str> = text.lines().filter(|line| !line.trim().is_empty()).collect();
+
+    // The sample may end in the middle of a line
+    if !sample.ends_with(b"\n") && lines.len() > 1 {
+        lines.pop();
+    }
+    lines.truncate(MAX_SAMPLE_LINES);
+
+    if lines.is_empty() {
+        return CsvDialect::default();
+    }
+
+    let quote = detect_quote(&lines);
+    let delimiter = detect_delimiter(&lines, quote);
+    let rows: Vec<Vec<String>> = lines.iter().map(|line| split_line(line, delimiter, quote)).collect();
+    let has_headers = detect_headers(&rows);
+
+    CsvDialect {
+        delimiter,
+        quote,
+        has_headers,
+    }
+}
+
+fn detect_quote(lines: &[&str]) -> u8 {
+    let mut best = (b'"', 0usize);
+
+    for &quote in QUOTE_CANDIDATES {
+        // Count quotes that open a field: at line start or right after a delimiter
+        let opening = lines
+            .iter()
+            .map(|line| {
+                let bytes = line.as_bytes();
+                (0..bytes.len())
+                    .filter(|&i| {
+                        bytes[i] == quote && (i == 0 || DELIMITER_CANDIDATES.contains(&bytes[i - 1]))
+                    })
+                    .count()
+            })
+            .sum::<usize>();
+
+        if opening > best.1 {
+            best = (quote, opening);
+        }
+    }
+
+    best.0
+}
+
+fn detect_delimiter(lines: &[&str], quote: u8) -> u8 {
+    let mut best = (b',', 0usize, 0usize);
+
+    for &delimiter in DELIMITER_CANDIDATES {
+        let counts: Vec<usize> = lines
+            .iter()
+            .map(|line| split_line(line, delimiter, quote).len())
+            .collect();
+
+        // Most common field count and how many lines agree with it
+        let mut mode = (1usize, 0usize);
+        for &count in &counts {
+            let agreeing = counts.iter().filter(|&&c| c == count).count();
+            if agreeing > mode.1 || (agreeing == mode.1 && count > mode.0) {
+                mode = (count, agreeing);
+            }
+        }
+
+        if mode.0 > 1 && (mode.1 > best.2 || (mode.1 == best.2 && mode.0 > best.1)) {
+            best = (delimiter, mode.0, mode.1);
+        }
+    }
+
+    best.0
+}
+
+fn detect_headers(rows: &[Vec<String>]) -> bool {
+    if rows.len() < 2 {
+        return true;
+    }
+
+    let header = &rows[0];
+    let body = &rows[1..];
+    let mut votes = 0i32;
+
+    for (col_idx, header_cell) in header.iter().enumerate() {
+        let cells: Vec<&str> = body
+            .iter()
+            .filter_map(|row| row.get(col_idx))
+            .map(|cell| cell.trim())
+            .filter(|cell| !cell.is_empty())
+            .collect();
+
+        if cells.is_empty() {
+            continue;
+        }
+
+        let header_cell = header_cell.trim();
+        if cells.iter().all(|cell| cell.parse::<f64>().is_ok()) {
+            // Numeric body under a non-numeric first row looks like a header
+            votes += if header_cell.parse::<f64>().is_ok() { -1 } else { 1 };
+        } else {
+            let length = cells[0].chars().count();
+            if cells.iter().all(|cell| cell.chars().count() == length) {
+                votes += if header_cell.chars().count() == length { -1 } else { 1 };
+            }
+        }
+    }
+
+    // Text-only files with varying lengths give no signal; most files have a header
+    votes >= 0
+}
+
+/// 따옴표를 고려해 한 줄을 필드로 분리
+fn split_line(line: &str, delimiter: u8, quote: u8) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = Vec::new();
+    let mut in_quotes = false;
+    let bytes = line.as_bytes();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let byte = bytes[i];
+        if byte == quote {
+            if in_quotes && bytes.get(i + 1) == Some(&quote) {
+                field.push(quote);
+                i += 1;
+            } else {
+                in_quotes = !in_quotes;
+            }
+        } else if byte == delimiter && !in_quotes {
+            fields.push(String::from_utf8_lossy(&field).to_string());
+            field.clear();
+        } else {
+            field.push(byte);
+        }
+        i += 1;
+    }
+
+    fields.push(String::from_utf8_lossy(&field).to_string());
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_delimiter_and_quote() {
+        let dialect = sniff(b"name;city;note\n'Kim';'Seoul';'a,b'\n'Lee';'Busan';'c,d'\n");
+        assert_eq!((dialect.delimiter, dialect.quote), (b';', b'\''));
+
+        assert_eq!(sniff(b"a\tb\tc\n1\t2\t3\n").delimiter, b'\t');
+        assert_eq!(sniff(b"a|b\n1|2\n3|4\n").delimiter, b'|');
+        assert_eq!(sniff(b"").delimiter, b',');
+    }
+
+    #[test]
+    fn detects_headers_from_types_of_the_first_row() {
+        assert!(sniff(b"name,score\nKim,90\nLee,85\n").has_headers);
+        assert!(!sniff(b"1,90\n2,85\n3,70\n").has_headers);
+    }
+}
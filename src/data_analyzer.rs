@@ -1,7 +1,12 @@
-use crate::read_file::DataSet;
+use crate::read_file::{DataSet, MalformedRow};
+use crate::schema::{self, ColumnSchema, SchemaOptions};
+use crate::number_format::{self, NumberFormatReport};
+use crate::dates::{self, DateFormatReport, DateStats};
+use crate::group_by::{self, Group};
+use crate::column::{parse_number_text, Column, MissingValueReport, Value};
 use anyhow::{Result, anyhow};
 use std::collections::HashMap;
-use statrs::statistics::{Statistics, OrderStatistics};
+use statrs::statistics::Statistics;
 use plotters::prelude::*;
 use rand::seq::SliceRandom;
 use rand::thread_rng;
@@ -15,14 +20,18 @@ pub struct BasicStats {
     pub min: f64,
     pub max: f64,
     pub count: usize,
+    pub missing: usize,
+    pub invalid: usize,
     pub q1: f64,
     pub q3: f64,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct FrequencyData {
     pub frequencies: HashMap<String, usize>,
     pub total_count: usize,
+    /// 값 종류가 상한을 넘어 빈도표에서 빠진 값의 개수 (배치 분석). 0이 아니면 남은 빈도는 하한
+    pub dropped: usize,
 }
 
 impl FrequencyData {
@@ -32,19 +41,250 @@ impl FrequencyData {
             .map(|(k, &v)| (k.clone(), v as f64 / self.total_count as f64))
             .collect()
     }
+
+    /// 값 하나를 빈도에 더함
+    pub fn add(&mut self, value: &str) {
+        match self.frequencies.get_mut(value) {
+            Some(count) => *count += 1,
+            None => {
+                self.frequencies.insert(value.to_string(), 1);
+            }
+        }
+        self.total_count += 1;
+    }
+
+    /// 빈도가 높은 limit개 값만 남기고 나머지는 dropped로 셈. 결측("")은 항상 남김
+    pub fn truncate(&mut self, limit: usize) {
+        if self.frequencies.len() <= limit {
+            return;
+        }
+        let mut entries: Vec<(String, usize)> = self.frequencies.drain().collect();
+        entries.sort_by(|a, b| {
+            b.0.is_empty()
+                .cmp(&a.0.is_empty())
+                .then_with(|| b.1.cmp(&a.1))
+                .then_with(|| a.0.cmp(&b.0))
+        });
+        self.dropped += entries[limit..].iter().map(|(_, count)| count).sum::<usize>();
+        entries.truncate(limit);
+        self.frequencies = entries.into_iter().collect();
+    }
+
+    pub fn is_truncated(&self) -> bool {
+        self.dropped > 0
+    }
+}
+
+/// 값을 하나씩 받아 기초통계량을 계산. 평균/분산은 Welford 방식으로 한 번에 계산하고
+/// 중앙값과 사분위수는 P² 알고리즘으로 추정 (값을 저장하지 않음)
+#[derive(Debug, Clone)]
+pub struct StreamingStats {
+    count: usize,
+    mean: f64,
+    m2: f64,
+    min: f64,
+    max: f64,
+    missing: usize,
+    invalid: usize,
+    q1: P2Quantile,
+    median: P2Quantile,
+    q3: P2Quantile,
+}
+
+impl Default for StreamingStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StreamingStats {
+    pub fn new() -> Self {
+        Self {
+            count: 0,
+            mean: 0.0,
+            m2: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+            missing: 0,
+            invalid: 0,
+            q1: P2Quantile::new(0.25),
+            median: P2Quantile::new(0.5),
+            q3: P2Quantile::new(0.75),
+        }
+    }
+
+    pub fn push(&mut self, value: f64) {
+        self.count += 1;
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (value - self.mean);
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        self.q1.push(value);
+        self.median.push(value);
+        self.q3.push(value);
+    }
+
+    pub fn push_missing(&mut self) {
+        self.missing += 1;
+    }
+
+    pub fn push_invalid(&mut self) {
+        self.invalid += 1;
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// 지금까지 받은 값의 기초통계량. 분산은 calculate_basic_stats와 같은 표본 분산
+    pub fn finish(&self) -> Result<BasicStats> {
+        if self.count == 0 {
+            return Err(anyhow!("Cannot calculate statistics for empty data"));
+        }
+
+        let variance = if self.count > 1 {
+            self.m2 / (self.count - 1) as f64
+        } else {
+            f64::NAN
+        };
+
+        Ok(BasicStats {
+            mean: self.mean,
+            median: self.median.estimate(),
+            std_dev: variance.sqrt(),
+            variance,
+            min: self.min,
+            max: self.max,
+            count: self.count,
+            missing: self.missing,
+            invalid: self.invalid,
+            q1: self.q1.estimate(),
+            q3: self.q3.estimate(),
+        })
+    }
+}
+
+/// P² 분위수 추정기 (Jain & Chlamtac, 1985). 마커 5개만 유지하며, 값이 5개 이하면 정확히 계산
+#[derive(Debug, Clone)]
+struct P2Quantile {
+    p: f64,
+    heights: Vec<f64>,
+    positions: [f64; 5],
+    desired: [f64; 5],
+    increments: [f64; 5],
+}
+
+impl P2Quantile {
+    fn new(p: f64) -> Self {
+        Self {
+            p,
+            heights: Vec::with_capacity(5),
+            positions: [0.0, 1.0, 2.0, 3.0, 4.0],
+            desired: [0.0, 2.0 * p, 4.0 * p, 2.0 + 2.0 * p, 4.0],
+            increments: [0.0, p / 2.0, p, (1.0 + p) / 2.0, 1.0],
+        }
+    }
+
+    fn push(&mut self, value: f64) {
+        if self.heights.len() < 5 {
+            self.heights.push(value);
+            self.heights.sort_by(f64::total_cmp);
+            return;
+        }
+
+        let q = &mut self.heights;
+        let k = if value < q[0] {
+            q[0] = value;
+            0
+        } else if value >= q[4] {
+            q[4] = value;
+            3
+        } else {
+            (0..4).find(|&i| value < q[i + 1]).unwrap_or(3)
+        };
+
+        for position in &mut self.positions[k + 1..] {
+            *position += 1.0;
+        }
+        for (desired, increment) in self.desired.iter_mut().zip(&self.increments) {
+            *desired += increment;
+        }
+
+        for i in 1..4 {
+            let n = &mut self.positions;
+            let d = self.desired[i] - n[i];
+            if (d >= 1.0 && n[i + 1] - n[i] > 1.0) || (d <= -1.0 && n[i - 1] - n[i] < -1.0) {
+                let d = d.signum();
+                let parabolic = q[i]
+                    + d / (n[i + 1] - n[i - 1])
+                        * ((n[i] - n[i - 1] + d) * (q[i + 1] - q[i]) / (n[i + 1] - n[i])
+                            + (n[i + 1] - n[i] - d) * (q[i] - q[i - 1]) / (n[i] - n[i - 1]));
+
+                q[i] = if q[i - 1] < parabolic && parabolic < q[i + 1] {
+                    parabolic
+                } else {
+                    // Fall back to linear interpolation toward the neighbouring marker
+                    let j = if d > 0.0 { i + 1 } else { i - 1 };
+                    q[i] + d * (q[j] - q[i]) / (n[j] - n[i])
+                };
+                n[i] += d;
+            }
+        }
+    }
+
+    fn estimate(&self) -> f64 {
+        if self.heights.len() < 5 {
+            return calculate_quartile(&self.heights, self.p);
+        }
+        self.heights[2]
+    }
+}
+
+/// 배치 분석에서 열 하나의 결과
+#[derive(Debug, Clone)]
+pub enum ColumnSummary {
+    Numeric(BasicStats),
+    Categorical(FrequencyData),
+    /// 모든 값이 결측인 열
+    Empty,
+}
+
+/// 배치 단위로 계산한 데이터셋 요약
+#[derive(Debug, Clone)]
+pub struct StreamingSummary {
+    pub file_path: String,
+    pub headers: Vec<String>,
+    pub row_count: usize,
+    pub batch_count: usize,
+    pub columns: Vec<ColumnSummary>,
+    /// 필드 개수가 헤더와 달라 채우거나 잘라낸 행
+    pub malformed_rows: Vec<MalformedRow>,
+    /// 서식 있는 숫자로 읽은 열
+    pub number_formats: Vec<NumberFormatReport>,
+    /// 날짜/시간으로 읽은 열
+    pub date_formats: Vec<DateFormatReport>,
+}
+
+#[derive(Debug)]
+enum ColumnAccumulator {
+    /// 아직 결측이 아닌 값을 보지 못한 열 (결측 개수만 셈)
+    Undecided(usize),
+    Numeric(Box<StreamingStats>),
+    Categorical(FrequencyData),
 }
 
 // Helper functions for statistical calculations
-fn calculate_median(sorted_data: &[f64]) -> f64 {
+pub(crate) fn calculate_median(sorted_data: &[f64]) -> f64 {
     let len = sorted_data.len();
-    if len % 2 == 0 {
+    if len.is_multiple_of(2) {
         (sorted_data[len / 2 - 1] + sorted_data[len / 2]) / 2.0
     } else {
         sorted_data[len / 2]
     }
 }
 
-fn calculate_quartile(sorted_data: &[f64], percentile: f64) -> f64 {
+pub(crate) fn calculate_quartile(sorted_data: &[f64], percentile: f64) -> f64 {
     let len = sorted_data.len();
     let index = percentile * (len - 1) as f64;
     let lower = index.floor() as usize;
@@ -58,11 +298,48 @@ fn calculate_quartile(sorted_data: &[f64], percentile: f64) -> f64 {
     }
 }
 
-pub struct DataAnalyzer;
+/// 배치 분석에서 열마다 빈도를 셀 값 종류의 기본 상한
+pub const DEFAULT_DISTINCT_LIMIT: usize = 100_000;
+
+#[derive(Debug, Clone)]
+pub struct DataAnalyzer {
+    schema_options: SchemaOptions,
+    distinct_limit: usize,
+}
+
+impl Default for DataAnalyzer {
+    fn default() -> Self {
+        Self::with_schema_options(SchemaOptions::default())
+    }
+}
 
 impl DataAnalyzer {
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// 열 종류 추론 기준(임계 비율, 직접 지정한 타입)을 바꾼 분석기
+    pub fn with_schema_options(schema_options: SchemaOptions) -> Self {
+        Self { schema_options, distinct_limit: DEFAULT_DISTINCT_LIMIT }
+    }
+
+    /// 배치 분석에서 열마다 빈도를 셀 값 종류의 상한을 바꿈
+    pub fn with_distinct_limit(mut self, limit: usize) -> Self {
+        self.distinct_limit = limit.max(1);
+        self
+    }
+
+    /// 열마다 종류(정수, 실수, 범주형, 식별자 등)와 신뢰도를 추론
+    pub fn infer_schema(&self, dataset: &DataSet) -> Vec<ColumnSchema> {
+        schema::infer_schema(dataset, &self.schema_options)
+    }
+
+    /// 숫자 열로 분석할 열의 값. 종류가 숫자가 아니거나 숫자 값이 없으면 None
+    pub fn numeric_values(&self, dataset: &DataSet, column_schema: &ColumnSchema) -> Option<Vec<f64>> {
+        if !column_schema.semantic_type.is_numeric() {
+            return None;
+        }
+        dataset.get_numeric_column(&column_schema.name).ok()
     }
 
     /// 기초통계량 계산 (평균, 중앙값, 표준편차 등)
@@ -72,7 +349,7 @@ impl DataAnalyzer {
         }
 
         let mut sorted_data = data.to_vec();
-        sorted_data.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        sorted_data.sort_by(f64::total_cmp);
 
         let mean = data.mean();
         let median = calculate_median(&sorted_data);
@@ -94,6 +371,8 @@ impl DataAnalyzer {
             min,
             max,
             count,
+            missing: 0,
+            invalid: 0,
             q1,
             q3,
         })
@@ -110,13 +389,210 @@ impl DataAnalyzer {
         FrequencyData {
             frequencies,
             total_count: data.len(),
+            dropped: 0,
         }
     }
 
     /// 특정 열의 기초통계량 계산
     pub fn analyze_column(&self, dataset: &DataSet, column_name: &str) -> Result<BasicStats> {
         let numeric_data = dataset.get_numeric_column(column_name)?;
-        self.calculate_basic_stats(&numeric_data)
+        let mut stats = self.calculate_basic_stats(&numeric_data)?;
+        let report = dataset.missing_value_report(column_name)?;
+        stats.missing = report.missing;
+        stats.invalid = report.invalid;
+        Ok(stats)
+    }
+
+    /// 배치를 차례로 받아 열별 기초통계량(숫자 열)과 빈도(그 외 열)를 한 번에 계산하며, 전체 데이터를 메모리에 올리지 않음.
+    /// 열의 종류는 결측이 아닌 값이 처음 나온 배치에서 정하고 바꾸지 않음: 숫자 열에서 나중에 나온 숫자가 아닌 값은
+    /// 오류 값으로, 범주형 열의 숫자는 값 그대로 셈. 빈도는 값 종류가 distinct_limit을 넘으면 자주 나온 값만 남김
+    pub fn analyze_batches<I>(&self, batches: I) -> Result<StreamingSummary>
+    where
+        I: IntoIterator<Item = Result<DataSet>>,
+    {
+        let mut summary = StreamingSummary {
+            file_path: String::new(),
+            headers: Vec::new(),
+            row_count: 0,
+            batch_count: 0,
+            columns: Vec::new(),
+            malformed_rows: Vec::new(),
+            number_formats: Vec::new(),
+            date_formats: Vec::new(),
+        };
+        let mut accumulators: Vec<ColumnAccumulator> = Vec::new();
+
+        for batch in batches {
+            let batch = batch?;
+            if summary.batch_count == 0 {
+                summary.file_path = batch.file_path.clone();
+                summary.headers = batch.headers.clone();
+                accumulators = batch.headers.iter().map(|_| ColumnAccumulator::Undecided(0)).collect();
+            } else if batch.headers != summary.headers {
+                return Err(anyhow!("Batch {} has different columns", summary.batch_count + 1));
+            }
+            summary.malformed_rows.extend(batch.load_report.malformed_rows.iter().cloned());
+            number_format::merge_reports(&mut summary.number_formats, &batch.load_report.number_formats);
+            dates::merge_reports(&mut summary.date_formats, &batch.load_report.date_formats);
+
+            for (header, accumulator) in batch.headers.iter().zip(accumulators.iter_mut()) {
+                let column = batch.column(header)?;
+                if let ColumnAccumulator::Undecided(missing) = *accumulator {
+                    if column.null_count() == column.len() {
+                        *accumulator = ColumnAccumulator::Undecided(missing + column.len());
+                        continue;
+                    }
+                    // Same rule as analyze_file, decided on the first batch with values
+                    let column_schema = schema::infer_column(header, column, &self.schema_options);
+                    *accumulator = if self.numeric_values(&batch, &column_schema).is_some() {
+                        let mut stats = StreamingStats::new();
+                        stats.missing = missing;
+                        ColumnAccumulator::Numeric(Box::new(stats))
+                    } else {
+                        let mut frequencies = FrequencyData::default();
+                        (0..missing).for_each(|_| frequencies.add(""));
+                        ColumnAccumulator::Categorical(frequencies)
+                    };
+                }
+
+                match accumulator {
+                    ColumnAccumulator::Numeric(stats) => {
+                        for value in column.values() {
+                            match value {
+                                Value::Null => stats.push_missing(),
+                                Value::Integer(i) => stats.push(i as f64),
+                                Value::Float(f) => stats.push(f),
+                                Value::String(s) => match parse_number_text(&s) {
+                                    Some(f) => stats.push(f),
+                                    None => stats.push_invalid(),
+                                },
+                                _ => stats.push_invalid(),
+                            }
+                        }
+                    }
+                    ColumnAccumulator::Categorical(frequencies) => {
+                        for i in 0..column.len() {
+                            frequencies.add(&column.get_string(i));
+                        }
+                        // Pruning only once the map doubles keeps the cost per value constant
+                        if frequencies.frequencies.len() > 2 * self.distinct_limit {
+                            frequencies.truncate(self.distinct_limit);
+                        }
+                    }
+                    ColumnAccumulator::Undecided(_) => unreachable!(),
+                }
+            }
+
+            summary.row_count += batch.row_count();
+            summary.batch_count += 1;
+        }
+
+        summary.columns = accumulators
+            .into_iter()
+            .map(|accumulator| match accumulator {
+                ColumnAccumulator::Numeric(stats) => match stats.finish() {
+                    Ok(stats) => ColumnSummary::Numeric(stats),
+                    Err(_) => ColumnSummary::Empty,
+                },
+                ColumnAccumulator::Categorical(mut frequencies) => {
+                    frequencies.truncate(self.distinct_limit);
+                    ColumnSummary::Categorical(frequencies)
+                }
+                ColumnAccumulator::Undecided(_) => ColumnSummary::Empty,
+            })
+            .collect();
+
+        Ok(summary)
+    }
+
+    /// 열마다 기초통계량(숫자 열) 또는 빈도(그 외 열)를 계산
+    pub fn summarize_columns(&self, dataset: &DataSet) -> Result<Vec<ColumnSummary>> {
+        let mut summaries = Vec::with_capacity(dataset.column_count());
+        for (header, column_schema) in dataset.headers.iter().zip(self.infer_schema(dataset)) {
+            let column = dataset.column(header)?;
+            let summary = if column.null_count() == column.len() {
+                ColumnSummary::Empty
+            } else if self.numeric_values(dataset, &column_schema).is_some() {
+                ColumnSummary::Numeric(self.analyze_column(dataset, header)?)
+            } else {
+                ColumnSummary::Categorical(self.analyze_column_frequency(dataset, header)?)
+            };
+            summaries.push(summary);
+        }
+        Ok(summaries)
+    }
+
+    /// 열별 요약을 열 하나당 한 행인 표로 만듦 (CSV 등으로 저장해 다른 도구에 넘길 때 사용)
+    pub fn summary_table(&self, headers: &[String], summaries: &[ColumnSummary]) -> DataSet {
+        let names = [
+            "column", "kind", "count", "missing", "invalid", "unique", "mean", "std_dev", "min", "q1", "median",
+            "q3", "max",
+        ];
+        let mut cells: Vec<Vec<Value>> = vec![Vec::with_capacity(headers.len()); names.len()];
+
+        for (header, summary) in headers.iter().zip(summaries) {
+            let mut row = vec![Value::String(header.clone())];
+            match summary {
+                ColumnSummary::Numeric(stats) => {
+                    row.push(Value::String("numeric".to_string()));
+                    row.extend([stats.count, stats.missing, stats.invalid].map(|n| Value::Integer(n as i64)));
+                    row.push(Value::Null);
+                    row.extend(
+                        [stats.mean, stats.std_dev, stats.min, stats.q1, stats.median, stats.q3, stats.max]
+                            .map(Value::Float),
+                    );
+                }
+                ColumnSummary::Categorical(freq_data) => {
+                    // Missing cells are counted under the empty string
+                    let missing = freq_data.frequencies.get("").copied().unwrap_or(0);
+                    let unique = freq_data.frequencies.len() - usize::from(missing > 0);
+                    row.push(Value::String("categorical".to_string()));
+                    row.extend([freq_data.total_count - missing, missing, 0].map(|n| Value::Integer(n as i64)));
+                    // The number of distinct values is unknown once the frequency table was cut down
+                    row.push(if freq_data.is_truncated() { Value::Null } else { Value::Integer(unique as i64) });
+                }
+                ColumnSummary::Empty => {
+                    row.push(Value::String("empty".to_string()));
+                }
+            }
+            row.resize(names.len(), Value::Null);
+
+            for (column, value) in cells.iter_mut().zip(row) {
+                column.push(value);
+            }
+        }
+
+        let columns = cells.into_iter().map(Column::from_values).collect();
+        let headers = names.iter().map(|name| name.to_string()).collect();
+        DataSet::from_columns(headers, columns, "summary".to_string())
+    }
+
+    /// 키 열로 묶은 그룹마다 숫자 열의 기초통계량. 숫자 값이 없는 그룹은 None
+    pub fn grouped_stats(&self, dataset: &DataSet, keys: &[String], column_name: &str) -> Result<Vec<(Group, Option<BasicStats>)>> {
+        let column = dataset.column(column_name)?;
+        if column.numeric_values().is_none() {
+            return Err(anyhow!("Column '{}' is not numeric ({})", column_name, column.column_type()));
+        }
+
+        group_by::group_rows(dataset, keys)?
+            .into_iter()
+            .map(|group| {
+                let values: Vec<f64> = group.rows.iter().filter_map(|&row| column.get(row).as_f64()).collect();
+                let stats = match self.calculate_basic_stats(&values) {
+                    Ok(mut stats) => {
+                        stats.missing = group.rows.len() - values.len();
+                        Some(stats)
+                    }
+                    Err(_) => None,
+                };
+                Ok((group, stats))
+            })
+            .collect()
+    }
+
+    /// 날짜/시간 열의 최솟값, 최댓값, 기간과 일/주/월별 개수
+    pub fn analyze_date_column(&self, dataset: &DataSet, column_name: &str) -> Result<DateStats> {
+        dates::date_stats(dataset.column(column_name)?)
     }
 
     /// 특정 열의 빈도 분석
@@ -132,7 +608,7 @@ impl DataAnalyzer {
         }
 
         let mut sorted_data = data.to_vec();
-        sorted_data.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        sorted_data.sort_by(f64::total_cmp);
 
         let stats = self.calculate_basic_stats(data)?;
 
@@ -161,27 +637,27 @@ impl DataAnalyzer {
         // Draw median line
         chart.draw_series(std::iter::once(PathElement::new(
             vec![(box_x - box_width/2.0, stats.median), (box_x + box_width/2.0, stats.median)],
-            &RED,
+            RED,
         )))?;
 
         // Draw whiskers
         chart.draw_series(std::iter::once(PathElement::new(
             vec![(box_x, stats.min), (box_x, stats.q1)],
-            &BLACK,
+            BLACK,
         )))?;
         chart.draw_series(std::iter::once(PathElement::new(
             vec![(box_x, stats.q3), (box_x, stats.max)],
-            &BLACK,
+            BLACK,
         )))?;
 
         // Draw whisker caps
         chart.draw_series(std::iter::once(PathElement::new(
             vec![(box_x - 0.1, stats.min), (box_x + 0.1, stats.min)],
-            &BLACK,
+            BLACK,
         )))?;
         chart.draw_series(std::iter::once(PathElement::new(
             vec![(box_x - 0.1, stats.max), (box_x + 0.1, stats.max)],
-            &BLACK,
+            BLACK,
         )))?;
 
         root.present()?;
@@ -196,7 +672,7 @@ impl DataAnalyzer {
         }
 
         let mut sorted_data = data.to_vec();
-        sorted_data.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        sorted_data.sort_by(f64::total_cmp);
 
         let n = sorted_data.len();
         let mut theoretical_quantiles = Vec::new();
@@ -248,7 +724,7 @@ impl DataAnalyzer {
         let max_val = x_max.max(y_max);
         chart.draw_series(std::iter::once(PathElement::new(
             vec![(min_val, min_val), (max_val, max_val)],
-            &RED,
+            RED,
         )))?;
 
         root.present()?;
@@ -314,16 +790,8 @@ impl DataAnalyzer {
         indices.shuffle(&mut rng);
         indices.truncate(sample_size);
 
-        let mut sampled_data = Vec::new();
-        for &index in &indices {
-            if let Some(row) = dataset.get_row(index) {
-                sampled_data.push(row.clone());
-            }
-        }
-
-        Ok(DataSet::new(
-            dataset.headers.clone(),
-            sampled_data,
+        Ok(dataset.take_rows(
+            &indices,
             format!("{}_sample_{}", dataset.file_path, sample_size),
         ))
     }
@@ -335,7 +803,7 @@ impl DataAnalyzer {
 
         // Group rows by strata
         for (index, stratum) in strata_data.iter().enumerate() {
-            strata_groups.entry(stratum.clone()).or_insert_with(Vec::new).push(index);
+            strata_groups.entry(stratum.clone()).or_default().push(index);
         }
 
         let total_strata = strata_groups.len();
@@ -349,16 +817,8 @@ impl DataAnalyzer {
             sampled_indices.extend(indices.into_iter().take(take_count));
         }
 
-        let mut sampled_data = Vec::new();
-        for &index in &sampled_indices {
-            if let Some(row) = dataset.get_row(index) {
-                sampled_data.push(row.clone());
-            }
-        }
-
-        Ok(DataSet::new(
-            dataset.headers.clone(),
-            sampled_data,
+        Ok(dataset.take_rows(
+            &sampled_indices,
             format!("{}_stratified_sample_{}", dataset.file_path, sample_size),
         ))
     }
@@ -369,7 +829,7 @@ impl DataAnalyzer {
         let a0 = -3.969683028665376e+01;
         let a1 = 2.209460984245205e+02;
         let a2 = -2.759285104469687e+02;
-        let a3 = 1.383577518672690e+02;
+        let a3 = 1.383_577_518_672_69e+02;
         let a4 = -3.066479806614716e+01;
         let a5 = 2.506628277459239e+00;
 
@@ -397,6 +857,112 @@ impl DataAnalyzer {
         println!("Rows: {}", dataset.row_count());
         println!("Columns: {}", dataset.column_count());
         println!("Headers: {:?}", dataset.headers);
+
+        let cell_errors = &dataset.load_report.cell_errors;
+        if !cell_errors.is_empty() {
+            println!("Cell errors (treated as missing): {}", cell_errors.len());
+            for error in cell_errors.iter().take(10) {
+                println!("  row {}, column '{}': {}", error.row + 1, error.column, error.error);
+            }
+            if cell_errors.len() > 10 {
+                println!("  ... and {} more cell errors", cell_errors.len() - 10);
+            }
+        }
+        self.print_malformed_rows(&dataset.load_report.malformed_rows);
+        self.print_number_formats(&dataset.load_report.number_formats);
+        self.print_date_formats(&dataset.load_report.date_formats);
+        self.print_schema(&self.infer_schema(dataset));
+        println!("========================");
+    }
+
+    /// 서식 있는 숫자로 읽은 열과 적용한 규칙 출력
+    fn print_number_formats(&self, reports: &[NumberFormatReport]) {
+        if reports.is_empty() {
+            return;
+        }
+        println!("Formatted numbers:");
+        for report in reports {
+            print!("  {}: {} ({} values converted", report.column, report.rules, report.converted);
+            if report.unparsed > 0 {
+                print!(", {} not numeric", report.unparsed);
+            }
+            println!(")");
+        }
+    }
+
+    /// 날짜/시간으로 읽은 열과 사용한 형식 출력
+    fn print_date_formats(&self, reports: &[DateFormatReport]) {
+        if reports.is_empty() {
+            return;
+        }
+        println!("Date columns:");
+        for report in reports {
+            print!("  {}: {} ({} values converted", report.column, report.format, report.converted);
+            if report.unparsed > 0 {
+                print!(", {} did not match, treated as missing", report.unparsed);
+            }
+            println!(")");
+        }
+    }
+
+    /// 열 종류 추론 결과 출력
+    pub fn print_schema(&self, schema: &[ColumnSchema]) {
+        println!("Column types (threshold {:.0}%):", self.schema_options.threshold * 100.0);
+        for column in schema {
+            if column.non_missing == 0 {
+                println!("  {}: empty", column.name);
+                continue;
+            }
+            let source = if column.overridden { ", from schema" } else { "" };
+            println!(
+                "  {}: {} ({:.1}% confidence, {} unique{})",
+                column.name,
+                column.semantic_type,
+                column.confidence * 100.0,
+                column.unique,
+                source
+            );
+        }
+    }
+
+    /// 필드 개수가 맞지 않아 채우거나 잘라낸 행 출력 (최대 10개)
+    fn print_malformed_rows(&self, malformed_rows: &[MalformedRow]) {
+        if malformed_rows.is_empty() {
+            return;
+        }
+        println!("Malformed rows (padded or truncated): {}", malformed_rows.len());
+        for malformed in malformed_rows.iter().take(10) {
+            println!(
+                "  line {}: {} fields, expected {}",
+                malformed.line, malformed.fields, malformed.expected
+            );
+        }
+        if malformed_rows.len() > 10 {
+            println!("  ... and {} more malformed rows", malformed_rows.len() - 10);
+        }
+    }
+
+    /// 배치 분석 요약 정보 출력
+    pub fn print_streaming_summary(&self, summary: &StreamingSummary) {
+        println!("=== Dataset Summary (streamed) ===");
+        println!("File: {}", summary.file_path);
+        println!("Rows: {}", summary.row_count);
+        println!("Batches: {}", summary.batch_count);
+        println!("Columns: {}", summary.headers.len());
+        println!("Headers: {:?}", summary.headers);
+        self.print_malformed_rows(&summary.malformed_rows);
+        self.print_number_formats(&summary.number_formats);
+        self.print_date_formats(&summary.date_formats);
+        let truncated: Vec<&str> = summary
+            .headers
+            .iter()
+            .zip(&summary.columns)
+            .filter(|(_, column)| matches!(column, ColumnSummary::Categorical(freq_data) if freq_data.is_truncated()))
+            .map(|(header, _)| header.as_str())
+            .collect();
+        if !truncated.is_empty() {
+            println!("Frequency tables cut to the most frequent values: {}", truncated.join(", "));
+        }
         println!("========================");
     }
 
@@ -404,6 +970,8 @@ impl DataAnalyzer {
     pub fn print_basic_stats(&self, stats: &BasicStats, column_name: &str) {
         println!("=== Basic Statistics for '{}' ===", column_name);
         println!("Count: {}", stats.count);
+        println!("Missing: {}", stats.missing);
+        println!("Invalid: {}", stats.invalid);
         println!("Mean: {:.4}", stats.mean);
         println!("Median: {:.4}", stats.median);
         println!("Standard Deviation: {:.4}", stats.std_dev);
@@ -415,6 +983,63 @@ impl DataAnalyzer {
         println!("=====================================");
     }
 
+    /// 날짜 통계 출력. 일별 개수는 날짜가 31일 이하일 때만, 주/월별 개수는 최대 12개까지 표시
+    pub fn print_date_stats(&self, stats: &DateStats, column_name: &str) {
+        let format = if stats.has_time { "%Y-%m-%d %H:%M:%S" } else { "%Y-%m-%d" };
+        println!("=== Date Statistics for '{}' ===", column_name);
+        println!("Count: {}", stats.count);
+        println!("Missing: {}", stats.missing);
+        println!("Earliest: {}", stats.min.format(format));
+        println!("Latest: {}", stats.max.format(format));
+        println!("Span: {}", format_span(stats.span));
+
+        if stats.per_day.len() <= 31 {
+            println!("Per day:");
+            for (day, count) in &stats.per_day {
+                println!("  {}: {}", day, count);
+            }
+        } else {
+            println!("Days with data: {}", stats.per_day.len());
+        }
+        print_recent_counts("Per week", stats.per_week.iter().map(|((year, week), count)| (format!("{}-W{:02}", year, week), *count)));
+        print_recent_counts("Per month", stats.per_month.iter().map(|((year, month), count)| (format!("{}-{:02}", year, month), *count)));
+        println!("=====================================");
+    }
+
+    /// 그룹별 기초통계량 출력 (그룹 하나에 한 줄)
+    pub fn print_grouped_stats(&self, grouped: &[(Group, Option<BasicStats>)], keys: &[String], column_name: &str) {
+        println!("=== Basic Statistics for '{}' by {} ===", column_name, keys.join(", "));
+        for (group, stats) in grouped {
+            let label = group.label();
+            match stats {
+                Some(stats) => println!(
+                    "{}: count {}, missing {}, mean {:.4}, std {:.4}, min {:.4}, q1 {:.4}, median {:.4}, q3 {:.4}, max {:.4}",
+                    label, stats.count, stats.missing, stats.mean, stats.std_dev, stats.min, stats.q1, stats.median,
+                    stats.q3, stats.max
+                ),
+                None => println!("{}: no values ({} rows)", label, group.rows.len()),
+            }
+        }
+        println!("=====================================");
+    }
+
+    /// 결측/오류 값 보고서 출력
+    pub fn print_missing_value_report(&self, report: &MissingValueReport, column_name: &str) {
+        println!("=== Missing Values for '{}' ===", column_name);
+        println!("Valid: {}", report.valid);
+        println!("Missing: {}", report.missing);
+        println!("Invalid: {}", report.invalid);
+
+        for cell in report.invalid_cells.iter().take(20) {
+            println!("  row {}: {:?}", cell.row + 1, cell.value);
+        }
+
+        if report.invalid_cells.len() > 20 {
+            println!("  ... and {} more invalid cells", report.invalid_cells.len() - 20);
+        }
+        println!("=====================================");
+    }
+
     /// 빈도 결과 출력
     pub fn print_frequency_data(&self, freq_data: &FrequencyData, column_name: &str) {
         println!("=== Frequency Analysis for '{}' ===", column_name);
@@ -431,6 +1056,143 @@ impl DataAnalyzer {
         if sorted_freq.len() > 10 {
             println!("... and {} more unique values", sorted_freq.len() - 10);
         }
+        if freq_data.is_truncated() {
+            println!(
+                "Too many distinct values: kept the {} most frequent, {} values not counted individually (counts are lower bounds)",
+                freq_data.frequencies.len(),
+                freq_data.dropped
+            );
+        }
         println!("=====================================");
     }
 }
+
+/// 기간을 "N days HH:MM:SS" 형식으로 표시
+fn format_span(span: chrono::Duration) -> String {
+    let seconds = span.num_seconds();
+    let days = seconds / 86_400;
+    let rest = seconds % 86_400;
+    if rest == 0 {
+        format!("{} days", days)
+    } else {
+        format!("{} days {:02}:{:02}:{:02}", days, rest / 3600, rest % 3600 / 60, rest % 60)
+    }
+}
+
+// Only the latest periods are listed so long ranges stay readable
+fn print_recent_counts(label: &str, counts: impl ExactSizeIterator<Item = (String, usize)>) {
+    let total = counts.len();
+    println!("{}:", label);
+    if total > 12 {
+        println!("  ... {} earlier periods", total - 12);
+    }
+    for (period, count) in counts.skip(total.saturating_sub(12)) {
+        println!("  {}: {}", period, count);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dataset(headers: &[&str], rows: &[Vec<String>]) -> DataSet {
+        DataSet::new(headers.iter().map(|h| h.to_string()).collect(), rows.to_vec(), "test".to_string())
+    }
+
+    fn rows_with(cells: &[&str]) -> Vec<Vec<String>> {
+        cells.iter().enumerate().map(|(i, cell)| vec![i.to_string(), cell.to_string()]).collect()
+    }
+
+    #[test]
+    fn non_finite_cells_are_invalid_not_numbers() {
+        let data = dataset(&["id", "v"], &rows_with(&["1", "2", "3", "NaN", "4", "5", "inf", "6", "7", "8"]));
+
+        let stats = DataAnalyzer::new().analyze_column(&data, "v").unwrap();
+
+        assert_eq!((stats.count, stats.invalid), (8, 2));
+        assert_eq!((stats.min, stats.max), (1.0, 8.0));
+        assert_eq!(stats.mean, 4.5);
+    }
+
+    fn batches(headers: &[&str], rows: &[Vec<String>], size: usize) -> Vec<Result<DataSet>> {
+        rows.chunks(size).map(|chunk| Ok(dataset(headers, chunk))).collect()
+    }
+
+    #[test]
+    fn streamed_stats_match_in_memory_stats() {
+        // A skewed, shuffled-looking sequence so the quantile markers have work to do
+        let cells: Vec<String> = (0..2000u64).map(|i| ((i * 7919) % 1000).pow(2).to_string()).collect();
+        let cells: Vec<&str> = cells.iter().map(String::as_str).collect();
+        let rows = rows_with(&cells);
+        let analyzer = DataAnalyzer::new();
+
+        let exact = analyzer.analyze_column(&dataset(&["id", "v"], &rows), "v").unwrap();
+        let summary = analyzer.analyze_batches(batches(&["id", "v"], &rows, 128)).unwrap();
+        let ColumnSummary::Numeric(streamed) = &summary.columns[1] else {
+            panic!("expected a numeric summary, got {:?}", summary.columns[1]);
+        };
+
+        assert_eq!((summary.row_count, summary.batch_count), (2000, 16));
+        assert_eq!((streamed.count, streamed.min, streamed.max), (exact.count, exact.min, exact.max));
+        assert!((streamed.mean - exact.mean).abs() < 1e-6 * exact.mean);
+        assert!((streamed.std_dev - exact.std_dev).abs() < 1e-6 * exact.std_dev);
+        // P² only estimates the quantiles; allow 2% of the range
+        let tolerance = (exact.max - exact.min) * 0.02;
+        for (streamed, exact) in [(streamed.q1, exact.q1), (streamed.median, exact.median), (streamed.q3, exact.q3)] {
+            assert!((streamed - exact).abs() < tolerance, "{} vs {}", streamed, exact);
+        }
+    }
+
+    #[test]
+    fn streamed_non_finite_cells_are_invalid() {
+        let rows = rows_with(&["1", "2", "3", "4", "5", "6", "7", "8", "9", "NaN", " 11 ", "inf"]);
+
+        let summary = DataAnalyzer::new().analyze_batches(batches(&["id", "v"], &rows, 5)).unwrap();
+
+        let ColumnSummary::Numeric(stats) = &summary.columns[1] else {
+            panic!("expected a numeric summary, got {:?}", summary.columns[1]);
+        };
+        assert_eq!((stats.count, stats.invalid), (10, 2));
+        assert_eq!(stats.max, 11.0);
+    }
+
+    #[test]
+    fn truncate_keeps_most_frequent_values_and_missing() {
+        let mut frequencies = FrequencyData::default();
+        for value in ["a", "a", "a", "b", "b", "", "c", "d"] {
+            frequencies.add(value);
+        }
+
+        frequencies.truncate(3);
+
+        assert_eq!(frequencies.frequencies.len(), 3);
+        assert_eq!((frequencies.frequencies[""], frequencies.frequencies["a"], frequencies.frequencies["b"]), (1, 3, 2));
+        assert_eq!((frequencies.dropped, frequencies.total_count), (2, 8));
+        assert!(frequencies.is_truncated());
+    }
+
+    #[test]
+    fn streamed_frequencies_stay_within_the_distinct_limit() {
+        // Every other row is a frequent value; the rest are all different
+        let cells: Vec<String> =
+            (0..1000).map(|i| if i % 2 == 0 { format!("common{}", i % 6) } else { format!("rare{}", i) }).collect();
+        let cells: Vec<&str> = cells.iter().map(String::as_str).collect();
+        let rows = rows_with(&cells);
+
+        let analyzer = DataAnalyzer::new().with_distinct_limit(10);
+        let summary = analyzer.analyze_batches(batches(&["id", "v"], &rows, 100)).unwrap();
+
+        let ColumnSummary::Categorical(frequencies) = &summary.columns[1] else {
+            panic!("expected a categorical summary, got {:?}", summary.columns[1]);
+        };
+        assert_eq!(frequencies.frequencies.len(), 10);
+        assert_eq!(frequencies.total_count, 1000);
+        for common in ["common0", "common2", "common4"] {
+            assert!(frequencies.frequencies[common] > 100, "{}", common);
+        }
+        assert!(frequencies.is_truncated());
+
+        let table = analyzer.summary_table(&summary.headers, &summary.columns);
+        assert!(table.column("unique").unwrap().is_null(1));
+    }
+}
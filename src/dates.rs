@@ -0,0 +1,427 @@
+use std::collections::{BTreeMap, HashMap};
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta};
+use crate::column::{self, Column, ColumnData, ColumnType, Value};
+use crate::read_file::DataSet;
+
+/// Unix 시각으로 볼 초 단위 정수 범위 (2000-01-01 ~ 2100-01-01)
+const UNIX_SECONDS_RANGE: std::ops::RangeInclusive<i64> = 946_684_800..=4_102_444_800;
+
+/// 정수 열을 Unix 시각으로 볼 수 있는 열 이름 힌트
+const TIME_NAME_HINTS: &[&str] = &["time", "date", "_at", "시각", "시간", "일시", "날짜"];
+
+/// 날짜/시간 해석 방식
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DateFormat {
+    /// chrono strftime 형식 (예: "%d/%m/%Y", "%Y%m%d %H%M")
+    Pattern(String),
+    /// Unix 시각 (초)
+    UnixSeconds,
+    /// Unix 시각 (밀리초)
+    UnixMillis,
+    /// 시간대가 붙은 RFC 3339 (2024-03-01T10:00:00+09:00, ...Z)
+    Rfc3339,
+}
+
+impl DateFormat {
+    pub fn parse(text: &str) -> Self {
+        match text.trim().to_lowercase().as_str() {
+            "unix" | "unix_s" | "epoch" => DateFormat::UnixSeconds,
+            "unix_ms" | "epoch_ms" => DateFormat::UnixMillis,
+            "rfc3339" | "iso8601" => DateFormat::Rfc3339,
+            _ => DateFormat::Pattern(text.to_string()),
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            DateFormat::Pattern(pattern) => format!("'{}'", pattern),
+            DateFormat::UnixSeconds => "unix seconds".to_string(),
+            DateFormat::UnixMillis => "unix milliseconds".to_string(),
+            DateFormat::Rfc3339 => "RFC 3339 with offset".to_string(),
+        }
+    }
+}
+
+/// 날짜/시간 읽기 옵션. 자주 쓰는 형식(2024-03-01, 2024.03.01, 2024년 3월 1일 등)은 지정하지 않아도 인식
+#[derive(Debug, Clone)]
+pub struct DateOptions {
+    /// 열 이름별로 지정한 형식
+    pub formats: HashMap<String, DateFormat>,
+    /// Unix 시각과 시간대가 붙은 값을 변환할 시간대 (기본 UTC)
+    pub timezone: FixedOffset,
+}
+
+impl Default for DateOptions {
+    fn default() -> Self {
+        Self {
+            formats: HashMap::new(),
+            timezone: FixedOffset::east_opt(0).unwrap(),
+        }
+    }
+}
+
+/// "UTC", "KST", "Asia/Seoul", "+09:00", "-0500" 형식의 시간대 해석
+pub fn parse_timezone(text: &str) -> Result<FixedOffset> {
+    let text = text.trim();
+    let seconds = match text.to_uppercase().as_str() {
+        "UTC" | "GMT" | "Z" => 0,
+        "KST" | "ASIA/SEOUL" => 9 * 3600,
+        "JST" | "ASIA/TOKYO" => 9 * 3600,
+        _ => {
+            let (sign, rest) = match text.strip_prefix('+') {
+                Some(rest) => (1, rest),
+                None => (-1, text.strip_prefix('-').ok_or_else(|| anyhow!("Invalid timezone: {}", text))?),
+            };
+            let digits: String = rest.chars().filter(|c| *c != ':').collect();
+            let (hours, minutes) = match digits.len() {
+                1 | 2 => (digits.parse::<i32>()?, 0),
+                4 => (digits[..2].parse::<i32>()?, digits[2..].parse::<i32>()?),
+                _ => return Err(anyhow!("Invalid timezone: {}", text)),
+            };
+            sign * (hours * 3600 + minutes * 60)
+        }
+    };
+    FixedOffset::east_opt(seconds).ok_or_else(|| anyhow!("Invalid timezone: {}", text))
+}
+
+/// 날짜/시간으로 읽은 열과 사용한 형식
+#[derive(Debug, Clone)]
+pub struct DateFormatReport {
+    pub column: String,
+    pub format: String,
+    pub converted: usize,
+    /// 형식에 맞지 않아 결측 처리한 셀 수
+    pub unparsed: usize,
+}
+
+/// 지정한 형식이 있는 열, 시간대가 붙은 날짜/시간 문자열 열, 이름이 시각을 뜻하는 Unix 시각 정수 열을
+/// 날짜/시간 열로 바꿈. 지정한 열이 없으면 오류
+pub fn apply_date_options(dataset: &mut DataSet, options: &DateOptions) -> Result<Vec<DateFormatReport>> {
+    let mut unknown: Vec<&str> = options
+        .formats
+        .keys()
+        .filter(|name| !dataset.headers.contains(name))
+        .map(String::as_str)
+        .collect();
+    if !unknown.is_empty() {
+        unknown.sort();
+        return Err(anyhow!("Date format given for columns not in the data: {}", unknown.join(", ")));
+    }
+
+    let mut reports = Vec::new();
+    for (header, column) in dataset.headers.iter().zip(dataset.columns.iter_mut()) {
+        let format = match options.formats.get(header) {
+            Some(format) => format.clone(),
+            None => match detect_format(header, column) {
+                Some(format) => format,
+                None => continue,
+            },
+        };
+
+        let mut unparsed = 0;
+        let values: Vec<Value> = column
+            .values()
+            .map(|value| {
+                if value.is_null() {
+                    return Value::Null;
+                }
+                let parsed = parse_with_format(&value, &format, options.timezone);
+                if parsed.is_null() {
+                    unparsed += 1;
+                }
+                parsed
+            })
+            .collect();
+
+        let converted = values.iter().filter(|value| !value.is_null()).count();
+        let column_type = if values.iter().any(|value| matches!(value, Value::DateTime(_))) {
+            ColumnType::DateTime
+        } else {
+            ColumnType::Date
+        };
+        *column = Column::from_values_as(values, column_type);
+        reports.push(DateFormatReport {
+            column: header.clone(),
+            format: format.describe(),
+            converted,
+            unparsed,
+        });
+    }
+
+    Ok(reports)
+}
+
+fn detect_format(header: &str, column: &Column) -> Option<DateFormat> {
+    match column.data() {
+        ColumnData::String(cells) => {
+            let present: Vec<&String> = cells.iter().enumerate().filter(|(i, _)| !column.is_null(*i)).map(|(_, c)| c).collect();
+            let all_rfc3339 = !present.is_empty() && present.iter().all(|cell| DateTime::parse_from_rfc3339(cell.trim()).is_ok());
+            all_rfc3339.then_some(DateFormat::Rfc3339)
+        }
+        ColumnData::Integer(_) => {
+            let name = header.to_lowercase();
+            let hinted = name == "ts" || name.ends_with("_ts") || TIME_NAME_HINTS.iter().any(|hint| name.contains(hint));
+            let values: Vec<i64> = column.values().filter_map(|value| match value {
+                Value::Integer(i) => Some(i),
+                _ => None,
+            }).collect();
+            if !hinted || values.is_empty() {
+                return None;
+            }
+            if values.iter().all(|v| UNIX_SECONDS_RANGE.contains(v)) {
+                Some(DateFormat::UnixSeconds)
+            } else if values.iter().all(|v| UNIX_SECONDS_RANGE.contains(&(v / 1000))) {
+                Some(DateFormat::UnixMillis)
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+/// 값 하나를 지정한 형식으로 해석. 형식에 맞지 않으면 Value::Null
+pub fn parse_with_format(value: &Value, format: &DateFormat, timezone: FixedOffset) -> Value {
+    let local = |datetime: DateTime<FixedOffset>| Value::DateTime(datetime.with_timezone(&timezone).naive_local());
+    match (format, value) {
+        // Already parsed (e.g. by the automatic detection or an earlier file) values stay as they are
+        (_, Value::Date(_) | Value::DateTime(_)) => value.clone(),
+        (DateFormat::UnixSeconds, Value::Integer(seconds)) => {
+            DateTime::from_timestamp(*seconds, 0).map(|dt| local(dt.fixed_offset())).unwrap_or(Value::Null)
+        }
+        (DateFormat::UnixMillis, Value::Integer(millis)) => {
+            DateTime::from_timestamp_millis(*millis).map(|dt| local(dt.fixed_offset())).unwrap_or(Value::Null)
+        }
+        (DateFormat::UnixSeconds | DateFormat::UnixMillis, Value::String(text)) => match text.trim().parse::<i64>() {
+            Ok(number) => parse_with_format(&Value::Integer(number), format, timezone),
+            Err(_) => Value::Null,
+        },
+        (DateFormat::Rfc3339, value) => DateTime::parse_from_rfc3339(value.to_string().trim())
+            .map(local)
+            .unwrap_or(Value::Null),
+        (DateFormat::Pattern(pattern), value) => {
+            let text = value.to_string();
+            let text = text.trim();
+            if let Ok(datetime) = NaiveDateTime::parse_from_str(text, pattern) {
+                Value::DateTime(datetime)
+            } else if let Ok(datetime) = DateTime::parse_from_str(text, pattern) {
+                local(datetime)
+            } else if let Ok(date) = NaiveDate::parse_from_str(text, pattern) {
+                Value::Date(date)
+            } else {
+                Value::Null
+            }
+        }
+        _ => Value::Null,
+    }
+}
+
+/// 같은 열의 보고를 하나로 합침 (여러 파일이나 배치를 읽은 경우)
+pub fn merge_reports(reports: &mut Vec<DateFormatReport>, others: &[DateFormatReport]) {
+    for other in others {
+        match reports.iter_mut().find(|report| report.column == other.column) {
+            Some(report) => {
+                report.converted += other.converted;
+                report.unparsed += other.unparsed;
+            }
+            None => reports.push(other.clone()),
+        }
+    }
+}
+
+/// 범위 조건에 쓰는 날짜 또는 날짜/시간 텍스트 해석. 날짜만 주면 하루 전체를 뜻함
+pub fn parse_date_bound(text: &str) -> Result<Value> {
+    let text = text.trim();
+    [ColumnType::DateTime, ColumnType::Date]
+        .into_iter()
+        .map(|column_type| column::parse_as(text, column_type))
+        .find(|value| !value.is_null())
+        .ok_or_else(|| anyhow!("Invalid date: {}", text))
+}
+
+pub(crate) fn as_datetime(value: &Value) -> Option<NaiveDateTime> {
+    match value {
+        Value::Date(date) => Some(date.and_time(NaiveTime::MIN)),
+        Value::DateTime(datetime) => Some(*datetime),
+        _ => None,
+    }
+}
+
+/// 날짜/시간 열이 start 이상, end 이하인 행만 남김. end가 날짜뿐이면 그날 전체를 포함
+pub fn filter_date_range(dataset: &DataSet, column_name: &str, start: Option<&Value>, end: Option<&Value>) -> Result<DataSet> {
+    let column = dataset.column(column_name)?;
+    if !matches!(column.column_type(), ColumnType::Date | ColumnType::DateTime) {
+        return Err(anyhow!("Column '{}' is not a date column ({})", column_name, column.column_type()));
+    }
+
+    let start = start.and_then(as_datetime);
+    let end_exclusive = end.and_then(|end| match end {
+        Value::Date(date) => date.succ_opt().map(|next| next.and_time(NaiveTime::MIN)),
+        other => as_datetime(other).map(|datetime| datetime + TimeDelta::nanoseconds(1)),
+    });
+
+    let indices: Vec<usize> = column
+        .values()
+        .enumerate()
+        .filter_map(|(row, value)| {
+            let datetime = as_datetime(&value)?;
+            let after_start = start.is_none_or(|start| datetime >= start);
+            let before_end = end_exclusive.is_none_or(|end| datetime < end);
+            (after_start && before_end).then_some(row)
+        })
+        .collect();
+
+    let mut filtered = dataset.take_rows(&indices, dataset.file_path.clone());
+    filtered.load_report = dataset.load_report.clone();
+    Ok(filtered)
+}
+
+/// 날짜/시간 열 요약
+#[derive(Debug, Clone)]
+pub struct DateStats {
+    pub count: usize,
+    pub missing: usize,
+    pub min: NaiveDateTime,
+    pub max: NaiveDateTime,
+    pub span: Duration,
+    /// 시각이 있는 열이면 true (요약에 시각까지 표시)
+    pub has_time: bool,
+    pub per_day: BTreeMap<NaiveDate, usize>,
+    /// ISO 주 (연도, 주차)별 개수
+    pub per_week: BTreeMap<(i32, u32), usize>,
+    /// (연도, 월)별 개수
+    pub per_month: BTreeMap<(i32, u32), usize>,
+}
+
+/// 날짜/시간 열의 최솟값, 최댓값, 기간과 일/주/월별 개수 계산
+pub fn date_stats(column: &Column) -> Result<DateStats> {
+    let has_time = match column.column_type() {
+        ColumnType::Date => false,
+        ColumnType::DateTime => true,
+        other => return Err(anyhow!("Expected a date column, found {}", other)),
+    };
+
+    let datetimes: Vec<NaiveDateTime> = column.values().filter_map(|value| as_datetime(&value)).collect();
+    let (Some(&min), Some(&max)) = (datetimes.iter().min(), datetimes.iter().max()) else {
+        return Err(anyhow!("Cannot calculate statistics for empty data"));
+    };
+
+    let mut per_day = BTreeMap::new();
+    let mut per_week = BTreeMap::new();
+    let mut per_month = BTreeMap::new();
+    for datetime in &datetimes {
+        let date = datetime.date();
+        let week = date.iso_week();
+        *per_day.entry(date).or_insert(0) += 1;
+        *per_week.entry((week.year(), week.week())).or_insert(0) += 1;
+        *per_month.entry((date.year(), date.month())).or_insert(0) += 1;
+    }
+
+    Ok(DateStats {
+        count: datetimes.len(),
+        missing: column.null_count(),
+        min,
+        max,
+        span: max - min,
+        has_time,
+        per_day,
+        per_week,
+        per_month,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dataset(headers: &[&str], rows: &[&[&str]]) -> DataSet {
+        DataSet::new(
+            headers.iter().map(|h| h.to_string()).collect(),
+            rows.iter().map(|row| row.iter().map(|cell| cell.to_string()).collect()).collect(),
+            "test".to_string(),
+        )
+    }
+
+    fn column(dataset: &DataSet, name: &str) -> Vec<String> {
+        let column = dataset.column(name).unwrap();
+        (0..dataset.row_count()).map(|i| column.get_string(i)).collect()
+    }
+
+    #[test]
+    fn timezones_are_parsed() {
+        assert_eq!(parse_timezone("UTC").unwrap().local_minus_utc(), 0);
+        assert_eq!(parse_timezone("Asia/Seoul").unwrap().local_minus_utc(), 9 * 3600);
+        assert_eq!(parse_timezone("+09:30").unwrap().local_minus_utc(), 9 * 3600 + 30 * 60);
+        assert_eq!(parse_timezone("-0500").unwrap().local_minus_utc(), -5 * 3600);
+        assert!(parse_timezone("Mars/Olympus").is_err());
+        assert!(parse_timezone("+123").is_err());
+    }
+
+    #[test]
+    fn given_formats_convert_columns_and_count_unparsed_cells() {
+        let mut data = dataset(
+            &["day", "ts"],
+            &[&["01/03/2024", "1709251200"], &["31/13/2024", "1709254800"], &["", "x"]],
+        );
+        let options = DateOptions {
+            formats: HashMap::from([
+                ("day".to_string(), DateFormat::parse("%d/%m/%Y")),
+                ("ts".to_string(), DateFormat::parse("unix")),
+            ]),
+            timezone: parse_timezone("+09:00").unwrap(),
+        };
+        let mut reports = apply_date_options(&mut data, &options).unwrap();
+        reports.sort_by(|a, b| a.column.cmp(&b.column));
+
+        assert_eq!(data.column("day").unwrap().column_type(), ColumnType::Date);
+        assert_eq!(column(&data, "day")[0], "2024-03-01");
+        assert_eq!(data.column("ts").unwrap().column_type(), ColumnType::DateTime);
+        assert_eq!(column(&data, "ts")[0], "2024-03-01 09:00:00");
+        let counts: Vec<(usize, usize)> = reports.iter().map(|report| (report.converted, report.unparsed)).collect();
+        // The empty cell is missing, not unparsed
+        assert_eq!(counts, vec![(1, 1), (2, 1)]);
+
+        let unknown = DateOptions {
+            formats: HashMap::from([("없는열".to_string(), DateFormat::Rfc3339)]),
+            ..DateOptions::default()
+        };
+        assert!(apply_date_options(&mut data, &unknown).is_err());
+    }
+
+    #[test]
+    fn date_range_includes_the_whole_end_day_and_skips_missing() {
+        let data = dataset(
+            &["when", "n"],
+            &[
+                &["2024-02-29 23:59:59", "1"],
+                &["2024-03-01 00:00:00", "2"],
+                &["2024-03-02 18:30:00", "3"],
+                &["", "4"],
+                &["2024-03-03 00:00:00", "5"],
+            ],
+        );
+        let start = parse_date_bound("2024-03-01").unwrap();
+        let end = parse_date_bound("2024-03-02").unwrap();
+        let filtered = filter_date_range(&data, "when", Some(&start), Some(&end)).unwrap();
+        assert_eq!(column(&filtered, "n"), vec!["2", "3"]);
+
+        let open = filter_date_range(&data, "when", Some(&end), None).unwrap();
+        assert_eq!(column(&open, "n"), vec!["3", "5"]);
+
+        assert!(parse_date_bound("내일").is_err());
+        assert!(filter_date_range(&data, "n", Some(&start), None).is_err());
+    }
+
+    #[test]
+    fn stats_group_dates_by_day_week_and_month() {
+        let data = dataset(&["day"], &[&["2024-01-31"], &["2024-02-01"], &["2024-02-01"], &[""], &["2024-02-05"]]);
+        let stats = date_stats(data.column("day").unwrap()).unwrap();
+
+        assert_eq!((stats.count, stats.missing, stats.has_time), (4, 1, false));
+        assert_eq!(stats.span, Duration::days(5));
+        assert_eq!(stats.per_day[&NaiveDate::from_ymd_opt(2024, 2, 1).unwrap()], 2);
+        assert_eq!(stats.per_month, BTreeMap::from([((2024, 1), 1), ((2024, 2), 3)]));
+        assert_eq!(stats.per_week, BTreeMap::from([((2024, 5), 3), ((2024, 6), 1)]));
+    }
+}
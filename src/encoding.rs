@@ -0,0 +1,172 @@
+use std::fmt;
+use std::str::FromStr;
+use anyhow::{Result, anyhow};
+use encoding_rs::{EUC_KR, UTF_16BE, UTF_16LE, UTF_8};
+
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+const UTF16LE_BOM: &[u8] = b"\xFF\xFE";
+const UTF16BE_BOM: &[u8] = b"\xFE\xFF";
+
+/// 텍스트 파일 인코딩
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextEncoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    Cp949,
+    EucKr,
+}
+
+impl fmt::Display for TextEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            TextEncoding::Utf8 => "UTF-8",
+            TextEncoding::Utf16Le => "UTF-16LE",
+            TextEncoding::Utf16Be => "UTF-16BE",
+            TextEncoding::Cp949 => "CP949",
+            TextEncoding::EucKr => "EUC-KR",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for TextEncoding {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().replace(['-', '_'], "").as_str() {
+            "utf8" => Ok(TextEncoding::Utf8),
+            "utf16le" | "utf16" => Ok(TextEncoding::Utf16Le),
+            "utf16be" => Ok(TextEncoding::Utf16Be),
+            "cp949" | "ms949" | "uhc" | "windows949" => Ok(TextEncoding::Cp949),
+            "euckr" => Ok(TextEncoding::EucKr),
+            _ => Err(anyhow!("Unsupported encoding: {}", s)),
+        }
+    }
+}
+
+/// BOM과 바이트 패턴으로 인코딩 추정
+pub fn detect_encoding(bytes: &[u8]) -> TextEncoding {
+    if bytes.starts_with(UTF8_BOM) {
+        return TextEncoding::Utf8;
+    }
+    if bytes.starts_with(UTF16LE_BOM) {
+        return TextEncoding::Utf16Le;
+    }
+    if bytes.starts_with(UTF16BE_BOM) {
+        return TextEncoding::Utf16Be;
+    }
+    if let Some(encoding) = detect_utf16_without_bom(bytes) {
+        return encoding;
+    }
+    if std::str::from_utf8(bytes).is_ok() {
+        return TextEncoding::Utf8;
+    }
+
+    match EUC_KR.decode_without_bom_handling_and_without_replacement(bytes) {
+        Some(_) if is_ks_x_1001_only(bytes) => TextEncoding::EucKr,
+        Some(_) => TextEncoding::Cp949,
+        // Nothing fits; decode as UTF-8 with replacement characters
+        None => TextEncoding::Utf8,
+    }
+}
+
+/// 바이트를 문자열로 디코딩. encoding이 None이면 자동 추정
+pub fn decode(bytes: &[u8], encoding: Option<TextEncoding>) -> (String, TextEncoding) {
+    let encoding = encoding.unwrap_or_else(|| detect_encoding(bytes));
+    let decoder = match encoding {
+        TextEncoding::Utf8 => UTF_8,
+        TextEncoding::Utf16Le => UTF_16LE,
+        TextEncoding::Utf16Be => UTF_16BE,
+        // encoding_rs implements EUC-KR as its CP949 superset
+        TextEncoding::Cp949 | TextEncoding::EucKr => EUC_KR,
+    };
+
+    let (text, _) = decoder.decode_with_bom_removal(bytes);
+    (text.into_owned(), encoding)
+}
+
+/// CSV 필드 하나를 디코딩. 구분자와 따옴표는 ASCII라 CP949 두 바이트 문자 안에 나오지 않으므로
+/// 필드 단위로 나눠 디코딩해도 됨 (UTF-16은 불가)
+pub fn decode_field(field: &[u8], encoding: TextEncoding) -> String {
+    match encoding {
+        TextEncoding::Cp949 | TextEncoding::EucKr => EUC_KR.decode_without_bom_handling(field).0.into_owned(),
+        _ => String::from_utf8_lossy(field).into_owned(),
+    }
+}
+
+// UTF-16 text without a BOM has a NUL byte in every other position for ASCII content
+fn detect_utf16_without_bom(bytes: &[u8]) -> Option<TextEncoding> {
+    let sample = &bytes[..bytes.len().min(4096) & !1];
+    if sample.len() < 4 {
+        return None;
+    }
+
+    let pairs = sample.len() / 2;
+    let even_zeros = sample.iter().step_by(2).filter(|&&b| b == 0).count();
+    let odd_zeros = sample.iter().skip(1).step_by(2).filter(|&&b| b == 0).count();
+
+    if odd_zeros * 10 > pairs * 3 && even_zeros == 0 {
+        Some(TextEncoding::Utf16Le)
+    } else if even_zeros * 10 > pairs * 3 && odd_zeros == 0 {
+        Some(TextEncoding::Utf16Be)
+    } else {
+        None
+    }
+}
+
+// CP949 extends EUC-KR with lead/trail bytes outside 0xA1..=0xFE
+fn is_ks_x_1001_only(bytes: &[u8]) -> bool {
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] < 0x80 {
+            i += 1;
+            continue;
+        }
+        let lead = bytes[i];
+        let trail = bytes.get(i + 1).copied().unwrap_or(0);
+        if !(0xA1..=0xFE).contains(&lead) || !(0xA1..=0xFE).contains(&trail) {
+            return false;
+        }
+        i += 2;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utf16le(text: &str, bom: bool) -> Vec<u8> {
+        let prefix = if bom { UTF16LE_BOM.to_vec() } else { Vec::new() };
+        prefix.into_iter().chain(text.encode_utf16().flat_map(u16::to_le_bytes)).collect()
+    }
+
+    #[test]
+    fn detects_korean_legacy_encodings() {
+        let (euc_kr, _, _) = EUC_KR.encode("이름,점수\n김철수,90\n");
+        assert_eq!(detect_encoding(&euc_kr), TextEncoding::EucKr);
+
+        // "똠" exists only in CP949's extended range
+        let (cp949, _, _) = EUC_KR.encode("이름\n똠방각하\n");
+        assert_eq!(detect_encoding(&cp949), TextEncoding::Cp949);
+        assert_eq!(decode(&cp949, None).0, "이름\n똠방각하\n");
+    }
+
+    #[test]
+    fn detects_and_strips_byte_order_marks() {
+        let utf8 = [UTF8_BOM, "이름,점수\n".as_bytes()].concat();
+        assert_eq!(decode(&utf8, None), ("이름,점수\n".to_string(), TextEncoding::Utf8));
+
+        assert_eq!(decode(&utf16le("name,score\n", true), None), ("name,score\n".to_string(), TextEncoding::Utf16Le));
+        assert_eq!(detect_encoding(&utf16le("name,score\n1,2\n", false)), TextEncoding::Utf16Le);
+    }
+
+    #[test]
+    fn parses_encoding_names() {
+        assert_eq!("ms949".parse::<TextEncoding>().unwrap(), TextEncoding::Cp949);
+        assert_eq!("EUC-KR".parse::<TextEncoding>().unwrap(), TextEncoding::EucKr);
+        assert_eq!("utf_16be".parse::<TextEncoding>().unwrap(), TextEncoding::Utf16Be);
+        assert!("latin1".parse::<TextEncoding>().is_err());
+    }
+}
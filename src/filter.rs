@@ -0,0 +1,602 @@
+use std::cmp::Ordering;
+use std::fmt;
+use anyhow::{Result, anyhow};
+use chrono::{NaiveDate, NaiveDateTime};
+use regex::Regex;
+use crate::column::{parse_number_text, Column, ColumnType, Value};
+use crate::dates;
+use crate::read_file::DataSet;
+
+/// 비교 연산자
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl CompareOp {
+    fn from_symbol(symbol: &str) -> Option<Self> {
+        match symbol {
+            "=" | "==" => Some(CompareOp::Eq),
+            "!=" | "<>" => Some(CompareOp::Ne),
+            "<" => Some(CompareOp::Lt),
+            "<=" => Some(CompareOp::Le),
+            ">" => Some(CompareOp::Gt),
+            ">=" => Some(CompareOp::Ge),
+            _ => None,
+        }
+    }
+
+    fn matches(self, ordering: Ordering) -> bool {
+        match self {
+            CompareOp::Eq => ordering == Ordering::Equal,
+            CompareOp::Ne => ordering != Ordering::Equal,
+            CompareOp::Lt => ordering == Ordering::Less,
+            CompareOp::Le => ordering != Ordering::Greater,
+            CompareOp::Gt => ordering == Ordering::Greater,
+            CompareOp::Ge => ordering != Ordering::Less,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Compare { column: String, op: CompareOp, value: String },
+    In { column: String, values: Vec<String> },
+    Between { column: String, low: String, high: String },
+    IsNull { column: String },
+    Contains { column: String, text: String },
+    Matches { column: String, regex: Regex },
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+}
+
+/// 행 필터 식. 예: `도시 = 서울 and 점수 >= 80`, `등급 in ('A', 'B')`, `비고 is not null`,
+/// `이름 contains '김'`, `코드 matches '^K\d+'`, `주문일 between 2024-03-01 and 2024-03-31`.
+/// 열 이름에 공백이 있으면 큰따옴표로, 공백이 있는 값은 작은따옴표로 감쌈.
+/// 결측 값과의 비교는 참도 거짓도 아니며(not을 붙여도 마찬가지), 조건이 참인 행만 남김
+#[derive(Debug, Clone)]
+pub struct Filter {
+    text: String,
+    expr: Expr,
+}
+
+impl Filter {
+    pub fn parse(text: &str) -> Result<Self> {
+        let tokens = tokenize(text)?;
+        if tokens.is_empty() {
+            return Err(anyhow!("Filter expression is empty"));
+        }
+        let mut parser = Parser { tokens, pos: 0 };
+        let expr = parser.parse_or()?;
+        if let Some(token) = parser.peek() {
+            return Err(anyhow!("Unexpected {} in filter", token));
+        }
+        Ok(Self {
+            text: text.trim().to_string(),
+            expr,
+        })
+    }
+
+    /// 조건에 맞는 행 번호 (extract_subset에 그대로 넘길 수 있음)
+    pub fn matching_rows(&self, dataset: &DataSet) -> Result<Vec<usize>> {
+        let predicate = resolve(&self.expr, dataset)?;
+        Ok((0..dataset.row_count()).filter(|&row| predicate.evaluate(row) == Some(true)).collect())
+    }
+}
+
+impl fmt::Display for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+/// 조건에 맞는 행만 남긴 데이터셋
+pub fn filter_dataset(dataset: &DataSet, filter: &Filter) -> Result<DataSet> {
+    let rows = filter.matching_rows(dataset)?;
+    let mut filtered = dataset.take_rows(&rows, dataset.file_path.clone());
+    filtered.load_report = dataset.load_report.clone();
+    Ok(filtered)
+}
+
+/// 식 문자열을 해석해 조건에 맞는 행만 남김
+pub fn filter_rows(dataset: &DataSet, expression: &str) -> Result<DataSet> {
+    filter_dataset(dataset, &Filter::parse(expression)?)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// 따옴표 없는 단어 (열 이름, 값, 키워드)
+    Word(String),
+    /// 큰따옴표로 감싼 열 이름 또는 값
+    Quoted(String),
+    /// 작은따옴표로 감싼 값
+    Text(String),
+    Op(String),
+    LParen,
+    RParen,
+    Comma,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Word(word) => write!(f, "'{}'", word),
+            Token::Quoted(text) => write!(f, "\"{}\"", text),
+            Token::Text(text) => write!(f, "'{}'", text),
+            Token::Op(op) => write!(f, "'{}'", op),
+            Token::LParen => write!(f, "'('"),
+            Token::RParen => write!(f, "')'"),
+            Token::Comma => write!(f, "','"),
+        }
+    }
+}
+
+const OPERATOR_CHARS: &str = "=!<>&|";
+
+fn tokenize(text: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' | ')' | ',' => {
+                chars.next();
+                tokens.push(match c {
+                    '(' => Token::LParen,
+                    ')' => Token::RParen,
+                    _ => Token::Comma,
+                });
+            }
+            '\'' | '"' => {
+                chars.next();
+                let mut quoted = String::new();
+                loop {
+                    match chars.next() {
+                        // A doubled quote stands for the quote character itself ('O''Brien')
+                        Some(q) if q == c && chars.peek() == Some(&c) => {
+                            chars.next();
+                            quoted.push(c);
+                        }
+                        Some(q) if q == c => break,
+                        Some(other) => quoted.push(other),
+                        None => return Err(anyhow!("Unterminated quote in filter: {}", text)),
+                    }
+                }
+                tokens.push(if c == '"' { Token::Quoted(quoted) } else { Token::Text(quoted) });
+            }
+            c if OPERATOR_CHARS.contains(c) => {
+                let mut op = String::new();
+                while let Some(&next) = chars.peek() {
+                    if !OPERATOR_CHARS.contains(next) || op.len() == 2 {
+                        break;
+                    }
+                    op.push(next);
+                    chars.next();
+                }
+                if !matches!(op.as_str(), "=" | "==" | "!=" | "<>" | "<" | "<=" | ">" | ">=" | "&&" | "||" | "!") {
+                    return Err(anyhow!("Unknown operator '{}' in filter", op));
+                }
+                tokens.push(Token::Op(op));
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(&next) = chars.peek() {
+                    if next.is_whitespace() || "(),'\"".contains(next) || OPERATOR_CHARS.contains(next) {
+                        break;
+                    }
+                    word.push(next);
+                    chars.next();
+                }
+                tokens.push(Token::Word(word));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+const KEYWORDS: &[&str] = &["and", "or", "not", "in", "is", "null", "contains", "matches", "between"];
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn at_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Word(word)) if word.eq_ignore_ascii_case(keyword))
+    }
+
+    fn at_op(&self, op: &str) -> bool {
+        matches!(self.peek(), Some(Token::Op(o)) if o == op)
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<()> {
+        if self.at_keyword(keyword) {
+            self.pos += 1;
+            return Ok(());
+        }
+        Err(anyhow!("Expected '{}' in filter, found {}", keyword, self.describe_next()))
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<()> {
+        if self.peek() == Some(&expected) {
+            self.pos += 1;
+            return Ok(());
+        }
+        Err(anyhow!("Expected {} in filter, found {}", expected, self.describe_next()))
+    }
+
+    fn describe_next(&self) -> String {
+        self.peek().map(Token::to_string).unwrap_or_else(|| "end of expression".to_string())
+    }
+
+    fn parse_or(&mut self) -> Result<Expr> {
+        let mut left = self.parse_and()?;
+        while self.at_keyword("or") || self.at_op("||") {
+            self.pos += 1;
+            left = Expr::Or(Box::new(left), Box::new(self.parse_and()?));
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr> {
+        let mut left = self.parse_unary()?;
+        while self.at_keyword("and") || self.at_op("&&") {
+            self.pos += 1;
+            left = Expr::And(Box::new(left), Box::new(self.parse_unary()?));
+        }
+        Ok(left)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr> {
+        if self.at_keyword("not") || self.at_op("!") {
+            self.pos += 1;
+            return Ok(Expr::Not(Box::new(self.parse_unary()?)));
+        }
+        if self.peek() == Some(&Token::LParen) {
+            self.pos += 1;
+            let expr = self.parse_or()?;
+            self.expect(Token::RParen)?;
+            return Ok(expr);
+        }
+        self.parse_predicate()
+    }
+
+    fn parse_predicate(&mut self) -> Result<Expr> {
+        let column = match self.next() {
+            Some(Token::Word(word)) if !KEYWORDS.iter().any(|k| word.eq_ignore_ascii_case(k)) => word,
+            Some(Token::Quoted(name)) => name,
+            Some(token) => return Err(anyhow!("Expected a column name in filter, found {}", token)),
+            None => return Err(anyhow!("Expected a column name in filter, found end of expression")),
+        };
+
+        if let Some(Token::Op(op)) = self.peek()
+            && let Some(op) = CompareOp::from_symbol(op)
+        {
+            self.pos += 1;
+            let value = self.parse_value()?;
+            return Ok(Expr::Compare { column, op, value });
+        }
+
+        if self.at_keyword("is") {
+            self.pos += 1;
+            let negated = self.at_keyword("not");
+            if negated {
+                self.pos += 1;
+            }
+            self.expect_keyword("null")?;
+            return Ok(negate(Expr::IsNull { column }, negated));
+        }
+
+        let negated = self.at_keyword("not");
+        if negated {
+            self.pos += 1;
+        }
+        let expr = if self.at_keyword("in") {
+            self.pos += 1;
+            self.expect(Token::LParen)?;
+            let mut values = vec![self.parse_value()?];
+            while self.peek() == Some(&Token::Comma) {
+                self.pos += 1;
+                values.push(self.parse_value()?);
+            }
+            self.expect(Token::RParen)?;
+            Expr::In { column, values }
+        } else if self.at_keyword("contains") {
+            self.pos += 1;
+            Expr::Contains { column, text: self.parse_value()? }
+        } else if self.at_keyword("matches") {
+            self.pos += 1;
+            let pattern = self.parse_value()?;
+            let regex = Regex::new(&pattern).map_err(|e| anyhow!("Invalid regular expression '{}': {}", pattern, e))?;
+            Expr::Matches { column, regex }
+        } else if self.at_keyword("between") {
+            self.pos += 1;
+            let low = self.parse_value()?;
+            self.expect_keyword("and")?;
+            let high = self.parse_value()?;
+            Expr::Between { column, low, high }
+        } else {
+            return Err(anyhow!(
+                "Expected a comparison, in, is null, contains, matches or between after '{}', found {}",
+                column,
+                self.describe_next()
+            ));
+        };
+        Ok(negate(expr, negated))
+    }
+
+    fn parse_value(&mut self) -> Result<String> {
+        match self.next() {
+            Some(Token::Word(word) | Token::Quoted(word) | Token::Text(word)) => Ok(word),
+            Some(token) => Err(anyhow!("Expected a value in filter, found {}", token)),
+            None => Err(anyhow!("Expected a value in filter, found end of expression")),
+        }
+    }
+}
+
+fn negate(expr: Expr, negated: bool) -> Expr {
+    if negated { Expr::Not(Box::new(expr)) } else { expr }
+}
+
+/// 열 타입에 맞게 변환한 비교 값
+#[derive(Debug, Clone)]
+enum Operand {
+    Number(f64),
+    Text(String),
+    Bool(bool),
+    DateTime(NaiveDateTime),
+    /// 시각 없이 준 날짜. 날짜/시간 열에서는 그날 전체와 같다고 봄
+    Day(NaiveDate),
+}
+
+enum Predicate<'a> {
+    Compare(&'a Column, CompareOp, Operand),
+    In(&'a Column, Vec<Operand>),
+    Between(&'a Column, Operand, Operand),
+    IsNull(&'a Column),
+    Contains(&'a Column, &'a str),
+    Matches(&'a Column, &'a Regex),
+    Not(Box<Predicate<'a>>),
+    And(Box<Predicate<'a>>, Box<Predicate<'a>>),
+    Or(Box<Predicate<'a>>, Box<Predicate<'a>>),
+}
+
+impl Predicate<'_> {
+    /// 행 하나의 결과. 결측 값과 비교하면 None
+    fn evaluate(&self, row: usize) -> Option<bool> {
+        match self {
+            Predicate::Compare(column, op, operand) => compare(&column.get(row), operand).map(|ordering| op.matches(ordering)),
+            Predicate::In(column, operands) => {
+                let value = column.get(row);
+                if value.is_null() {
+                    return None;
+                }
+                Some(operands.iter().any(|operand| compare(&value, operand) == Some(Ordering::Equal)))
+            }
+            Predicate::Between(column, low, high) => {
+                let value = column.get(row);
+                let above_low = compare(&value, low)? != Ordering::Less;
+                let below_high = compare(&value, high)? != Ordering::Greater;
+                Some(above_low && below_high)
+            }
+            Predicate::IsNull(column) => Some(column.is_null(row)),
+            Predicate::Contains(column, text) => match column.get(row) {
+                Value::Null => None,
+                value => Some(value.to_string().contains(text)),
+            },
+            Predicate::Matches(column, regex) => match column.get(row) {
+                Value::Null => None,
+                value => Some(regex.is_match(&value.to_string())),
+            },
+            Predicate::Not(inner) => inner.evaluate(row).map(|result| !result),
+            Predicate::And(left, right) => match (left.evaluate(row), right.evaluate(row)) {
+                (Some(false), _) | (_, Some(false)) => Some(false),
+                (Some(true), Some(true)) => Some(true),
+                _ => None,
+            },
+            Predicate::Or(left, right) => match (left.evaluate(row), right.evaluate(row)) {
+                (Some(true), _) | (_, Some(true)) => Some(true),
+                (Some(false), Some(false)) => Some(false),
+                _ => None,
+            },
+        }
+    }
+}
+
+// Column names and literal types are checked once up front so a typo fails before any row is read
+fn resolve<'a>(expr: &'a Expr, dataset: &'a DataSet) -> Result<Predicate<'a>> {
+    let operand = |column: &Column, name: &str, text: &str| to_operand(column.column_type(), name, text);
+    Ok(match expr {
+        Expr::Compare { column, op, value } => {
+            let data = dataset.column(column)?;
+            if !matches!(op, CompareOp::Eq | CompareOp::Ne) {
+                check_numeric_text(data, column, value)?;
+            }
+            Predicate::Compare(data, *op, operand(data, column, value)?)
+        }
+        Expr::In { column, values } => {
+            let data = dataset.column(column)?;
+            let operands = values.iter().map(|value| operand(data, column, value)).collect::<Result<_>>()?;
+            Predicate::In(data, operands)
+        }
+        Expr::Between { column, low, high } => {
+            let data = dataset.column(column)?;
+            check_numeric_text(data, column, low)?;
+            check_numeric_text(data, column, high)?;
+            Predicate::Between(data, operand(data, column, low)?, operand(data, column, high)?)
+        }
+        Expr::IsNull { column } => Predicate::IsNull(dataset.column(column)?),
+        Expr::Contains { column, text } => Predicate::Contains(dataset.column(column)?, text),
+        Expr::Matches { column, regex } => Predicate::Matches(dataset.column(column)?, regex),
+        Expr::Not(inner) => Predicate::Not(Box::new(resolve(inner, dataset)?)),
+        Expr::And(left, right) => Predicate::And(Box::new(resolve(left, dataset)?), Box::new(resolve(right, dataset)?)),
+        Expr::Or(left, right) => Predicate::Or(Box::new(resolve(left, dataset)?), Box::new(resolve(right, dataset)?)),
+    })
+}
+
+/// 문자열 열을 숫자 크기로 비교할 때, 숫자가 아닌 값이 섞여 있으면 문자열 순서로 섞어 비교하지 않고 오류
+fn check_numeric_text(column: &Column, name: &str, text: &str) -> Result<()> {
+    if column.column_type() != ColumnType::String || parse_number_text(text).is_none() {
+        return Ok(());
+    }
+    let bad = (0..column.len()).find(|&row| !column.is_null(row) && column.get(row).as_number().is_none());
+    match bad {
+        Some(row) => Err(anyhow!(
+            "Column '{}' holds text such as '{}' (row {}), so it cannot be compared with the number {}",
+            name,
+            column.get_string(row),
+            row + 1,
+            text.trim()
+        )),
+        None => Ok(()),
+    }
+}
+
+fn to_operand(column_type: ColumnType, column: &str, text: &str) -> Result<Operand> {
+    match column_type {
+        ColumnType::Integer | ColumnType::Float => text
+            .trim()
+            .parse()
+            .map(Operand::Number)
+            .map_err(|_| anyhow!("Column '{}' is numeric, but '{}' is not a number", column, text)),
+        ColumnType::Boolean => match text.trim().to_lowercase().as_str() {
+            "true" => Ok(Operand::Bool(true)),
+            "false" => Ok(Operand::Bool(false)),
+            _ => Err(anyhow!("Column '{}' is boolean, but '{}' is not true or false", column, text)),
+        },
+        ColumnType::Date | ColumnType::DateTime => match dates::parse_date_bound(text) {
+            Ok(Value::Date(date)) => Ok(Operand::Day(date)),
+            Ok(Value::DateTime(datetime)) => Ok(Operand::DateTime(datetime)),
+            _ => Err(anyhow!("Column '{}' holds dates, but '{}' is not a date", column, text)),
+        },
+        ColumnType::String => Ok(Operand::Text(text.to_string())),
+    }
+}
+
+fn compare(value: &Value, operand: &Operand) -> Option<Ordering> {
+    match (value, operand) {
+        (Value::Null, _) => None,
+        // Numbers in a text column (one bad cell is enough to make it text) still compare as numbers
+        (Value::String(text), Operand::Text(other)) => match (value.as_number(), parse_number_text(other)) {
+            (Some(number), Some(other)) => number.partial_cmp(&other),
+            _ => Some(text.as_str().cmp(other)),
+        },
+        (Value::Boolean(b), Operand::Bool(other)) => Some(b.cmp(other)),
+        (Value::Date(_) | Value::DateTime(_), Operand::DateTime(other)) => dates::as_datetime(value).map(|v| v.cmp(other)),
+        (Value::Date(_) | Value::DateTime(_), Operand::Day(day)) => {
+            dates::as_datetime(value).map(|v| v.date().cmp(day))
+        }
+        (value, Operand::Number(other)) => value.as_f64()?.partial_cmp(other),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dataset(headers: &[&str], rows: &[&[&str]]) -> DataSet {
+        DataSet::new(
+            headers.iter().map(|h| h.to_string()).collect(),
+            rows.iter().map(|row| row.iter().map(|cell| cell.to_string()).collect()).collect(),
+            "test".to_string(),
+        )
+    }
+
+    fn matching(dataset: &DataSet, expression: &str) -> Result<Vec<usize>> {
+        Filter::parse(expression)?.matching_rows(dataset)
+    }
+
+    #[test]
+    fn numbers_in_text_column_compare_as_numbers() {
+        let data = dataset(&["이름", "점수"], &[&["가", "9"], &["나", "100"], &["다", "85"], &["라", "abc"]]);
+        assert_eq!(data.column("점수").unwrap().column_type(), ColumnType::String);
+
+        assert_eq!(matching(&data, "점수 = 100").unwrap(), vec![1]);
+        assert_eq!(matching(&data, "점수 = 100.0").unwrap(), vec![1]);
+        assert_eq!(matching(&data, "점수 in (9, abc)").unwrap(), vec![0, 3]);
+
+        let error = matching(&data, "점수 >= 80").unwrap_err().to_string();
+        assert!(error.contains("'abc'"), "{}", error);
+        assert!(matching(&data, "점수 between 10 and 90").is_err());
+    }
+
+    #[test]
+    fn and_binds_tighter_than_or() {
+        let data = dataset(&["a", "b", "c"], &[&["1", "0", "0"], &["0", "1", "0"], &["0", "1", "1"], &["1", "1", "1"]]);
+
+        assert_eq!(matching(&data, "a = 1 or b = 1 and c = 1").unwrap(), vec![0, 2, 3]);
+        assert_eq!(matching(&data, "(a = 1 or b = 1) and c = 1").unwrap(), vec![2, 3]);
+        assert_eq!(matching(&data, "a = 1 || b = 1 && c = 1").unwrap(), vec![0, 2, 3]);
+        assert_eq!(matching(&data, "not a = 1 and b = 1").unwrap(), vec![1, 2]);
+        assert_eq!(matching(&data, "not (a = 1 and b = 1)").unwrap(), vec![0, 1, 2]);
+        assert_eq!(matching(&data, "!(a = 0) and not not c = 1").unwrap(), vec![3]);
+    }
+
+    #[test]
+    fn missing_values_match_neither_a_condition_nor_its_negation() {
+        let data = dataset(&["이름", "점수"], &[&["가", "90"], &["나", ""], &["다", "40"]]);
+
+        assert_eq!(matching(&data, "점수 > 50").unwrap(), vec![0]);
+        assert_eq!(matching(&data, "not 점수 > 50").unwrap(), vec![2]);
+        assert_eq!(matching(&data, "점수 > 50 or 이름 = 나").unwrap(), vec![0, 1]);
+        assert_eq!(matching(&data, "점수 is null").unwrap(), vec![1]);
+        assert_eq!(matching(&data, "점수 is not null").unwrap(), vec![0, 2]);
+        assert_eq!(matching(&data, "점수 not in (40)").unwrap(), vec![0]);
+    }
+
+    #[test]
+    fn quoted_names_and_values() {
+        let data = dataset(&["도시 이름", "비고"], &[&["서울 시", "O'Brien"], &["부산", "김씨"], &["대구", "K12"]]);
+
+        assert_eq!(matching(&data, "\"도시 이름\" = '서울 시'").unwrap(), vec![0]);
+        assert_eq!(matching(&data, "비고 = 'O''Brien'").unwrap(), vec![0]);
+        assert_eq!(matching(&data, "비고 contains '김'").unwrap(), vec![1]);
+        assert_eq!(matching(&data, r"비고 matches '^K\d+$'").unwrap(), vec![2]);
+        assert_eq!(matching(&data, "\"도시 이름\" in ('부산', 대구)").unwrap(), vec![1, 2]);
+    }
+
+    #[test]
+    fn between_includes_both_ends() {
+        let data = dataset(
+            &["주문일", "수량"],
+            &[&["2024-02-29", "1"], &["2024-03-01", "5"], &["2024-03-31", "10"], &["2024-04-01", "11"]],
+        );
+
+        assert_eq!(matching(&data, "주문일 between 2024-03-01 and 2024-03-31").unwrap(), vec![1, 2]);
+        assert_eq!(matching(&data, "수량 between 5 and 10 and 주문일 >= 2024-03-15").unwrap(), vec![2]);
+        assert_eq!(matching(&data, "수량 not between 5 and 10").unwrap(), vec![0, 3]);
+    }
+
+    #[test]
+    fn malformed_expressions_are_errors() {
+        let data = dataset(&["a"], &[&["1"]]);
+
+        for expression in ["", "a = 'open", "a = 1 and", "a = 1 b", "(a = 1", "a =< 1", "a in 1, 2", "a between 1"] {
+            assert!(Filter::parse(expression).is_err(), "{}", expression);
+        }
+        assert!(Filter::parse("a matches '['").is_err());
+        assert!(matching(&data, "없는열 = 1").is_err());
+        assert!(matching(&data, "a > 가").is_err());
+    }
+}
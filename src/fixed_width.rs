@@ -0,0 +1,294 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+use anyhow::{Result, anyhow};
+use crate::column::NullValues;
+use crate::encoding::{self, TextEncoding};
+use crate::read_file::DataSet;
+
+/// 열 위치와 폭을 세는 단위
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WidthUnit {
+    /// 문자 수 (한글 한 글자 = 1)
+    #[default]
+    Chars,
+    /// 파일 인코딩 기준 바이트 수 (CP949 한글 한 글자 = 2)
+    Bytes,
+}
+
+impl fmt::Display for WidthUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            WidthUnit::Chars => "chars",
+            WidthUnit::Bytes => "bytes",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for WidthUnit {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "chars" | "char" | "characters" => Ok(WidthUnit::Chars),
+            "bytes" | "byte" => Ok(WidthUnit::Bytes),
+            _ => Err(anyhow!("Unknown width unit: {} (use chars or bytes)", s)),
+        }
+    }
+}
+
+/// 고정폭 열 하나. start는 0부터 시작하는 위치
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixedWidthField {
+    pub name: String,
+    pub start: usize,
+    pub width: usize,
+}
+
+/// 고정폭 파일의 열 정의
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FixedWidthSpec {
+    pub fields: Vec<FixedWidthField>,
+}
+
+impl FixedWidthSpec {
+    /// 쉼표로 구분한 열 정의 해석. "이름:시작:폭"(시작은 1부터) 또는 "이름:폭"(앞 열 바로 뒤),
+    /// 폭만 쓰면 column_N 이름을 붙임 (예: "id:1:5,name:6:20,amount:10", "5,20,10")
+    pub fn parse(spec: &str) -> Result<Self> {
+        let entries = spec
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .enumerate()
+            .map(|(i, entry)| {
+                let parts: Vec<&str> = entry.split(':').map(str::trim).collect();
+                match parts.as_slice() {
+                    [width] => Ok((format!("column_{}", i + 1), None, parse_number(width, entry)?)),
+                    [name, width] => Ok((name.to_string(), None, parse_number(width, entry)?)),
+                    [name, start, width] => {
+                        Ok((name.to_string(), Some(parse_number(start, entry)?), parse_number(width, entry)?))
+                    }
+                    _ => Err(anyhow!("Invalid column spec '{}' (use name:start:width or name:width)", entry)),
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Self::from_entries(entries)
+    }
+
+    /// 열 정의 파일 읽기. 한 줄에 "이름 시작 폭" 또는 "이름 폭"을 쉼표, 탭 또는 공백으로 구분해 적음.
+    /// 빈 줄과 #으로 시작하는 줄, 숫자가 없는 머리글 줄은 건너뜀
+    pub fn from_file<P: AsRef<Path>>(spec_path: P) -> Result<Self> {
+        let path = spec_path.as_ref();
+        let bytes = std::fs::read(path)?;
+        let (text, _) = encoding::decode(&bytes, None);
+
+        let mut entries = Vec::new();
+        for (line_idx, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let tokens: Vec<&str> = if line.contains([',', '\t']) {
+                line.split([',', '\t']).map(str::trim).filter(|t| !t.is_empty()).collect()
+            } else {
+                line.split_whitespace().collect()
+            };
+
+            // Names may contain spaces when the line is whitespace separated, so numbers are taken from the end
+            let numbers: Vec<usize> = tokens
+                .iter()
+                .rev()
+                .take(2)
+                .map_while(|token| token.parse().ok())
+                .collect();
+            let name_tokens = &tokens[..tokens.len() - numbers.len()];
+
+            if numbers.is_empty() && entries.is_empty() {
+                continue;
+            }
+            if numbers.is_empty() || name_tokens.is_empty() {
+                return Err(anyhow!(
+                    "{} line {}: expected 'name start width' or 'name width', found '{}'",
+                    path.display(),
+                    line_idx + 1,
+                    line
+                ));
+            }
+
+            let name = name_tokens.join(" ");
+            match numbers.as_slice() {
+                [width] => entries.push((name, None, *width)),
+                [width, start] => entries.push((name, Some(*start), *width)),
+                _ => unreachable!(),
+            }
+        }
+
+        Self::from_entries(entries)
+    }
+
+    /// (이름, 1부터 시작하는 위치, 폭) 목록으로 정의 생성. 위치가 없으면 앞 열 바로 뒤
+    fn from_entries(entries: Vec<(String, Option<usize>, usize)>) -> Result<Self> {
+        if entries.is_empty() {
+            return Err(anyhow!("Fixed-width column spec has no fields"));
+        }
+
+        let mut seen = HashSet::new();
+        let mut next_start = 0;
+        let mut fields = Vec::with_capacity(entries.len());
+        for (name, start, width) in entries {
+            if !seen.insert(name.clone()) {
+                return Err(anyhow!("Duplicate column '{}' in fixed-width spec", name));
+            }
+            if width == 0 {
+                return Err(anyhow!("Column '{}' must have a width of at least 1", name));
+            }
+            let start = match start {
+                Some(0) => return Err(anyhow!("Column '{}' start position must be 1 or more", name)),
+                Some(start) => start - 1,
+                None => next_start,
+            };
+            next_start = start + width;
+            fields.push(FixedWidthField { name, start, width });
+        }
+
+        Ok(Self { fields })
+    }
+
+    pub fn headers(&self) -> Vec<String> {
+        self.fields.iter().map(|field| field.name.clone()).collect()
+    }
+}
+
+fn parse_number(text: &str, entry: &str) -> Result<usize> {
+    text.parse()
+        .map_err(|_| anyhow!("Invalid number '{}' in column spec '{}'", text, entry))
+}
+
+/// 고정폭 파일 읽기 옵션
+#[derive(Debug, Clone, Default)]
+pub struct FixedWidthOptions {
+    pub spec: FixedWidthSpec,
+    pub unit: WidthUnit,
+    /// 텍스트 인코딩. None이면 자동 판별
+    pub encoding: Option<TextEncoding>,
+    /// 데이터 앞에서 건너뛸 줄 수 (제목, 머리글 등)
+    pub skip_rows: usize,
+    pub null_values: NullValues,
+}
+
+pub fn read_fixed_width_file<P: AsRef<Path>>(file_path: P, options: &FixedWidthOptions) -> Result<DataSet> {
+    let path = file_path.as_ref();
+    let bytes = std::fs::read(path)?;
+    read_fixed_width_bytes(&bytes, path.to_string_lossy().to_string(), options)
+}
+
+/// 메모리에 있는 고정폭 텍스트를 열 정의대로 잘라 읽음. 각 칸의 앞뒤 공백은 제거
+pub fn read_fixed_width_bytes(bytes: &[u8], file_path: String, options: &FixedWidthOptions) -> Result<DataSet> {
+    if options.spec.fields.is_empty() {
+        return Err(anyhow!("Fixed-width files need a column spec"));
+    }
+
+    let encoding = options.encoding.unwrap_or_else(|| encoding::detect_encoding(bytes));
+    let fields = &options.spec.fields;
+
+    let rows: Vec<Vec<String>> = match options.unit {
+        WidthUnit::Chars => {
+            let (text, _) = encoding::decode(bytes, Some(encoding));
+            data_lines(text.lines(), options.skip_rows, |line| line.trim().is_empty())
+                .map(|line| {
+                    let chars: Vec<char> = line.chars().collect();
+                    fields
+                        .iter()
+                        .map(|field| slice(&chars, field).iter().collect::<String>().trim().to_string())
+                        .collect()
+                })
+                .collect()
+        }
+        WidthUnit::Bytes => {
+            if matches!(encoding, TextEncoding::Utf16Le | TextEncoding::Utf16Be) {
+                return Err(anyhow!("Byte widths are not supported for {} files; use character widths", encoding));
+            }
+            let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+            let lines = bytes
+                .split(|&b| b == b'\n')
+                .map(|line| line.strip_suffix(b"\r").unwrap_or(line));
+
+            // Each field is decoded on its own, so a cut through a double-byte character shows up as U+FFFD
+            data_lines(lines, options.skip_rows, |line| line.trim_ascii().is_empty())
+                .map(|line| {
+                    fields
+                        .iter()
+                        .map(|field| encoding::decode(slice(line, field), Some(encoding)).0.trim().to_string())
+                        .collect()
+                })
+                .collect()
+        }
+    };
+
+    Ok(DataSet::with_null_values(options.spec.headers(), rows, file_path, &options.null_values))
+}
+
+fn data_lines<T, I>(lines: I, skip_rows: usize, is_blank: impl Fn(&T) -> bool) -> impl Iterator<Item = T>
+where
+    I: Iterator<Item = T>,
+{
+    lines.skip(skip_rows).filter(move |line| !is_blank(line))
+}
+
+// Short lines leave the trailing fields empty instead of failing
+fn slice<'a, T>(line: &'a [T], field: &FixedWidthField) -> &'a [T] {
+    let start = field.start.min(line.len());
+    let end = (field.start + field.width).min(line.len());
+    &line[start..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(spec: &str, unit: WidthUnit) -> FixedWidthOptions {
+        FixedWidthOptions { spec: FixedWidthSpec::parse(spec).unwrap(), unit, ..FixedWidthOptions::default() }
+    }
+
+    #[test]
+    fn parses_column_specs() {
+        let spec = FixedWidthSpec::parse("id:1:3, name:6:4, amount:5").unwrap();
+        let positions: Vec<(usize, usize)> = spec.fields.iter().map(|field| (field.start, field.width)).collect();
+        assert_eq!(positions, [(0, 3), (5, 4), (9, 5)]);
+        assert_eq!(FixedWidthSpec::parse("2,3").unwrap().headers(), ["column_1", "column_2"]);
+
+        for spec in ["", "a:0", "a:0:3", "a:2,a:3", "a:b", "a:1:2:3"] {
+            assert!(FixedWidthSpec::parse(spec).is_err(), "{}", spec);
+        }
+    }
+
+    #[test]
+    fn reads_spec_files_with_header_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("layout.txt");
+        std::fs::write(&path, "# 거래 파일\n열 시작 폭\n거래 번호 1 4\n금액\t5\t6\n").unwrap();
+
+        let spec = FixedWidthSpec::from_file(&path).unwrap();
+        assert_eq!(spec.headers(), ["거래 번호", "금액"]);
+        assert_eq!((spec.fields[1].start, spec.fields[1].width), (4, 6));
+    }
+
+    #[test]
+    fn slices_by_characters_or_bytes() {
+        let text = "  1김철수  90\n  2이영     85\n\n  3\n";
+        let char_options = options("id:3,name:5,score:4", WidthUnit::Chars);
+        let dataset = read_fixed_width_bytes(text.as_bytes(), "t".to_string(), &char_options).unwrap();
+        assert_eq!(dataset.rows().collect::<Vec<_>>(), [["1", "김철수", "90"], ["2", "이영", "85"], ["3", "", ""]]);
+
+        // Legacy exports count columns in CP949 bytes, two per Hangul syllable
+        let (bytes, _, _) = encoding_rs::EUC_KR.encode("001김철수 090\n002이영   085\n");
+        let mut byte_options = options("id:3,name:7,score:3", WidthUnit::Bytes);
+        byte_options.encoding = Some(TextEncoding::Cp949);
+        let dataset = read_fixed_width_bytes(&bytes, "t".to_string(), &byte_options).unwrap();
+        assert_eq!(dataset.rows().collect::<Vec<_>>(), [["001", "김철수", "090"], ["002", "이영", "085"]]);
+    }
+}
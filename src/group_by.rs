@@ -0,0 +1,380 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+use anyhow::{Result, anyhow};
+use statrs::statistics::Statistics;
+use crate::column::{Column, ColumnType, Value};
+use crate::data_analyzer::{calculate_median, calculate_quartile};
+use crate::read_file::DataSet;
+use crate::sort::{self, NullsPlacement};
+
+/// 그룹마다 계산할 집계 함수
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Aggregation {
+    /// 열이 없으면 행 수, 있으면 결측이 아닌 값의 수
+    Count,
+    Sum,
+    Mean,
+    Median,
+    Min,
+    Max,
+    /// 표본 표준편차 (n - 1)
+    Std,
+    /// 0~1 사이 분위수
+    Quantile(f64),
+    /// 서로 다른 값의 수 (결측 제외)
+    DistinctCount,
+    /// 결측이 아닌 첫 값
+    First,
+    /// 결측이 아닌 마지막 값
+    Last,
+}
+
+impl Aggregation {
+    /// 숫자 열에만 쓸 수 있는 함수인지
+    fn needs_numbers(&self) -> bool {
+        matches!(
+            self,
+            Aggregation::Sum | Aggregation::Mean | Aggregation::Median | Aggregation::Std | Aggregation::Quantile(_)
+        )
+    }
+}
+
+impl fmt::Display for Aggregation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Aggregation::Count => write!(f, "count"),
+            Aggregation::Sum => write!(f, "sum"),
+            Aggregation::Mean => write!(f, "mean"),
+            Aggregation::Median => write!(f, "median"),
+            Aggregation::Min => write!(f, "min"),
+            Aggregation::Max => write!(f, "max"),
+            Aggregation::Std => write!(f, "std"),
+            // Rounded so 0.9 prints as p90 rather than p90.00000000000001
+            Aggregation::Quantile(q) => write!(f, "p{}", (q * 100_000.0).round() / 1000.0),
+            Aggregation::DistinctCount => write!(f, "nunique"),
+            Aggregation::First => write!(f, "first"),
+            Aggregation::Last => write!(f, "last"),
+        }
+    }
+}
+
+impl FromStr for Aggregation {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let name = s.trim().to_lowercase();
+        let aggregation = match name.as_str() {
+            "count" | "n" => Aggregation::Count,
+            "sum" => Aggregation::Sum,
+            "mean" | "avg" | "average" => Aggregation::Mean,
+            "median" => Aggregation::Median,
+            "min" => Aggregation::Min,
+            "max" => Aggregation::Max,
+            "std" | "stddev" | "std_dev" => Aggregation::Std,
+            "nunique" | "distinct" | "count_distinct" => Aggregation::DistinctCount,
+            "first" => Aggregation::First,
+            "last" => Aggregation::Last,
+            // p90 = 90th percentile
+            _ => match name.strip_prefix('p').and_then(|p| p.parse::<f64>().ok()) {
+                Some(percent) if (0.0..=100.0).contains(&percent) => Aggregation::Quantile(percent / 100.0),
+                _ => return Err(anyhow!("Unknown aggregation: {}", s)),
+            },
+        };
+        Ok(aggregation)
+    }
+}
+
+/// 결과 열 하나의 정의
+#[derive(Debug, Clone, PartialEq)]
+pub struct AggregationSpec {
+    /// 집계할 열. None이면 그룹의 행 수 (count만 가능)
+    pub column: Option<String>,
+    pub aggregation: Aggregation,
+}
+
+impl AggregationSpec {
+    pub fn new(column: &str, aggregation: Aggregation) -> Self {
+        Self { column: Some(column.to_string()), aggregation }
+    }
+
+    /// 그룹의 행 수
+    pub fn count_rows() -> Self {
+        Self { column: None, aggregation: Aggregation::Count }
+    }
+
+    /// 결과 열 이름 ("점수_mean", 행 수는 "count")
+    pub fn output_name(&self) -> String {
+        match &self.column {
+            Some(column) => format!("{}_{}", column, self.aggregation),
+            None => self.aggregation.to_string(),
+        }
+    }
+}
+
+/// 쉼표로 구분한 집계 목록 해석. 예: "count, mean(점수), max(점수), quantile(점수, 0.9), p90(점수), nunique(이름)"
+pub fn parse_aggregations(spec: &str) -> Result<Vec<AggregationSpec>> {
+    let specs: Vec<AggregationSpec> = split_outside_parens(spec)
+        .into_iter()
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(parse_aggregation)
+        .collect::<Result<_>>()?;
+    if specs.is_empty() {
+        return Err(anyhow!("No aggregations given"));
+    }
+    Ok(specs)
+}
+
+fn parse_aggregation(item: &str) -> Result<AggregationSpec> {
+    let Some((name, rest)) = item.split_once('(') else {
+        let aggregation: Aggregation = item.parse()?;
+        if aggregation != Aggregation::Count {
+            return Err(anyhow!("Aggregation '{}' needs a column, e.g. {}(column)", item, item));
+        }
+        return Ok(AggregationSpec::count_rows());
+    };
+    let arguments = rest
+        .strip_suffix(')')
+        .ok_or_else(|| anyhow!("Missing ')' in aggregation '{}'", item))?;
+
+    if name.trim().eq_ignore_ascii_case("quantile") {
+        let (column, q) = arguments
+            .rsplit_once(',')
+            .ok_or_else(|| anyhow!("quantile needs a column and a probability, e.g. quantile(column, 0.9)"))?;
+        let q: f64 = q.trim().parse().map_err(|_| anyhow!("Invalid quantile: {}", q.trim()))?;
+        if !(0.0..=1.0).contains(&q) {
+            return Err(anyhow!("Quantile must be between 0 and 1: {}", q));
+        }
+        return Ok(AggregationSpec::new(column.trim(), Aggregation::Quantile(q)));
+    }
+    Ok(AggregationSpec::new(arguments.trim(), name.parse()?))
+}
+
+fn split_outside_parens(text: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in text.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&text[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&text[start..]);
+    parts
+}
+
+/// 키 값이 같은 행 묶음
+#[derive(Debug, Clone)]
+pub struct Group {
+    /// 키 열 순서대로의 값 (결측 포함)
+    pub key: Vec<Value>,
+    /// 그룹에 속한 행 번호 (원래 순서)
+    pub rows: Vec<usize>,
+}
+
+impl Group {
+    /// "서울, A" 형식의 그룹 이름 (결측 키는 빈 칸)
+    pub fn label(&self) -> String {
+        self.key.iter().map(Value::to_string).collect::<Vec<_>>().join(", ")
+    }
+}
+
+/// 키 열 값이 같은 행끼리 묶음. 결측 키도 하나의 그룹이 되며, 그룹은 키 순서(결측은 마지막)로 정렬
+pub fn group_rows(dataset: &DataSet, keys: &[String]) -> Result<Vec<Group>> {
+    if keys.is_empty() {
+        return Err(anyhow!("No group keys given"));
+    }
+    let key_columns: Vec<&Column> = keys.iter().map(|key| dataset.column(key)).collect::<Result<_>>()?;
+
+    let mut groups: Vec<Group> = Vec::new();
+    // Values are hashed by their text, with None marking a missing key
+    let mut positions: HashMap<Vec<Option<String>>, usize> = HashMap::new();
+    for row in 0..dataset.row_count() {
+        let key: Vec<Value> = key_columns.iter().map(|column| column.get(row)).collect();
+        let hash_key = key.iter().map(|value| (!value.is_null()).then(|| value.to_string())).collect();
+        let position = *positions.entry(hash_key).or_insert_with(|| {
+            groups.push(Group { key, rows: Vec::new() });
+            groups.len() - 1
+        });
+        groups[position].rows.push(row);
+    }
+
+    groups.sort_by(|a, b| {
+        a.key
+            .iter()
+            .zip(&b.key)
+            .map(|(x, y)| sort::compare_cells(x, y, false, NullsPlacement::Last))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
+    });
+    Ok(groups)
+}
+
+/// 키 열로 묶어 그룹마다 집계한 데이터셋. 결과는 키 열 다음에 집계 열이 오며 그룹 하나가 한 행
+pub fn group_by(dataset: &DataSet, keys: &[String], aggregations: &[AggregationSpec]) -> Result<DataSet> {
+    check_aggregations(dataset, aggregations)?;
+
+    let groups = group_rows(dataset, keys)?;
+    let first_rows: Vec<usize> = groups.iter().map(|group| group.rows[0]).collect();
+
+    let mut headers = keys.to_vec();
+    let mut columns: Vec<Column> = keys
+        .iter()
+        .map(|key| Ok(dataset.column(key)?.take(&first_rows)))
+        .collect::<Result<_>>()?;
+
+    for spec in aggregations {
+        let values = groups
+            .iter()
+            .map(|group| aggregate(dataset, spec, &group.rows))
+            .collect::<Result<Vec<Value>>>()?;
+        let name = spec.output_name();
+        if headers.contains(&name) {
+            return Err(anyhow!("Duplicate output column '{}'", name));
+        }
+        headers.push(name);
+        columns.push(Column::from_values(values));
+    }
+
+    Ok(DataSet::from_columns(headers, columns, dataset.file_path.clone()))
+}
+
+/// 집계할 열이 있는지, 숫자 집계의 열이 숫자 열인지 확인
+pub(crate) fn check_aggregations(dataset: &DataSet, aggregations: &[AggregationSpec]) -> Result<()> {
+    for spec in aggregations {
+        let Some(column_name) = &spec.column else {
+            continue;
+        };
+        let column_type = dataset.column(column_name)?.column_type();
+        if spec.aggregation.needs_numbers() && !matches!(column_type, ColumnType::Integer | ColumnType::Float) {
+            return Err(anyhow!(
+                "Cannot calculate {} of column '{}' ({} values are not numeric)",
+                spec.aggregation,
+                column_name,
+                column_type
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// 행 묶음 하나에 대한 집계 값
+pub(crate) fn aggregate(dataset: &DataSet, spec: &AggregationSpec, rows: &[usize]) -> Result<Value> {
+    let Some(column_name) = &spec.column else {
+        return Ok(Value::Integer(rows.len() as i64));
+    };
+    let column = dataset.column(column_name)?;
+    let values: Vec<Value> = rows.iter().map(|&row| column.get(row)).filter(|value| !value.is_null()).collect();
+    let numbers: Vec<f64> = values.iter().filter_map(Value::as_f64).collect();
+
+    let value = match spec.aggregation {
+        Aggregation::Count => Value::Integer(values.len() as i64),
+        Aggregation::DistinctCount => {
+            let distinct: HashSet<String> = values.iter().map(Value::to_string).collect();
+            Value::Integer(distinct.len() as i64)
+        }
+        Aggregation::First => values.first().cloned().unwrap_or(Value::Null),
+        Aggregation::Last => values.last().cloned().unwrap_or(Value::Null),
+        Aggregation::Min => values.iter().min_by(|a, b| sort::compare_values(a, b)).cloned().unwrap_or(Value::Null),
+        Aggregation::Max => values.iter().max_by(|a, b| sort::compare_values(a, b)).cloned().unwrap_or(Value::Null),
+        _ if numbers.is_empty() => Value::Null,
+        // Integer sums stay integers unless they overflow i64
+        Aggregation::Sum if column.column_type() == ColumnType::Integer => values
+            .iter()
+            .try_fold(0i64, |sum, value| match value {
+                Value::Integer(i) => sum.checked_add(*i),
+                _ => Some(sum),
+            })
+            .map(Value::Integer)
+            .unwrap_or_else(|| Value::Float(numbers.iter().sum())),
+        Aggregation::Sum => Value::Float(numbers.iter().sum()),
+        Aggregation::Mean => Value::Float(numbers.as_slice().mean()),
+        Aggregation::Std if numbers.len() < 2 => Value::Null,
+        Aggregation::Std => Value::Float(numbers.as_slice().std_dev()),
+        Aggregation::Median | Aggregation::Quantile(_) => {
+            let mut sorted = numbers;
+            sorted.sort_by(|a, b| a.total_cmp(b));
+            match spec.aggregation {
+                Aggregation::Quantile(q) => Value::Float(calculate_quartile(&sorted, q)),
+                _ => Value::Float(calculate_median(&sorted)),
+            }
+        }
+    };
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dataset(headers: &[&str], rows: &[&[&str]]) -> DataSet {
+        DataSet::new(
+            headers.iter().map(|h| h.to_string()).collect(),
+            rows.iter().map(|row| row.iter().map(|cell| cell.to_string()).collect()).collect(),
+            "test".to_string(),
+        )
+    }
+
+    fn scores() -> DataSet {
+        dataset(
+            &["도시", "이름", "점수"],
+            &[&["서울", "가", "90"], &["부산", "나", "70"], &["서울", "다", "80"], &["", "라", "60"], &["서울", "마", ""]],
+        )
+    }
+
+    fn column(dataset: &DataSet, name: &str) -> Vec<String> {
+        let column = dataset.column(name).unwrap();
+        (0..dataset.row_count()).map(|i| column.get_string(i)).collect()
+    }
+
+    #[test]
+    fn parses_aggregation_lists() {
+        let specs = parse_aggregations("count, mean(점수), quantile(점수, 0.9), p25(점수), nunique(이름)").unwrap();
+        let names: Vec<String> = specs.iter().map(AggregationSpec::output_name).collect();
+        assert_eq!(names, ["count", "점수_mean", "점수_p90", "점수_p25", "이름_nunique"]);
+
+        for spec in ["", "mean", "median(점수", "quantile(점수, 2)", "p101(점수)", "mode(점수)"] {
+            assert!(parse_aggregations(spec).is_err(), "{}", spec);
+        }
+    }
+
+    #[test]
+    fn groups_are_sorted_with_missing_keys_last() {
+        let specs = parse_aggregations("count, count(점수), sum(점수), mean(점수), max(이름), first(점수)").unwrap();
+        let grouped = group_by(&scores(), &["도시".to_string()], &specs).unwrap();
+
+        assert_eq!(column(&grouped, "도시"), ["부산", "서울", ""]);
+        assert_eq!(column(&grouped, "count"), ["1", "3", "1"]);
+        assert_eq!(column(&grouped, "점수_count"), ["1", "2", "1"]);
+        assert_eq!(column(&grouped, "점수_sum"), ["70", "170", "60"]);
+        assert_eq!(column(&grouped, "점수_mean"), ["70", "85", "60"]);
+        assert_eq!(column(&grouped, "이름_max"), ["나", "마", "라"]);
+        assert_eq!(column(&grouped, "점수_first"), ["70", "90", "60"]);
+    }
+
+    #[test]
+    fn integer_sums_fall_back_to_floats_on_overflow() {
+        let big = i64::MAX.to_string();
+        let data = dataset(&["k", "v"], &[&["a", &big], &["a", "1"], &["b", "2"], &["b", "3"]]);
+        let grouped = group_by(&data, &["k".to_string()], &[AggregationSpec::new("v", Aggregation::Sum)]).unwrap();
+
+        assert_eq!(grouped.column("v_sum").unwrap().get(1), Value::Float(5.0));
+        assert_eq!(grouped.column("v_sum").unwrap().get(0), Value::Float(i64::MAX as f64 + 1.0));
+    }
+
+    #[test]
+    fn numeric_aggregations_need_numeric_columns() {
+        let specs = [AggregationSpec::new("이름", Aggregation::Mean)];
+        let error = group_by(&scores(), &["도시".to_string()], &specs).unwrap_err().to_string();
+        assert!(error.contains("'이름'"), "{}", error);
+        assert!(group_by(&scores(), &[], &[AggregationSpec::count_rows()]).is_err());
+    }
+}
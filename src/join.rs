@@ -0,0 +1,386 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+use anyhow::{Result, anyhow};
+use crate::column::{Column, ColumnType, Value};
+use crate::read_file::DataSet;
+
+/// 조인 방식
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum JoinKind {
+    /// 양쪽에 키가 있는 행만
+    #[default]
+    Inner,
+    /// 왼쪽 행은 모두, 오른쪽에 없으면 결측
+    Left,
+    /// 오른쪽 행은 모두, 왼쪽에 없으면 결측
+    Right,
+    /// 양쪽 행 모두
+    Full,
+    /// 오른쪽에 키가 있는 왼쪽 행 (왼쪽 열만)
+    Semi,
+    /// 오른쪽에 키가 없는 왼쪽 행 (왼쪽 열만)
+    Anti,
+}
+
+impl fmt::Display for JoinKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            JoinKind::Inner => "inner",
+            JoinKind::Left => "left",
+            JoinKind::Right => "right",
+            JoinKind::Full => "full",
+            JoinKind::Semi => "semi",
+            JoinKind::Anti => "anti",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for JoinKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "inner" => Ok(JoinKind::Inner),
+            "left" => Ok(JoinKind::Left),
+            "right" => Ok(JoinKind::Right),
+            "full" | "outer" | "full_outer" => Ok(JoinKind::Full),
+            "semi" => Ok(JoinKind::Semi),
+            "anti" => Ok(JoinKind::Anti),
+            _ => Err(anyhow!("Unknown join kind: {} (use inner, left, right, full, semi or anti)", s)),
+        }
+    }
+}
+
+/// 조인 옵션
+#[derive(Debug, Clone)]
+pub struct JoinOptions {
+    pub kind: JoinKind,
+    /// 왼쪽 키 열
+    pub left_on: Vec<String>,
+    /// 오른쪽 키 열. 비어 있으면 left_on과 같은 이름
+    pub right_on: Vec<String>,
+    /// 키가 아닌 열의 이름이 양쪽에서 겹칠 때 붙일 접미사 (왼쪽, 오른쪽)
+    pub suffixes: (String, String),
+}
+
+impl Default for JoinOptions {
+    fn default() -> Self {
+        Self {
+            kind: JoinKind::default(),
+            left_on: Vec::new(),
+            right_on: Vec::new(),
+            suffixes: ("_left".to_string(), "_right".to_string()),
+        }
+    }
+}
+
+impl JoinOptions {
+    /// 양쪽 이름이 같은 키 열로 조인
+    pub fn on(keys: &[&str], kind: JoinKind) -> Self {
+        Self {
+            kind,
+            left_on: keys.iter().map(|key| key.to_string()).collect(),
+            ..Self::default()
+        }
+    }
+
+    fn right_keys(&self) -> &[String] {
+        if self.right_on.is_empty() { &self.left_on } else { &self.right_on }
+    }
+}
+
+/// 조인하며 발견한 키 문제. 키는 "값1, 값2" 형식
+#[derive(Debug, Clone, Default)]
+pub struct JoinReport {
+    /// 오른쪽에 짝이 없는 왼쪽 키 (처음 나온 순서)
+    pub left_unmatched: Vec<String>,
+    /// 왼쪽에 짝이 없는 오른쪽 키
+    pub right_unmatched: Vec<String>,
+    /// 두 번 이상 나온 왼쪽 키와 횟수
+    pub left_duplicates: Vec<(String, usize)>,
+    /// 두 번 이상 나온 오른쪽 키와 횟수. 왼쪽 행이 그만큼 늘어남
+    pub right_duplicates: Vec<(String, usize)>,
+    /// 키에 결측이 있어 어떤 행과도 짝이 되지 않은 행 수 (왼쪽, 오른쪽)
+    pub null_keys: (usize, usize),
+    /// 양쪽 타입이 달라 값이 같아 보여도 짝이 되지 않을 수 있는 키 (왼쪽 열 이름, 왼쪽 타입, 오른쪽 타입)
+    pub type_mismatches: Vec<(String, ColumnType, ColumnType)>,
+}
+
+impl JoinReport {
+    /// 양쪽 모두 키가 중복되어 행이 곱으로 늘어나는 경우 (다대다)
+    pub fn is_many_to_many(&self) -> bool {
+        let right: HashSet<&String> = self.right_duplicates.iter().map(|(key, _)| key).collect();
+        self.left_duplicates.iter().any(|(key, _)| right.contains(key))
+    }
+}
+
+/// 조인 결과와 보고
+#[derive(Debug, Clone)]
+pub struct JoinResult {
+    pub dataset: DataSet,
+    pub report: JoinReport,
+}
+
+/// 키 열 값이 같은 행끼리 두 데이터셋을 합침. 결측 키는 어떤 행과도 짝이 되지 않음.
+/// 키는 값을 글자로 바꿔 비교하므로 정수 1과 실수 1.0은 짝이 되지만 문자열 "001"과 정수 1은 짝이 되지 않음
+/// (양쪽 키 타입이 다르면 JoinReport::type_mismatches에 기록).
+/// 결과는 키 열(왼쪽 이름), 왼쪽의 나머지 열, 오른쪽의 나머지 열 순서이며 행은 왼쪽 순서를 따르고
+/// right/full 조인에서 짝이 없는 오른쪽 행은 끝에 붙음
+pub fn join(left: &DataSet, right: &DataSet, options: &JoinOptions) -> Result<JoinResult> {
+    let left_keys = &options.left_on;
+    let right_keys = options.right_keys();
+    if left_keys.is_empty() {
+        return Err(anyhow!("No join keys given"));
+    }
+    if left_keys.len() != right_keys.len() {
+        return Err(anyhow!(
+            "Left and right key lists differ in length ({} vs {})",
+            left_keys.len(),
+            right_keys.len()
+        ));
+    }
+
+    let left_index = KeyIndex::build(left, left_keys).map_err(|e| anyhow!("Left side: {}", e))?;
+    let right_index = KeyIndex::build(right, right_keys).map_err(|e| anyhow!("Right side: {}", e))?;
+
+    let mut report = JoinReport {
+        left_duplicates: left_index.duplicates(),
+        right_duplicates: right_index.duplicates(),
+        null_keys: (left_index.null_rows, right_index.null_rows),
+        ..JoinReport::default()
+    };
+    for (left_key, right_key) in left_keys.iter().zip(right_keys) {
+        let types = (left.column(left_key)?.column_type(), right.column(right_key)?.column_type());
+        let both_numeric = matches!(types, (ColumnType::Integer | ColumnType::Float, ColumnType::Integer | ColumnType::Float));
+        if types.0 != types.1 && !both_numeric {
+            report.type_mismatches.push((left_key.clone(), types.0, types.1));
+        }
+    }
+    report.left_unmatched = left_index.keys_missing_from(&right_index);
+    report.right_unmatched = right_index.keys_missing_from(&left_index);
+
+    // Pairs of (left row, right row) in output order
+    let mut pairs: Vec<(Option<usize>, Option<usize>)> = Vec::new();
+    let mut matched_right = vec![false; right.row_count()];
+    for (left_row, key) in left_index.row_keys.iter().enumerate() {
+        let matches = key.as_ref().and_then(|key| right_index.rows.get(key));
+        match (options.kind, matches) {
+            (JoinKind::Semi, Some(_)) | (JoinKind::Anti, None) => pairs.push((Some(left_row), None)),
+            (JoinKind::Semi | JoinKind::Anti, _) => {}
+            (_, Some(right_rows)) => {
+                for &right_row in right_rows {
+                    matched_right[right_row] = true;
+                    pairs.push((Some(left_row), Some(right_row)));
+                }
+            }
+            (JoinKind::Left | JoinKind::Full, None) => pairs.push((Some(left_row), None)),
+            (_, None) => {}
+        }
+    }
+    if matches!(options.kind, JoinKind::Right | JoinKind::Full) {
+        pairs.extend((0..right.row_count()).filter(|&row| !matched_right[row]).map(|row| (None, Some(row))));
+    }
+
+    if matches!(options.kind, JoinKind::Semi | JoinKind::Anti) {
+        let rows: Vec<usize> = pairs.iter().filter_map(|(left_row, _)| *left_row).collect();
+        let dataset = left.take_rows(&rows, left.file_path.clone());
+        return Ok(JoinResult { dataset, report });
+    }
+
+    let mut headers = Vec::new();
+    let mut columns = Vec::new();
+
+    // Key columns come first; rows only found on the right take their key values from the right
+    for (left_key, right_key) in left_keys.iter().zip(right_keys) {
+        let (left_column, right_column) = (left.column(left_key)?, right.column(right_key)?);
+        let values = pairs
+            .iter()
+            .map(|&(left_row, right_row)| match (left_row, right_row) {
+                (Some(row), _) => left_column.get(row),
+                (None, Some(row)) => right_column.get(row),
+                (None, None) => Value::Null,
+            })
+            .collect();
+        headers.push(left_key.clone());
+        columns.push(Column::from_values(values));
+    }
+
+    let left_rest: Vec<&String> = left.headers.iter().filter(|h| !left_keys.contains(h)).collect();
+    let right_rest: Vec<&String> = right.headers.iter().filter(|h| !right_keys.contains(h)).collect();
+    let output_name = |name: &String, others: &[&String], suffix: &str| {
+        if others.contains(&name) || left_keys.contains(name) {
+            format!("{}{}", name, suffix)
+        } else {
+            name.clone()
+        }
+    };
+
+    for name in &left_rest {
+        headers.push(output_name(name, &right_rest, &options.suffixes.0));
+        let rows: Vec<Option<usize>> = pairs.iter().map(|(left_row, _)| *left_row).collect();
+        columns.push(take_optional(left.column(name)?, &rows));
+    }
+    for name in &right_rest {
+        headers.push(output_name(name, &left_rest, &options.suffixes.1));
+        let rows: Vec<Option<usize>> = pairs.iter().map(|(_, right_row)| *right_row).collect();
+        columns.push(take_optional(right.column(name)?, &rows));
+    }
+
+    let mut seen = HashSet::new();
+    if let Some(duplicate) = headers.iter().find(|header| !seen.insert(*header)) {
+        return Err(anyhow!("Column '{}' appears twice in the join result; choose different suffixes", duplicate));
+    }
+
+    let dataset = DataSet::from_columns(headers, columns, left.file_path.clone());
+    Ok(JoinResult { dataset, report })
+}
+
+fn take_optional(column: &Column, rows: &[Option<usize>]) -> Column {
+    let values = rows.iter().map(|row| row.map(|row| column.get(row)).unwrap_or(Value::Null)).collect();
+    Column::from_values_as(values, column.column_type())
+}
+
+/// 키별 행 번호
+struct KeyIndex {
+    /// 행마다의 키. 키에 결측이 있으면 None
+    row_keys: Vec<Option<Vec<String>>>,
+    rows: HashMap<Vec<String>, Vec<usize>>,
+    /// 처음 나온 순서대로의 키
+    order: Vec<Vec<String>>,
+    null_rows: usize,
+}
+
+impl KeyIndex {
+    fn build(dataset: &DataSet, keys: &[String]) -> Result<Self> {
+        let key_columns: Vec<&Column> = keys.iter().map(|key| dataset.column(key)).collect::<Result<_>>()?;
+        let mut index = KeyIndex { row_keys: Vec::new(), rows: HashMap::new(), order: Vec::new(), null_rows: 0 };
+
+        for row in 0..dataset.row_count() {
+            // Keys are matched by their text so 1 and 1.0 or a date and its ISO string pair up
+            let key: Option<Vec<String>> = key_columns
+                .iter()
+                .map(|column| (!column.is_null(row)).then(|| column.get_string(row)))
+                .collect();
+            match &key {
+                Some(key) => {
+                    let rows = index.rows.entry(key.clone()).or_default();
+                    if rows.is_empty() {
+                        index.order.push(key.clone());
+                    }
+                    rows.push(row);
+                }
+                None => index.null_rows += 1,
+            }
+            index.row_keys.push(key);
+        }
+        Ok(index)
+    }
+
+    fn duplicates(&self) -> Vec<(String, usize)> {
+        self.order
+            .iter()
+            .map(|key| (key.join(", "), self.rows[key].len()))
+            .filter(|(_, count)| *count > 1)
+            .collect()
+    }
+
+    fn keys_missing_from(&self, other: &KeyIndex) -> Vec<String> {
+        self.order.iter().filter(|key| !other.rows.contains_key(*key)).map(|key| key.join(", ")).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dataset(headers: &[&str], rows: &[&[&str]]) -> DataSet {
+        DataSet::new(
+            headers.iter().map(|h| h.to_string()).collect(),
+            rows.iter().map(|row| row.iter().map(|cell| cell.to_string()).collect()).collect(),
+            "test".to_string(),
+        )
+    }
+
+    fn students() -> DataSet {
+        dataset(&["학번", "이름"], &[&["1", "가"], &["2", "나"], &["3", "다"], &["", "라"]])
+    }
+
+    fn scores() -> DataSet {
+        dataset(&["번호", "이름", "점수"], &[&["1", "가", "90"], &["1", "가", "85"], &["4", "마", "60"], &["", "바", "50"]])
+    }
+
+    fn column(dataset: &DataSet, name: &str) -> Vec<String> {
+        let column = dataset.column(name).unwrap();
+        (0..dataset.row_count()).map(|i| column.get_string(i)).collect()
+    }
+
+    fn join_as(kind: JoinKind) -> JoinResult {
+        let options = JoinOptions { right_on: vec!["번호".to_string()], ..JoinOptions::on(&["학번"], kind) };
+        join(&students(), &scores(), &options).unwrap()
+    }
+
+    #[test]
+    fn join_kinds_keep_the_expected_rows() {
+        let inner = join_as(JoinKind::Inner).dataset;
+        assert_eq!(inner.headers, ["학번", "이름_left", "이름_right", "점수"]);
+        assert_eq!(column(&inner, "학번"), ["1", "1"]);
+        assert_eq!(column(&inner, "점수"), ["90", "85"]);
+
+        let left = join_as(JoinKind::Left).dataset;
+        assert_eq!(column(&left, "이름_left"), ["가", "가", "나", "다", "라"]);
+        assert_eq!(column(&left, "점수"), ["90", "85", "", "", ""]);
+
+        // Right-only rows come last and take their key from the right
+        let right = join_as(JoinKind::Right).dataset;
+        assert_eq!(column(&right, "학번"), ["1", "1", "4", ""]);
+        let full = join_as(JoinKind::Full).dataset;
+        assert_eq!(column(&full, "이름_right"), ["가", "가", "", "", "", "마", "바"]);
+
+        let semi = join_as(JoinKind::Semi).dataset;
+        assert_eq!(semi.headers, ["학번", "이름"]);
+        assert_eq!(column(&semi, "이름"), ["가"]);
+        assert_eq!(column(&join_as(JoinKind::Anti).dataset, "이름"), ["나", "다", "라"]);
+    }
+
+    #[test]
+    fn report_lists_unmatched_duplicate_and_missing_keys() {
+        let report = join_as(JoinKind::Inner).report;
+        assert_eq!(report.left_unmatched, ["2", "3"]);
+        assert_eq!(report.right_unmatched, ["4"]);
+        assert_eq!(report.right_duplicates, [("1".to_string(), 2)]);
+        assert!(report.left_duplicates.is_empty() && !report.is_many_to_many());
+        assert_eq!(report.null_keys, (1, 1));
+        assert!(report.type_mismatches.is_empty());
+    }
+
+    #[test]
+    fn keys_of_different_types_are_reported() {
+        let codes = dataset(&["코드", "값"], &[&["001", "a"], &["A2", "b"]]);
+        let numbers = dataset(&["코드", "수"], &[&["1", "10"], &["2", "20"]]);
+        let result = join(&codes, &numbers, &JoinOptions::on(&["코드"], JoinKind::Inner)).unwrap();
+
+        assert_eq!(result.dataset.row_count(), 0);
+        assert_eq!(result.report.type_mismatches, [("코드".to_string(), ColumnType::String, ColumnType::Integer)]);
+    }
+
+    #[test]
+    fn rejects_mismatched_keys_and_clashing_suffixes() {
+        let options = JoinOptions {
+            right_on: vec!["번호".to_string(), "이름".to_string()],
+            ..JoinOptions::on(&["학번"], JoinKind::Inner)
+        };
+        assert!(join(&students(), &scores(), &options).is_err());
+
+        let options = JoinOptions {
+            right_on: vec!["번호".to_string()],
+            suffixes: (String::new(), String::new()),
+            ..JoinOptions::on(&["학번"], JoinKind::Inner)
+        };
+        let error = join(&students(), &scores(), &options).unwrap_err().to_string();
+        assert!(error.contains("'이름'"), "{}", error);
+        assert_eq!("outer".parse::<JoinKind>().unwrap(), JoinKind::Full);
+        assert!("cross".parse::<JoinKind>().is_err());
+    }
+}
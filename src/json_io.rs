@@ -0,0 +1,308 @@
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::Path;
+use anyhow::{Result, anyhow};
+use serde_json::{Deserializer, Map, Number, Value as JsonValue};
+use crate::column::{Column, NullValues, Value};
+use crate::compression::{Compression, CompressedWriter};
+use crate::read_file::DataSet;
+
+/// JSON 저장 형식
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsonLayout {
+    /// 행마다 객체 하나인 배열: [{"a": 1, "b": 2}, ...]
+    Records,
+    /// 열 이름별 값 배열: {"a": [1, ...], "b": [2, ...]}
+    Columns,
+    /// 한 줄에 객체 하나 (NDJSON)
+    Lines,
+}
+
+/// JSON 배열(객체 목록) 또는 열 형식 객체를 읽음. 배열이 아니면 NDJSON으로 다시 시도
+pub fn read_json_file<P: AsRef<Path>>(file_path: P) -> Result<DataSet> {
+    let path = file_path.as_ref();
+    let text = std::fs::read_to_string(path)?;
+    read_json_str(&text, path)
+}
+
+/// 메모리에 있는 JSON 문자열을 읽음. file_path는 데이터셋에 기록할 이름
+pub fn read_json_str<P: AsRef<Path>>(text: &str, file_path: P) -> Result<DataSet> {
+    let path = file_path.as_ref();
+    match serde_json::from_str::<JsonValue>(text) {
+        Ok(JsonValue::Array(items)) => records_to_dataset(items, path),
+        Ok(JsonValue::Object(object)) if object.values().all(JsonValue::is_array) => {
+            columns_to_dataset(object, path)
+        }
+        Ok(JsonValue::Object(object)) => records_to_dataset(vec![JsonValue::Object(object)], path),
+        Ok(_) => Err(anyhow!("JSON file must contain an array of objects")),
+        // Several top-level values in one file are newline-delimited records
+        Err(_) => parse_ndjson(text, path),
+    }
+}
+
+/// 읽는 대로 JSON을 해석 (압축을 풀면서 읽는 파일 등). 최상위 값이 배열이나 열 형식 객체 하나면
+/// read_json_str과 같고, 값이 여러 개면 각 값을 레코드로 봄 (NDJSON)
+pub fn read_json_reader<R: Read, P: AsRef<Path>>(reader: R, file_path: P) -> Result<DataSet> {
+    let path = file_path.as_ref();
+    let mut values: Vec<JsonValue> = Vec::new();
+    for value in Deserializer::from_reader(BufReader::new(reader)).into_iter::<JsonValue>() {
+        values.push(value.map_err(|e| anyhow!("Invalid JSON: {}", e))?);
+    }
+    if values.len() == 1 {
+        return match values.pop() {
+            Some(JsonValue::Array(items)) => records_to_dataset(items, path),
+            Some(JsonValue::Object(object)) if object.values().all(JsonValue::is_array) => {
+                columns_to_dataset(object, path)
+            }
+            Some(JsonValue::Object(object)) => records_to_dataset(vec![JsonValue::Object(object)], path),
+            _ => Err(anyhow!("JSON file must contain an array of objects")),
+        };
+    }
+    records_to_dataset(values, path)
+}
+
+/// 읽는 대로 NDJSON을 한 줄씩 해석
+pub fn read_ndjson_reader<R: Read, P: AsRef<Path>>(reader: R, file_path: P) -> Result<DataSet> {
+    let mut items = Vec::new();
+    for (line_idx, line) in BufReader::new(reader).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let item = serde_json::from_str(&line)
+            .map_err(|e| anyhow!("Invalid JSON on line {}: {}", line_idx + 1, e))?;
+        items.push(item);
+    }
+    records_to_dataset(items, file_path.as_ref())
+}
+
+/// 한 줄에 JSON 객체 하나씩 있는 파일(NDJSON/JSON Lines)을 읽음
+pub fn read_ndjson_file<P: AsRef<Path>>(file_path: P) -> Result<DataSet> {
+    let path = file_path.as_ref();
+    let text = std::fs::read_to_string(path)?;
+    parse_ndjson(&text, path)
+}
+
+/// 메모리에 있는 NDJSON 문자열을 읽음. file_path는 데이터셋에 기록할 이름
+pub fn read_ndjson_str<P: AsRef<Path>>(text: &str, file_path: P) -> Result<DataSet> {
+    parse_ndjson(text, file_path.as_ref())
+}
+
+fn parse_ndjson(text: &str, path: &Path) -> Result<DataSet> {
+    let mut items = Vec::new();
+    for (line_idx, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let item = serde_json::from_str(line)
+            .map_err(|e| anyhow!("Invalid JSON on line {}: {}", line_idx + 1, e))?;
+        items.push(item);
+    }
+    records_to_dataset(items, path)
+}
+
+/// 객체 목록을 데이터셋으로 변환. 중첩 객체는 "a.b" 형식의 열 이름으로 펼치고
+/// 모든 레코드의 키를 처음 나온 순서대로 합침
+fn records_to_dataset(items: Vec<JsonValue>, path: &Path) -> Result<DataSet> {
+    let mut headers: Vec<String> = Vec::new();
+    let mut header_index: HashMap<String, usize> = HashMap::new();
+    let mut records = Vec::with_capacity(items.len());
+
+    for (index, item) in items.into_iter().enumerate() {
+        let JsonValue::Object(object) = item else {
+            return Err(anyhow!("Record {} is not a JSON object", index + 1));
+        };
+
+        let mut flat = Vec::new();
+        flatten_object("", object, &mut flat);
+        for (key, _) in &flat {
+            if !header_index.contains_key(key) {
+                header_index.insert(key.clone(), headers.len());
+                headers.push(key.clone());
+            }
+        }
+        records.push(flat);
+    }
+
+    let mut cells: Vec<Vec<Value>> = vec![vec![Value::Null; records.len()]; headers.len()];
+    for (row_idx, record) in records.into_iter().enumerate() {
+        for (key, value) in record {
+            cells[header_index[&key]][row_idx] = value;
+        }
+    }
+
+    Ok(build_dataset(headers, cells, path))
+}
+
+fn columns_to_dataset(object: Map<String, JsonValue>, path: &Path) -> Result<DataSet> {
+    let row_count = object
+        .values()
+        .filter_map(JsonValue::as_array)
+        .map(Vec::len)
+        .max()
+        .unwrap_or(0);
+
+    let mut headers = Vec::new();
+    let mut cells = Vec::new();
+    for (key, values) in object {
+        let JsonValue::Array(values) = values else {
+            return Err(anyhow!("Column '{}' is not an array", key));
+        };
+        let mut column: Vec<Value> = values.into_iter().map(json_to_value).collect();
+        column.resize(row_count, Value::Null);
+        headers.push(key);
+        cells.push(column);
+    }
+
+    Ok(build_dataset(headers, cells, path))
+}
+
+fn build_dataset(headers: Vec<String>, cells: Vec<Vec<Value>>, path: &Path) -> DataSet {
+    let null_values = NullValues::default();
+    let columns = cells
+        .into_iter()
+        .map(|values| Column::from_cells(values, &null_values))
+        .collect();
+    DataSet::from_columns(headers, columns, path.to_string_lossy().to_string())
+}
+
+fn flatten_object(prefix: &str, object: Map<String, JsonValue>, out: &mut Vec<(String, Value)>) {
+    for (key, value) in object {
+        let name = if prefix.is_empty() {
+            key
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        match value {
+            JsonValue::Object(nested) if !nested.is_empty() => flatten_object(&name, nested, out),
+            other => out.push((name, json_to_value(other))),
+        }
+    }
+}
+
+fn json_to_value(value: JsonValue) -> Value {
+    match value {
+        JsonValue::Null => Value::Null,
+        JsonValue::Bool(b) => Value::Boolean(b),
+        JsonValue::Number(n) => match n.as_i64() {
+            Some(i) => Value::Integer(i),
+            None => n.as_f64().map(Value::Float).unwrap_or(Value::Null),
+        },
+        JsonValue::String(s) => Value::String(s),
+        // Arrays and empty objects are kept as their JSON text
+        other => Value::String(other.to_string()),
+    }
+}
+
+fn value_to_json(value: Value) -> JsonValue {
+    match value {
+        Value::Null => JsonValue::Null,
+        Value::Integer(i) => JsonValue::from(i),
+        Value::Float(f) => Number::from_f64(f).map(JsonValue::Number).unwrap_or(JsonValue::Null),
+        Value::Boolean(b) => JsonValue::Bool(b),
+        other => JsonValue::String(other.to_string()),
+    }
+}
+
+fn row_object(dataset: &DataSet, row_idx: usize) -> JsonValue {
+    let object = dataset
+        .headers
+        .iter()
+        .zip(&dataset.columns)
+        .map(|(header, column)| (header.clone(), value_to_json(column.get(row_idx))))
+        .collect();
+    JsonValue::Object(object)
+}
+
+/// 데이터셋을 JSON으로 저장. 경로가 "-"이면 표준 출력에 쓰고, .gz 등 압축 확장자면 압축해서 저장
+pub fn save_dataset_to_json<P: AsRef<Path>>(dataset: &DataSet, output_path: P, layout: JsonLayout) -> Result<()> {
+    let path = output_path.as_ref();
+    let mut writer = CompressedWriter::create(path, Compression::from_path(path), "json")?;
+
+    match layout {
+        JsonLayout::Records => {
+            let records: Vec<JsonValue> = (0..dataset.row_count()).map(|i| row_object(dataset, i)).collect();
+            serde_json::to_writer_pretty(&mut writer, &records)?;
+            writeln!(writer)?;
+        }
+        JsonLayout::Columns => {
+            let object: Map<String, JsonValue> = dataset
+                .headers
+                .iter()
+                .zip(&dataset.columns)
+                .map(|(header, column)| {
+                    let values = column.values().map(value_to_json).collect();
+                    (header.clone(), JsonValue::Array(values))
+                })
+                .collect();
+            serde_json::to_writer_pretty(&mut writer, &object)?;
+            writeln!(writer)?;
+        }
+        JsonLayout::Lines => {
+            for row_idx in 0..dataset.row_count() {
+                serde_json::to_writer(&mut writer, &row_object(dataset, row_idx))?;
+                writeln!(writer)?;
+            }
+        }
+    }
+
+    writer.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read(text: &str) -> Result<DataSet> {
+        read_json_reader(text.as_bytes(), "test.json")
+    }
+
+    #[test]
+    fn json_reader_accepts_every_layout_of_read_json_str() {
+        for text in [
+            r#"[{"a": 1, "b": "x"}, {"a": 2, "b": "y"}]"#,
+            r#"{"a": [1, 2], "b": ["x", "y"]}"#,
+            "{\"a\": 1, \"b\": \"x\"}\n{\"a\": 2, \"b\": \"y\"}\n",
+        ] {
+            let streamed = read(text).unwrap();
+            let in_memory = read_json_str(text, "test.json").unwrap();
+            assert_eq!(streamed.headers, ["a", "b"], "{}", text);
+            assert_eq!(streamed.rows().collect::<Vec<_>>(), in_memory.rows().collect::<Vec<_>>(), "{}", text);
+        }
+        assert!(read("42").is_err());
+        assert!(read("[{\"a\": 1}").is_err());
+    }
+
+    #[test]
+    fn flattens_nested_objects_and_merges_keys() {
+        let text = r#"[{"id": 1, "user": {"name": "가", "age": 30}}, {"id": 2, "tags": ["a", "b"], "user": {"name": "나"}}]"#;
+        let dataset = read(text).unwrap();
+        assert_eq!(dataset.headers, ["id", "user.name", "user.age", "tags"]);
+        assert!(dataset.column("user.age").unwrap().is_null(1));
+        assert_eq!(dataset.column("tags").unwrap().get_string(1), r#"["a","b"]"#);
+    }
+
+    #[test]
+    fn every_layout_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let text = r#"[{"이름": "가", "점수": 90, "평균": 1.5, "합격": true}, {"이름": "나", "점수": null, "평균": 2.0, "합격": false}]"#;
+        let original = read(text).unwrap();
+        let types = |dataset: &DataSet| dataset.columns.iter().map(Column::column_type).collect::<Vec<_>>();
+        let layouts = [("r.json", JsonLayout::Records), ("c.json.gz", JsonLayout::Columns), ("l.ndjson", JsonLayout::Lines)];
+        for (name, layout) in layouts {
+            let path = dir.path().join(name);
+            save_dataset_to_json(&original, &path, layout).unwrap();
+            let read = crate::read_file::read_file(&path).unwrap();
+
+            assert_eq!(read.headers, original.headers, "{}", name);
+            assert_eq!(read.rows().collect::<Vec<_>>(), original.rows().collect::<Vec<_>>(), "{}", name);
+            assert_eq!(types(&read), types(&original), "{}", name);
+        }
+    }
+
+    #[test]
+    fn ndjson_reader_reports_the_bad_line() {
+        let error = read_ndjson_reader("{\"a\": 1}\n\n{\"a\": \n".as_bytes(), "test.ndjson").unwrap_err();
+        assert!(error.to_string().contains("line 3"), "{}", error);
+    }
+}
@@ -0,0 +1,20 @@
+pub mod column;
+pub mod csv_sniffer;
+pub mod encoding;
+pub mod compression;
+pub mod read_file;
+pub mod batch_reader;
+pub mod json_io;
+pub mod columnar_io;
+pub mod sqlite_io;
+pub mod multi_file;
+pub mod fixed_width;
+pub mod schema;
+pub mod number_format;
+pub mod dates;
+pub mod filter;
+pub mod sort;
+pub mod group_by;
+pub mod join;
+pub mod reshape;
+pub mod data_analyzer;
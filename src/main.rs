@@ -1,45 +1,269 @@
-mod read_file;
-mod data_analyzer;
-
-use read_file::{read_file, save_dataset_to_csv, extract_subset};
-use data_analyzer::DataAnalyzer;
-use anyhow::Result;
+use data_analyzer::read_file::{
+    apply_value_formats, read_file_with_options, read_workbook, save_dataset_to_csv_with_options, save_dataset_with_options, extract_subset,
+    list_sheets, CsvWriteOptions, DataSet, FileFormat, ReadOptions, SheetSelector, STDIO_PATH,
+};
+use data_analyzer::sqlite_io::SqliteSource;
+use data_analyzer::multi_file;
+use data_analyzer::fixed_width::{FixedWidthOptions, FixedWidthSpec, WidthUnit};
+use data_analyzer::batch_reader::{read_csv_batches, DEFAULT_BATCH_SIZE};
+use data_analyzer::data_analyzer::{ColumnSummary, DataAnalyzer, DEFAULT_DISTINCT_LIMIT};
+use data_analyzer::schema::{self, SchemaOptions, SemanticType};
+use data_analyzer::number_format::NumberFormat;
+use data_analyzer::dates::{self, DateFormat};
+use data_analyzer::filter::{self, Filter};
+use data_analyzer::sort::{self, SortOptions};
+use data_analyzer::group_by;
+use data_analyzer::join::{self, JoinOptions, JoinReport};
+use data_analyzer::reshape::{self, MeltOptions, PivotOptions};
+use data_analyzer::column::{ColumnType, Value};
+use anyhow::{Result, anyhow};
 use std::path::Path;
 use std::io::{self, Write};
 
 fn main() -> Result<()> {
+    // 인자가 있으면 명령어 하나만 실행 (셸 파이프라인용)
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        if let Err(e) = run_command_line(&args) {
+            eprintln!("오류: {:#}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     // 인사말 출력
     println!("안녕하세요. 데이터 분석 프로그램입니다");
     println!();
     print_usage();
 
+    // load/filter로 만든 현재 데이터. analyze와 save에 경로 없이 이어서 사용
+    let mut current: Option<DataSet> = None;
+
     loop {
-        print!("\n명령어를 입력하세요 (help, analyze, demo, exit): ");
+        print!("\n명령어를 입력하세요 (help, analyze, load, filter, sort, groupby, join, pivot, melt, select, save, sheets, demo, exit): ");
         io::stdout().flush()?;
 
         let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
+        if io::stdin().read_line(&mut input)? == 0 {
+            break;
+        }
         let input = input.trim();
 
         if input.is_empty() {
             continue;
         }
 
-        let parts: Vec<&str> = input.split_whitespace().collect();
-        let command = parts[0];
+        let parts = split_command_line(input);
+        if parts.is_empty() {
+            continue;
+        }
+        let command = parts[0].as_str();
 
         match command {
+            "analyze" if current.is_some() && parts.get(1).is_none_or(|arg| arg.starts_with("--")) => {
+                let dataset = current.as_ref().unwrap();
+                let result = parse_options_only(&parts[1..], CURRENT_ANALYZE_FLAGS).and_then(|options| {
+                    println!("현재 데이터 분석 중: {} 행 ({})", dataset.row_count(), dataset.file_path);
+                    // Schema date overrides and the date range work on a copy, leaving the loaded data as it is
+                    if options.schema.has_date_overrides() || options.date_range.is_some() {
+                        let prepared = prepare_dataset(dataset.clone(), &options)?;
+                        return analyze_dataset(&prepared, &dataset.file_path, &options);
+                    }
+                    analyze_dataset(dataset, &dataset.file_path, &options)
+                });
+                if let Err(e) = result {
+                    println!("데이터 분석 중 오류가 발생했습니다: {}", e);
+                }
+            }
             "analyze" => {
                 if parts.len() < 2 {
-                    println!("사용법: analyze <파일경로>");
+                    println!("사용법: analyze <파일경로> [--sheet <이름>] [--sheet-index <번호>] [--header-row <행>] [--skip-rows <개수>] [--range <B4:F20>] [--stack] [--columns <열1,열2>] [--table <테이블>] [--query <SQL>] [--stream] [--batch-size <행 수>] [--max-distinct <개수>] [--format <형식>] [--output <경로|->] [--source-column <열>] [--schema <union|strict>] [--widths <이름:시작:폭,...>] [--width-spec <파일>] [--width-unit <chars|bytes>] [--ragged-rows <strict|lenient>] [--encoding <인코딩>] [--delimiter <문자>] [--quote <문자>] [--header|--no-header] [--bom] [--schema-file <파일>] [--type-threshold <비율>] [--decimal-comma] [--percent-as-fraction] [--plain-numbers] [--date-format <열>=<형식>] [--timezone <시간대>] [--date-range <열>=<시작>..<끝>]");
                     println!("예시: analyze data.csv");
+                    println!("예시: analyze report.xlsx --sheet 3월 --header-row 3 --range B3:H40");
+                    println!("예시: analyze results.db --query \"SELECT * FROM runs WHERE score > 0.5\"");
+                    println!("예시: analyze access_log.csv.gz --stream --batch-size 50000");
+                    println!("예시: analyze \"logs/2024-*.csv\" --source-column file --schema strict");
+                    println!("예시: analyze export.dat --width-spec layout.txt --width-unit bytes");
+                    continue;
+                }
+                let file_path = &parts[1];
+                if file_path == STDIO_PATH {
+                    println!("표준 입력(-)은 명령행 실행에서만 사용할 수 있습니다. 예시: cat data.csv | data-analyzer analyze -");
                     continue;
                 }
-                let file_path = parts[1];
-                if let Err(e) = analyze_file(file_path) {
+                let result = parse_analyze_options(&parts[2..])
+                    .and_then(|options| analyze_file(file_path, &options));
+                if let Err(e) = result {
                     println!("파일 분석 중 오류가 발생했습니다: {}", e);
                 }
             }
+            "load" => {
+                if parts.len() < 2 {
+                    println!("사용법: load <파일경로> [analyze의 읽기 옵션]");
+                    println!("예시: load scores.csv");
+                    continue;
+                }
+                let result = parse_analyze_options(&parts[2..]).and_then(|options| load_dataset(&parts[1], &options));
+                match result {
+                    Ok(dataset) => {
+                        println!("{} 행 x {} 열을 불러왔습니다: {}", dataset.row_count(), dataset.column_count(), parts[1]);
+                        current = Some(dataset);
+                    }
+                    Err(e) => println!("파일을 불러오는 중 오류가 발생했습니다: {}", e),
+                }
+            }
+            "filter" => {
+                // The expression keeps its own quoting, so it is taken from the raw input
+                let expression = input[command.len()..].trim();
+                let Some(dataset) = &current else {
+                    println!("먼저 load 명령어로 파일을 불러오세요");
+                    continue;
+                };
+                if expression.is_empty() {
+                    print_filter_usage();
+                    continue;
+                }
+                match Filter::parse(expression).and_then(|filter| filter::filter_dataset(dataset, &filter)) {
+                    Ok(filtered) => {
+                        println!("필터 결과: {} / {} 행", filtered.row_count(), dataset.row_count());
+                        current = Some(filtered);
+                    }
+                    Err(e) => println!("필터 적용 중 오류가 발생했습니다: {}", e),
+                }
+            }
+            "sort" => {
+                let Some(dataset) = &current else {
+                    println!("먼저 load 명령어로 파일을 불러오세요");
+                    continue;
+                };
+                if parts.len() < 2 {
+                    print_sort_usage();
+                    continue;
+                }
+                match parse_sort_command(&parts[1..]).and_then(|options| sort::sort_dataset(dataset, &options)) {
+                    Ok(sorted) => {
+                        println!("{} 행을 정렬했습니다.", sorted.row_count());
+                        current = Some(sorted);
+                    }
+                    Err(e) => println!("정렬 중 오류가 발생했습니다: {}", e),
+                }
+            }
+            "groupby" => {
+                let Some(dataset) = &current else {
+                    println!("먼저 load 명령어로 파일을 불러오세요");
+                    continue;
+                };
+                if parts.len() < 3 {
+                    print_groupby_usage();
+                    continue;
+                }
+                match run_groupby(dataset, &parts[1], &parts[2..]) {
+                    Ok(Some(grouped)) => {
+                        print_rows(&grouped, 20);
+                        println!("그룹 {}개를 현재 데이터로 사용합니다.", grouped.row_count());
+                        current = Some(grouped);
+                    }
+                    Ok(None) => {}
+                    Err(e) => println!("그룹 집계 중 오류가 발생했습니다: {}", e),
+                }
+            }
+            "join" => {
+                let Some(dataset) = &current else {
+                    println!("먼저 load 명령어로 파일을 불러오세요");
+                    continue;
+                };
+                if parts.len() < 3 {
+                    print_join_usage();
+                    continue;
+                }
+                match run_join(dataset, &parts[1], &parts[2], &parts[3..]) {
+                    Ok((joined, report)) => {
+                        for line in join_report_lines(&report) {
+                            println!("{}", line);
+                        }
+                        println!("조인 결과: {} 행 x {} 열", joined.row_count(), joined.column_count());
+                        current = Some(joined);
+                    }
+                    Err(e) => println!("조인 중 오류가 발생했습니다: {}", e),
+                }
+            }
+            "pivot" => {
+                let Some(dataset) = &current else {
+                    println!("먼저 load 명령어로 파일을 불러오세요");
+                    continue;
+                };
+                if parts.len() < 4 {
+                    print_pivot_usage();
+                    continue;
+                }
+                match parse_pivot_options(&parts[1..]).and_then(|options| reshape::pivot(dataset, &options)) {
+                    Ok(pivoted) => {
+                        print_rows(&pivoted, 20);
+                        println!("넓은 형식으로 바꿨습니다: {} 행 x {} 열", pivoted.row_count(), pivoted.column_count());
+                        current = Some(pivoted);
+                    }
+                    Err(e) => println!("피벗 중 오류가 발생했습니다: {}", e),
+                }
+            }
+            "melt" | "unpivot" => {
+                let Some(dataset) = &current else {
+                    println!("먼저 load 명령어로 파일을 불러오세요");
+                    continue;
+                };
+                match parse_melt_options(&parts[1..]).and_then(|options| reshape::melt(dataset, &options)) {
+                    Ok(melted) => {
+                        print_rows(&melted, 20);
+                        println!("긴 형식으로 바꿨습니다: {} 행 x {} 열", melted.row_count(), melted.column_count());
+                        current = Some(melted);
+                    }
+                    Err(e) => {
+                        println!("언피벗 중 오류가 발생했습니다: {}", e);
+                        print_melt_usage();
+                    }
+                }
+            }
+            "select" => {
+                let Some(dataset) = &current else {
+                    println!("먼저 load 명령어로 파일을 불러오세요");
+                    continue;
+                };
+                if parts.len() < 2 {
+                    println!("사용법: select <열1,열2,...>");
+                    continue;
+                }
+                match extract_subset(dataset, None, Some(split_names(&parts[1..].join(" ")))) {
+                    Ok(selected) => {
+                        println!("{} 열을 남겼습니다: {}", selected.column_count(), selected.headers.join(", "));
+                        current = Some(selected);
+                    }
+                    Err(e) => println!("열 선택 중 오류가 발생했습니다: {}", e),
+                }
+            }
+            "save" => {
+                let Some(dataset) = &current else {
+                    println!("먼저 load 명령어로 파일을 불러오세요");
+                    continue;
+                };
+                if parts.len() < 2 {
+                    println!("사용법: save <경로> [--output-format <형식>] [--bom]");
+                    continue;
+                }
+                let result = parse_options_only(&parts[2..], SAVE_FLAGS)
+                    .and_then(|options| options.save(dataset, &parts[1]));
+                match result {
+                    Ok(()) => println!("{} 행이 {}에 저장되었습니다.", dataset.row_count(), parts[1]),
+                    Err(e) => println!("저장 중 오류가 발생했습니다: {}", e),
+                }
+            }
+            "sheets" => {
+                if parts.len() < 2 {
+                    println!("사용법: sheets <Excel 파일경로>");
+                    continue;
+                }
+                if let Err(e) = print_sheets(&parts[1]) {
+                    println!("시트 목록 조회 중 오류가 발생했습니다: {}", e);
+                }
+            }
             "demo" => {
                 if let Err(e) = run_demo() {
                     println!("데모 실행 중 오류가 발생했습니다: {}", e);
@@ -54,7 +278,7 @@ fn main() -> Result<()> {
             }
             _ => {
                 println!("알 수 없는 명령어입니다: {}", command);
-                println!("사용 가능한 명령어: help, analyze, demo, exit");
+                println!("사용 가능한 명령어: help, analyze, load, filter, sort, groupby, join, pivot, melt, select, save, sheets, demo, exit");
             }
         }
     }
@@ -65,11 +289,74 @@ fn main() -> Result<()> {
 fn print_usage() {
     println!("데이터 분석기 (Data Analyzer)");
     println!("사용 가능한 명령어:");
-    println!("  analyze <파일경로>  - CSV 또는 Excel 파일 분석");
+    println!("  analyze <파일경로>  - CSV, Excel, JSON, Parquet, Arrow, SQLite 파일 분석 (.gz/.zst/.bz2/.zip 압축 포함)");
+    println!("                        디렉터리나 글롭 패턴(\"data/*.csv\")을 주면 맞는 파일을 모두 합쳐 분석");
+    println!("    --sheet <이름>       읽을 시트 이름 (Excel)");
+    println!("    --sheet-index <번호> 읽을 시트 순번, 1부터 (Excel)");
+    println!("    --header-row <행>    헤더 행 번호, 1부터 / 0이면 헤더 없음 (Excel)");
+    println!("    --skip-rows <개수>   헤더 다음에 건너뛸 행 수 (Excel), 데이터 앞에서 건너뛸 줄 수 (고정폭)");
+    println!("    --range <B4:F20>     읽을 셀 범위 (Excel)");
+    println!("    --stack              헤더가 같은 시트를 모두 합쳐 분석, 'sheet' 열 추가 (Excel)");
+    println!("    --stack-column <열>  시트를 합칠 때 추가할 열 이름 (Excel)");
+    println!("    --columns <열1,열2>  지정한 열만 읽음 (Parquet/Arrow는 해당 열만 디코딩)");
+    println!("    --table <테이블>     읽을 테이블 이름 (SQLite)");
+    println!("    --query <SQL>        실행할 SELECT 쿼리 (SQLite)");
+    println!("    --stream             메모리에 모두 올리지 않고 나눠 읽으며 통계/빈도만 계산 (CSV)");
+    println!("    --batch-size <행 수> 한 번에 읽을 행 수, --stream 포함 (기본 10000)");
+    println!("    --max-distinct <개수> --stream에서 열마다 빈도를 셀 값 종류 상한, 넘으면 자주 나온 값만 남김 (기본 100000)");
+    println!("    --format <형식>      입력 형식 지정 (csv, json, ndjson, parquet, arrow, excel, sqlite, fwf)");
+    println!("    --output <경로|->    열별 통계 요약 표 저장, '-'이면 표준 출력에 쓰고 다른 출력은 생략");
+    println!("    --output-format <형식> 요약 표 저장 형식 (기본: 확장자, 표준 출력은 csv)");
+    println!("    --source-column <열> 여러 파일을 합칠 때 출처 파일 이름을 담을 열 추가");
+    println!("    --schema <방식>      여러 파일의 열이 다를 때: union(기본, 없는 열은 결측) / strict(오류)");
+    println!("    --widths <정의>      고정폭 열 정의, 이름:시작:폭 또는 이름:폭을 쉼표로 구분 (시작은 1부터)");
+    println!("    --width-spec <파일>  고정폭 열 정의 파일, 한 줄에 '이름 시작 폭' 또는 '이름 폭'");
+    println!("    --width-unit <단위>  고정폭 위치/폭 단위: chars(기본, 글자 수) / bytes(CP949 한글은 2바이트)");
+    println!("    --schema-file <파일> 열 종류 직접 지정, 한 줄에 '열 종류' 또는 JSON 객체");
+    println!("                         (integer, float, boolean, date, datetime, categorical, text, identifier)");
+    println!("    --type-threshold <비율> 숫자/날짜 등으로 볼 최소 값 비율, 0~1 (기본 0.9)");
+    println!("    --decimal-comma      소수점이 쉼표인 숫자로 해석 (1.234,5), 기본은 1,234.5");
+    println!("    --percent-as-fraction 퍼센트를 비율로 저장 (15.3% → 0.153), 기본은 15.3");
+    println!("    --plain-numbers      천 단위 구분자, 통화 기호(₩, 원, $), 퍼센트, 괄호 음수를 해석하지 않음");
+    println!("    --date-format <열>=<형식> 날짜 열의 형식 지정, 여러 번 사용 가능 (예: 일자=%d/%m/%Y, ts=unix, ts=unix_ms)");
+    println!("                         2024-03-01, 2024.03.01, 2024/03/01, 2024년 3월 1일 형식은 지정하지 않아도 인식");
+    println!("    --timezone <시간대>  Unix 시각과 시간대가 붙은 값을 변환할 시간대 (UTC(기본), KST, +09:00)");
+    println!("    --date-range <열>=<시작>..<끝> 날짜가 범위 안인 행만 분석, 끝 날짜 포함 (한쪽은 생략 가능)");
+    println!("    --ragged-rows <방식> 필드 개수가 헤더와 다른 행: strict(기본, 모두 나열하고 중단) / lenient(채우거나 잘라내고 보고) (CSV)");
+    println!("    --encoding <인코딩>  입력 인코딩 지정: utf-8, utf-16le, utf-16be, cp949, euc-kr (CSV, 기본은 자동 판별)");
+    println!("    --delimiter <문자>   구분자 지정, 탭은 tab (CSV, 기본은 자동 판별)");
+    println!("    --quote <문자>       따옴표 문자 지정 (CSV, 기본은 자동 판별)");
+    println!("    --header / --no-header 첫 행을 헤더로 볼지 지정 (CSV, 기본은 자동 판별)");
+    println!("    --bom                CSV로 저장할 때 한글 Excel용 UTF-8 BOM을 붙임");
+    println!("  load <파일경로>     - 파일을 현재 데이터로 불러옴 (analyze의 읽기 옵션 사용 가능)");
+    println!("  filter <식>        - 현재 데이터에서 조건에 맞는 행만 남김 (예: filter 도시 = 서울 and 점수 >= 80)");
+    println!("  sort <열> [desc], ... - 현재 데이터를 여러 열 기준으로 정렬 (--nulls first|last, 기본 last)");
+    println!("  groupby <키1,키2> <집계, ...> - 키 열로 묶어 그룹마다 집계 (예: groupby 도시,등급 count, mean(점수))");
+    println!("                       --stats <열>: 그룹별 기초통계량 출력");
+    println!("  join <파일경로> <키1,키2> - 현재 데이터(왼쪽)와 다른 파일(오른쪽)을 키 열로 조인 (--how inner|left|right|full|semi|anti)");
+    println!("                       --right-on <키>: 오른쪽 키 이름이 다를 때, --suffixes <_a,_b>: 겹치는 열 이름 접미사");
+    println!("  pivot <키> <열> <값> - 긴 형식을 넓은 형식으로: '열'의 값마다 열을 만들어 '값'을 채움 (--agg <집계>)");
+    println!("  melt [<id열>]      - 넓은 형식을 긴 형식으로: id 열이 아닌 열을 (variable, value) 행으로 펼침 (unpivot)");
+    println!("  select <열1,열2>   - 현재 데이터에서 지정한 열만 남김");
+    println!("  save <경로>        - 현재 데이터를 저장 (형식은 확장자, --output-format으로 지정 가능, --bom)");
+    println!("                       현재 데이터가 있으면 analyze를 경로 없이 실행해 현재 데이터를 분석");
+    println!("                       (--output, --output-format, --bom, --schema-file, --type-threshold, --date-range만 사용 가능)");
+    println!("  sheets <파일경로>   - Excel 파일의 시트 목록과 크기 표시");
     println!("  demo               - 샘플 데이터로 데모 실행");
     println!("  help               - 도움말 표시");
     println!("  exit               - 프로그램 종료");
     println!();
+    println!("명령행 실행 (파이프라인):");
+    println!("  data-analyzer analyze <파일경로|-> [옵션]   '-'는 표준 입력, 형식은 --format 또는 내용으로 판별");
+    println!("  예시: curl -s https://example.com/data.csv | data-analyzer analyze - --output - > stats.csv");
+    println!("  data-analyzer filter <파일경로|-> <식> [--output <경로|->]   조건에 맞는 행 저장, 기본은 표준 출력(CSV)");
+    println!("  data-analyzer sort <파일경로|-> <열 [desc], ...> [--nulls first|last] [--output <경로|->]   정렬한 행 저장");
+    println!("  data-analyzer groupby <파일경로|-> <키1,키2> <집계, ...> [--stats <열>] [--output <경로|->]   그룹별 집계 저장");
+    println!("  data-analyzer pivot <파일경로|-> <키> <열> <값> [--agg <집계>] [--output <경로|->]   넓은 형식으로 바꿔 저장");
+    println!("  data-analyzer melt <파일경로|-> [<id열>] [--value-columns <열>] [--output <경로|->]   긴 형식으로 바꿔 저장");
+    println!("  data-analyzer join <왼쪽 파일> <오른쪽 파일> <키1,키2> [--how <방식>] [--right-on <키>] [--output <경로|->]   조인 결과 저장");
+    println!("      읽기 옵션은 두 파일 모두에 적용, --left-<옵션>/--right-<옵션>은 그쪽 파일에만 적용 (예: --left-sheet 명단 --right-columns 학번,점수)");
+    println!();
     println!("주요 기능:");
     println!("  - CSV/Excel 파일 읽기");
     println!("  - 기초통계량 계산 (평균, 중앙값, 표준편차 등)");
@@ -79,52 +366,627 @@ fn print_usage() {
     println!("  - 표본 추출");
 }
 
-fn analyze_file(file_path: &str) -> Result<()> {
-    println!("파일 분석 중: {}", file_path);
+fn print_filter_usage() {
+    println!("사용법: filter <식>");
+    println!("  비교: = != < <= > >=, 목록: 열 in (값1, 값2), 결측: 열 is null / is not null");
+    println!("  문자열: 열 contains 값, 열 matches 정규식, 범위: 열 between 시작 and 끝 (날짜는 끝 날짜 포함)");
+    println!("  조합: and, or, not, 괄호. 공백이 있는 값은 '작은따옴표', 열 이름은 \"큰따옴표\"로 감쌈");
+    println!("예시: filter 도시 = 서울 and 점수 >= 80");
+    println!("예시: filter 등급 in (A, B) and not 비고 is null");
+    println!("예시: filter 주문일 between 2024-03-01 and 2024-03-31 or 이름 matches '^김'");
+}
+
+fn print_sort_usage() {
+    println!("사용법: sort <열> [asc|desc], <열> [asc|desc], ... [--nulls first|last]");
+    println!("  숫자/날짜 열은 값 순서, 문자열 열은 가나다순. 결측은 기본적으로 맨 뒤");
+    println!("예시: sort 도시, 점수 desc");
+    println!("예시: sort 가입일 desc --nulls first");
+}
+
+/// sort 명령어 인자 해석. --nulls를 뺀 나머지를 이어 붙여 정렬 기준으로 사용
+fn parse_sort_command(args: &[String]) -> Result<SortOptions> {
+    let (nulls, keys) = take_option(args, "--nulls")?;
+    sort_options(&keys.join(" "), nulls)
+}
+
+fn sort_options(keys: &str, nulls: Option<String>) -> Result<SortOptions> {
+    Ok(SortOptions {
+        keys: sort::parse_sort_keys(keys)?,
+        nulls: nulls.map(|value| value.parse()).transpose()?.unwrap_or_default(),
+    })
+}
+
+fn print_groupby_usage() {
+    println!("사용법: groupby <키1,키2> <집계, ...> [--stats <열>]");
+    println!("  집계: count, count(열), sum(열), mean(열), median(열), min(열), max(열), std(열),");
+    println!("        quantile(열, 0.9) 또는 p90(열), nunique(열), first(열), last(열)");
+    println!("  결과는 키 열과 '열_집계' 이름의 열로 된 표이며 현재 데이터가 됨");
+    println!("예시: groupby 도시,등급 count, mean(점수), max(점수)");
+    println!("예시: groupby 도시 --stats 점수");
+}
+
+/// groupby 인자 실행. 집계가 있으면 결과 표를 반환하고, --stats가 있으면 그룹별 기초통계량을 출력
+fn run_groupby(dataset: &DataSet, keys: &str, args: &[String]) -> Result<Option<DataSet>> {
+    let keys = split_names(keys);
+    let (stats_column, aggregations) = take_option(args, "--stats")?;
+
+    if let Some(column) = &stats_column {
+        let analyzer = DataAnalyzer::new();
+        let grouped = analyzer.grouped_stats(dataset, &keys, column)?;
+        analyzer.print_grouped_stats(&grouped, &keys, column);
+    }
+    if aggregations.is_empty() {
+        if stats_column.is_none() {
+            return Err(anyhow!("집계를 하나 이상 지정하거나 --stats를 사용하세요"));
+        }
+        return Ok(None);
+    }
+
+    let aggregations = group_by::parse_aggregations(&aggregations.join(" "))?;
+    group_by::group_by(dataset, &keys, &aggregations).map(Some)
+}
+
+fn print_pivot_usage() {
+    println!("사용법: pivot <키1,키2> <열> <값> [--agg <집계>]");
+    println!("  키 열로 묶은 그룹이 한 행, '열'의 값마다 결과 열이 하나씩 생기고 칸에는 '값' 열의 값이 들어감");
+    println!("  같은 칸에 값이 여러 개면 오류이며 --agg로 합칠 방법을 지정 (count, sum, mean, median, min, max, std, p90, nunique, first, last)");
+    println!("예시: pivot 응답자 문항 응답");
+    println!("예시: pivot 도시 월 매출 --agg sum");
+}
+
+fn print_melt_usage() {
+    println!("사용법: melt [<id열1,id열2>] [--value-columns <열1,열2>] [--var-name <이름>] [--value-name <이름>] [--drop-nulls]");
+    println!("  id 열은 그대로 반복하고, 나머지 열(또는 --value-columns)을 열 이름과 값 두 열로 펼침");
+    println!("  결과 열 이름은 기본 variable, value이며 --drop-nulls는 값이 결측인 행을 뺌");
+    println!("예시: melt 응답자 --var-name 문항 --value-name 응답");
+}
+
+/// 쉼표로 구분한 열 이름 목록
+fn split_names(text: &str) -> Vec<String> {
+    text.split(',').map(|name| name.trim().to_string()).filter(|name| !name.is_empty()).collect()
+}
+
+/// pivot 인자(키, 열, 값, --agg) 해석
+fn parse_pivot_options(args: &[String]) -> Result<PivotOptions> {
+    let (aggregation, rest) = take_option(args, "--agg")?;
+    let [index, columns, values] = rest.as_slice() else {
+        return Err(anyhow!("pivot에는 키 열, 열 이름이 될 열, 값 열이 필요합니다"));
+    };
+    Ok(PivotOptions {
+        index: split_names(index),
+        columns: columns.clone(),
+        values: values.clone(),
+        aggregation: aggregation.map(|name| name.parse()).transpose()?,
+    })
+}
+
+/// melt 인자 해석. 처음 인자가 옵션이 아니면 id 열 목록
+fn parse_melt_options(args: &[String]) -> Result<MeltOptions> {
+    let (options, rest) = parse_melt_args(args)?;
+    if let Some(arg) = rest.first() {
+        return Err(anyhow!("알 수 없는 인자입니다: {}", arg));
+    }
+    Ok(options)
+}
+
+/// melt 옵션을 꺼내고 나머지 인자(읽기 옵션)를 반환
+fn parse_melt_args(args: &[String]) -> Result<(MeltOptions, Vec<String>)> {
+    let (ids, args) = match args.first() {
+        Some(arg) if !arg.starts_with("--") => (split_names(arg), &args[1..]),
+        _ => (Vec::new(), args),
+    };
+    let (value_columns, rest) = take_option(args, "--value-columns")?;
+    let (variable_name, rest) = take_option(&rest, "--var-name")?;
+    let (value_name, rest) = take_option(&rest, "--value-name")?;
+    let drop_nulls = rest.iter().any(|arg| arg == "--drop-nulls");
+    let rest = rest.into_iter().filter(|arg| arg != "--drop-nulls").collect();
+
+    let defaults = MeltOptions::default();
+    let options = MeltOptions {
+        id_columns: ids,
+        value_columns: value_columns.as_deref().map(split_names).unwrap_or_default(),
+        variable_name: variable_name.unwrap_or(defaults.variable_name),
+        value_name: value_name.unwrap_or(defaults.value_name),
+        drop_nulls,
+    };
+    Ok((options, rest))
+}
+
+fn print_join_usage() {
+    println!("사용법: join <파일경로> <키1,키2> [--how <방식>] [--right-on <키1,키2>] [--suffixes <_왼쪽,_오른쪽>] [읽기 옵션]");
+    println!("  방식: inner(기본, 양쪽에 있는 키), left, right, full(양쪽 모두), semi(짝이 있는 행), anti(짝이 없는 행)");
+    println!("  현재 데이터가 왼쪽, 파일이 오른쪽이며 읽기 옵션은 오른쪽 파일에 적용됨 (--right-<옵션>으로 써도 같음)");
+    println!("  명령행에서는 읽기 옵션이 두 파일 모두에 적용되고, --left-<옵션>/--right-<옵션>은 그쪽 파일에만 적용됨");
+    println!("  키가 아닌 열 이름이 겹치면 접미사를 붙임 (기본 _left, _right)");
+    println!("예시: join 점수.csv 학번 --how left");
+    println!("예시: join 학생.csv 학번,학기 --right-on 번호,학기 --how anti");
+}
+
+/// join 인자 해석 후 실행. 키 옵션을 뺀 나머지 인자는 오른쪽 파일의 읽기 옵션 (왼쪽은 이미 불러온 현재 데이터)
+fn run_join(left: &DataSet, right_path: &str, keys: &str, args: &[String]) -> Result<(DataSet, JoinReport)> {
+    let (options, rest) = parse_join_options(keys, args)?;
+    if let Some(flag) = rest.iter().find(|arg| arg.starts_with("--left-")) {
+        return Err(anyhow!("왼쪽은 이미 불러온 현재 데이터라서 {} 옵션을 쓸 수 없습니다", flag));
+    }
+    let (_, right_args) = split_join_read_args(&rest);
+    let right = load_dataset(right_path, &parse_analyze_options(&right_args)?)?;
+    let result = join::join(left, &right, &options)?;
+    Ok((result.dataset, result.report))
+}
+
+fn parse_join_options(keys: &str, args: &[String]) -> Result<(JoinOptions, Vec<String>)> {
+    let (how, rest) = take_option(args, "--how")?;
+    let (right_on, rest) = take_option(&rest, "--right-on")?;
+    let (suffixes, rest) = take_option(&rest, "--suffixes")?;
+
+    let mut options = JoinOptions {
+        kind: how.map(|how| how.parse()).transpose()?.unwrap_or_default(),
+        left_on: split_names(keys),
+        right_on: right_on.as_deref().map(split_names).unwrap_or_default(),
+        ..JoinOptions::default()
+    };
+    if let Some(suffixes) = suffixes {
+        let (left, right) = suffixes
+            .split_once(',')
+            .ok_or_else(|| anyhow!("--suffixes는 '_왼쪽,_오른쪽' 형식이어야 합니다: {}", suffixes))?;
+        options.suffixes = (left.trim().to_string(), right.trim().to_string());
+    }
+    Ok((options, rest))
+}
+
+/// 조인할 두 파일의 읽기 옵션을 나눔. 접두사가 없는 옵션은 양쪽, --left-/--right-를 붙인 옵션은 그쪽 파일에만 들어감
+fn split_join_read_args(args: &[String]) -> (Vec<String>, Vec<String>) {
+    let mut left = Vec::new();
+    let mut right = Vec::new();
+    // Values follow their flag to the same side
+    let mut sides = (true, true);
+    for arg in args {
+        let arg = if let Some(flag) = arg.strip_prefix("--left-") {
+            sides = (true, false);
+            format!("--{}", flag)
+        } else if let Some(flag) = arg.strip_prefix("--right-") {
+            sides = (false, true);
+            format!("--{}", flag)
+        } else {
+            if arg.starts_with("--") {
+                sides = (true, true);
+            }
+            arg.clone()
+        };
+        if sides.0 {
+            left.push(arg.clone());
+        }
+        if sides.1 {
+            right.push(arg);
+        }
+    }
+    (left, right)
+}
+
+/// 조인 보고 내용. 중복 키 경고와 짝이 없는 키 (쪽마다 최대 10개)
+fn join_report_lines(report: &JoinReport) -> Vec<String> {
+    const SHOWN: usize = 10;
+    let list = |keys: &[String]| {
+        let shown: Vec<&str> = keys.iter().take(SHOWN).map(String::as_str).collect();
+        let more = if keys.len() > SHOWN { format!(" 외 {}개", keys.len() - SHOWN) } else { String::new() };
+        format!("[{}]{}", shown.join("], ["), more)
+    };
+    let duplicates = |keys: &[(String, usize)]| {
+        let shown: Vec<String> = keys.iter().take(SHOWN).map(|(key, count)| format!("[{}] {}번", key, count)).collect();
+        let more = if keys.len() > SHOWN { format!(" 외 {}개", keys.len() - SHOWN) } else { String::new() };
+        format!("{}{}", shown.join(", "), more)
+    };
+
+    let mut lines = Vec::new();
+    for (key, left_type, right_type) in &report.type_mismatches {
+        lines.push(format!(
+            "경고: 키 '{}'의 타입이 양쪽에서 다릅니다 (왼쪽 {}, 오른쪽 {}). 값이 같아 보여도 짝이 되지 않을 수 있습니다 (예: 001과 1)",
+            key, left_type, right_type
+        ));
+    }
+    if !report.left_duplicates.is_empty() {
+        lines.push(format!("경고: 왼쪽에 중복된 키 {}개: {}", report.left_duplicates.len(), duplicates(&report.left_duplicates)));
+    }
+    if !report.right_duplicates.is_empty() {
+        lines.push(format!(
+            "경고: 오른쪽에 중복된 키 {}개 (짝이 되는 왼쪽 행이 늘어남): {}",
+            report.right_duplicates.len(),
+            duplicates(&report.right_duplicates)
+        ));
+    }
+    if report.is_many_to_many() {
+        lines.push("경고: 양쪽 모두 중복된 키가 있어 다대다로 조인되었습니다".to_string());
+    }
+    if !report.left_unmatched.is_empty() {
+        lines.push(format!("오른쪽에 없는 왼쪽 키 {}개: {}", report.left_unmatched.len(), list(&report.left_unmatched)));
+    }
+    if !report.right_unmatched.is_empty() {
+        lines.push(format!("왼쪽에 없는 오른쪽 키 {}개: {}", report.right_unmatched.len(), list(&report.right_unmatched)));
+    }
+    let (left_nulls, right_nulls) = report.null_keys;
+    if left_nulls + right_nulls > 0 {
+        lines.push(format!("키에 결측이 있어 짝을 찾지 않은 행: 왼쪽 {}개, 오른쪽 {}개", left_nulls, right_nulls));
+    }
+    lines
+}
+
+/// 데이터셋의 앞부분을 열을 맞춰 출력 (한글은 두 칸으로 셈)
+fn print_rows(dataset: &DataSet, limit: usize) {
+    let rows: Vec<Vec<String>> = dataset.rows().take(limit).collect();
+    let width = |text: &str| text.chars().map(|c| if c.is_ascii() { 1 } else { 2 }).sum::<usize>();
+    let widths: Vec<usize> = dataset
+        .headers
+        .iter()
+        .enumerate()
+        .map(|(i, header)| rows.iter().map(|row| width(&row[i])).chain([width(header)]).max().unwrap_or(0))
+        .collect();
+    let format_row = |cells: &[String]| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, &w)| format!("{}{}", cell, " ".repeat(w - width(cell))))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    println!("{}", format_row(&dataset.headers));
+    for row in &rows {
+        println!("{}", format_row(row));
+    }
+    if dataset.row_count() > limit {
+        println!("... 외 {}행", dataset.row_count() - limit);
+    }
+}
+
+/// 인자 목록에서 값이 있는 옵션 하나를 꺼내고 나머지 인자를 반환
+fn take_option(args: &[String], flag: &str) -> Result<(Option<String>, Vec<String>)> {
+    let mut value = None;
+    let mut rest = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == flag {
+            value = Some(iter.next().ok_or_else(|| anyhow!("{} 옵션에 값이 필요합니다", flag))?.clone());
+        } else {
+            rest.push(arg.clone());
+        }
+    }
+    Ok((value, rest))
+}
+
+/// 큰따옴표/작은따옴표로 묶인 인자를 하나로 취급해 명령어 분리
+fn split_command_line(input: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+    let mut in_token = false;
+
+    for c in input.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => current.push(c),
+            None if c == '"' || c == '\'' => {
+                quote = Some(c);
+                in_token = true;
+            }
+            None if c.is_whitespace() => {
+                if in_token {
+                    parts.push(std::mem::take(&mut current));
+                    in_token = false;
+                }
+            }
+            None => {
+                current.push(c);
+                in_token = true;
+            }
+        }
+    }
+
+    if in_token {
+        parts.push(current);
+    }
+    parts
+}
+
+const DEFAULT_SHEET_COLUMN: &str = "sheet";
+
+#[derive(Debug, Default)]
+struct AnalyzeOptions {
+    read: ReadOptions,
+    /// 헤더가 같은 시트를 합칠 때 추가할 시트 이름 열
+    stack_column: Option<String>,
+    /// 배치 단위로 나눠 읽을 때의 행 수
+    batch_size: Option<usize>,
+    /// 배치 분석에서 열마다 빈도를 셀 값 종류의 상한
+    distinct_limit: Option<usize>,
+    /// 열별 통계 요약 표를 저장할 경로 ("-"이면 표준 출력)
+    output: Option<String>,
+    output_format: Option<FileFormat>,
+    /// CSV로 저장할 때의 옵션 (BOM)
+    csv_write: CsvWriteOptions,
+    /// 열 종류 추론 기준
+    schema: SchemaOptions,
+    /// 날짜 열과 범위 (시작, 끝). 범위 안의 행만 분석
+    date_range: Option<(String, Option<Value>, Option<Value>)>,
+}
+
+impl AnalyzeOptions {
+    /// 결과 표를 표준 출력으로 보낼 때는 다른 안내 문구를 출력하지 않음
+    fn writes_to_stdout(&self) -> bool {
+        self.output.as_deref() == Some(STDIO_PATH)
+    }
+
+    /// 출력 형식과 CSV 저장 옵션에 맞춰 저장
+    fn save(&self, dataset: &DataSet, output_path: &str) -> Result<()> {
+        save_dataset_with_options(dataset, output_path, self.output_format, &self.csv_write)
+    }
+}
+
+/// analyze 명령어의 옵션 해석
+fn parse_analyze_options(args: &[String]) -> Result<AnalyzeOptions> {
+    let mut analyze_options = AnalyzeOptions::default();
+    let options = &mut analyze_options.read;
+    let mut iter = args.iter();
+    let mut width_spec = None;
+    let mut width_unit = WidthUnit::default();
+
+    while let Some(flag) = iter.next() {
+        let mut value = || {
+            iter.next()
+                .ok_or_else(|| anyhow!("{} 옵션에 값이 필요합니다", flag))
+        };
+        match flag.as_str() {
+            "--sheet" => options.excel.sheet = SheetSelector::Name(value()?.clone()),
+            "--sheet-index" => {
+                let index: usize = value()?.parse()?;
+                if index == 0 {
+                    return Err(anyhow!("시트 순번은 1부터 시작합니다"));
+                }
+                options.excel.sheet = SheetSelector::Index(index - 1);
+            }
+            "--header-row" => {
+                let row: usize = value()?.parse()?;
+                options.excel.header_row = row.checked_sub(1);
+            }
+            "--skip-rows" => options.excel.skip_rows = value()?.parse()?,
+            "--range" => options.excel.range = Some(value()?.clone()),
+            "--columns" => {
+                let columns = value()?.split(',').map(|c| c.trim().to_string()).collect();
+                options.columns = Some(columns);
+            }
+            "--table" => options.sqlite = Some(SqliteSource::Table(value()?.clone())),
+            "--query" => options.sqlite = Some(SqliteSource::Query(value()?.clone())),
+            "--stream" => {
+                analyze_options.batch_size.get_or_insert(DEFAULT_BATCH_SIZE);
+            }
+            "--batch-size" => analyze_options.batch_size = Some(value()?.parse()?),
+            "--max-distinct" => analyze_options.distinct_limit = Some(value()?.parse()?),
+            "--format" => options.format = Some(value()?.parse()?),
+            "--output" => analyze_options.output = Some(value()?.clone()),
+            "--output-format" => analyze_options.output_format = Some(value()?.parse()?),
+            "--stack" => analyze_options.stack_column = Some(DEFAULT_SHEET_COLUMN.to_string()),
+            "--stack-column" => analyze_options.stack_column = Some(value()?.clone()),
+            "--source-column" => options.source_column = Some(value()?.clone()),
+            "--schema" => options.schema_mode = value()?.parse()?,
+            "--widths" => width_spec = Some(FixedWidthSpec::parse(value()?)?),
+            "--width-spec" => width_spec = Some(FixedWidthSpec::from_file(value()?)?),
+            "--width-unit" => width_unit = value()?.parse()?,
+            "--encoding" => options.csv.encoding = Some(value()?.parse()?),
+            "--delimiter" => options.csv.delimiter = Some(parse_csv_char(flag, value()?)?),
+            "--quote" => options.csv.quote = Some(parse_csv_char(flag, value()?)?),
+            "--header" => options.csv.has_headers = Some(true),
+            "--no-header" => options.csv.has_headers = Some(false),
+            "--bom" => analyze_options.csv_write.utf8_bom = true,
+            "--ragged-rows" => options.csv.ragged_rows = value()?.parse()?,
+            "--decimal-comma" => options.numbers.decimal_separator = ',',
+            "--percent-as-fraction" => options.numbers.percent_as_fraction = true,
+            "--plain-numbers" => options.numbers = NumberFormat::plain(),
+            "--schema-file" => analyze_options.schema.overrides = schema::read_schema_file(value()?)?,
+            "--type-threshold" => {
+                let threshold: f64 = value()?.parse()?;
+                if !(threshold > 0.0 && threshold <= 1.0) {
+                    return Err(anyhow!("--type-threshold는 0보다 크고 1 이하여야 합니다"));
+                }
+                analyze_options.schema.threshold = threshold;
+            }
+            "--date-format" => {
+                let (column, format) = value()?
+                    .split_once('=')
+                    .ok_or_else(|| anyhow!("--date-format은 <열>=<형식> 형태로 지정하세요"))?;
+                options.dates.formats.insert(column.trim().to_string(), DateFormat::parse(format));
+            }
+            "--timezone" => options.dates.timezone = dates::parse_timezone(value()?)?,
+            "--date-range" => analyze_options.date_range = Some(parse_date_range(value()?)?),
+            _ => return Err(anyhow!("알 수 없는 옵션입니다: {}", flag)),
+        }
+    }
+
+    if let Some(spec) = width_spec {
+        options.fixed_width = Some(FixedWidthOptions {
+            spec,
+            unit: width_unit,
+            skip_rows: options.excel.skip_rows,
+            ..Default::default()
+        });
+    }
+
+    Ok(analyze_options)
+}
+
+/// 불러온 데이터를 분석할 때 쓸 수 있는 옵션. 파일을 읽는 옵션은 이미 읽은 데이터에 효과가 없음
+const CURRENT_ANALYZE_FLAGS: &[&str] = &["--output", "--output-format", "--bom", "--schema-file", "--type-threshold", "--date-range"];
+/// save 명령어에 쓸 수 있는 옵션
+const SAVE_FLAGS: &[&str] = &["--output-format", "--bom"];
+
+/// 허용한 옵션만 받아 해석. 효과가 없는 옵션은 무시하지 않고 오류로 알림
+fn parse_options_only(args: &[String], allowed: &[&str]) -> Result<AnalyzeOptions> {
+    if let Some(flag) = args.iter().find(|arg| arg.starts_with("--") && !allowed.contains(&arg.as_str())) {
+        return Err(anyhow!("{} 옵션은 불러온 데이터에는 쓸 수 없습니다 (쓸 수 있는 옵션: {})", flag, allowed.join(", ")));
+    }
+    parse_analyze_options(args)
+}
+
+/// 구분자나 따옴표로 쓸 한 글자 해석. 탭은 "tab" 또는 "\t"로도 지정 가능
+fn parse_csv_char(flag: &str, text: &str) -> Result<u8> {
+    match text {
+        "tab" | "\\t" => Ok(b'\t'),
+        _ if text.len() == 1 && text.is_ascii() => Ok(text.as_bytes()[0]),
+        _ => Err(anyhow!("{} 옵션에는 ASCII 한 글자를 지정하세요: {}", flag, text)),
+    }
+}
+
+/// "열=시작..끝" 형태의 날짜 범위 해석. 시작이나 끝은 비워 둘 수 있음
+fn parse_date_range(text: &str) -> Result<(String, Option<Value>, Option<Value>)> {
+    let (column, range) = text
+        .rsplit_once('=')
+        .ok_or_else(|| anyhow!("--date-range는 <열>=<시작>..<끝> 형태로 지정하세요"))?;
+    let (start, end) = range
+        .split_once("..")
+        .ok_or_else(|| anyhow!("날짜 범위는 <시작>..<끝> 형태로 지정하세요: {}", range))?;
+    let bound = |text: &str| {
+        let text = text.trim();
+        if text.is_empty() { Ok(None) } else { dates::parse_date_bound(text).map(Some) }
+    };
+    Ok((column.trim().to_string(), bound(start)?, bound(end)?))
+}
+
+/// 파일 읽기. 시트 합치기 옵션이 있으면 헤더가 같은 시트를 모두 합쳐서 반환
+fn load_dataset(file_path: &str, options: &AnalyzeOptions) -> Result<DataSet> {
+    let dataset = match &options.stack_column {
+        None => read_file_with_options(file_path, &options.read)?,
+        Some(stack_column) => {
+            let workbook = read_workbook(file_path, &options.read.excel)?;
+            let (dataset, skipped) = workbook.stack(stack_column)?;
+            // Status goes to stderr, and is left out entirely when results are written to stdout
+            if !options.writes_to_stdout() {
+                eprintln!("시트 {}개를 하나로 합쳤습니다.", workbook.sheets.len() - skipped.len());
+                if !skipped.is_empty() {
+                    eprintln!("헤더가 달라 제외된 시트: {}", skipped.join(", "));
+                }
+            }
+            // The same post-processing read_file_with_options gives a single sheet
+            let mut dataset = match &options.read.columns {
+                Some(columns) => dataset.select_columns(columns)?,
+                None => dataset,
+            };
+            apply_value_formats(&mut dataset, &options.read)?;
+            dataset
+        }
+    };
+    prepare_dataset(dataset, options)
+}
+
+/// 스키마 파일의 날짜 열 변환과 날짜 범위를 적용
+fn prepare_dataset(mut dataset: DataSet, options: &AnalyzeOptions) -> Result<DataSet> {
+    options.schema.validate(&dataset.headers)?;
+    schema::apply_date_overrides(&mut dataset, &options.schema);
+    match &options.date_range {
+        Some((column, start, end)) => {
+            let filtered = dates::filter_date_range(&dataset, column, start.as_ref(), end.as_ref())?;
+            if !options.writes_to_stdout() {
+                eprintln!("날짜 범위에 맞는 행: {} / {}", filtered.row_count(), dataset.row_count());
+            }
+            Ok(filtered)
+        }
+        None => Ok(dataset),
+    }
+}
+
+fn print_sheets(file_path: &str) -> Result<()> {
+    let sheets = list_sheets(file_path)?;
+    println!("=== Sheets in '{}' ===", file_path);
+    for (index, sheet) in sheets.iter().enumerate() {
+        println!(
+            "{}. {} - {} rows x {} columns ({})",
+            index + 1,
+            sheet.name,
+            sheet.rows,
+            sheet.columns,
+            sheet.used_range.as_deref().unwrap_or("empty"),
+        );
+    }
+    println!("========================");
+    Ok(())
+}
+
+fn analyze_file(file_path: &str, options: &AnalyzeOptions) -> Result<()> {
+    if let Some(batch_size) = options.batch_size {
+        return analyze_file_streaming(file_path, options, batch_size);
+    }
+
+    if !options.writes_to_stdout() {
+        println!("파일 분석 중: {}", file_path);
+    }
 
     // 파일 읽기
-    let dataset = read_file(file_path)?;
-    let analyzer = DataAnalyzer::new();
+    let dataset = load_dataset(file_path, options)?;
+    analyze_dataset(&dataset, file_path, options)
+}
+
+/// 읽어 둔 데이터셋 분석. 표본 등 결과 파일 이름은 file_path에서 만듦
+fn analyze_dataset(dataset: &DataSet, file_path: &str, options: &AnalyzeOptions) -> Result<()> {
+    let analyzer = DataAnalyzer::with_schema_options(options.schema.clone());
+
+    // 요약 표만 표준 출력으로 내보냄
+    if options.writes_to_stdout() {
+        let summaries = analyzer.summarize_columns(dataset)?;
+        let table = analyzer.summary_table(&dataset.headers, &summaries);
+        return options.save(&table, STDIO_PATH);
+    }
 
     // 데이터셋 요약 정보 출력
-    analyzer.print_dataset_summary(&dataset);
+    analyzer.print_dataset_summary(dataset);
 
     // 각 열에 대해 분석 수행
-    for header in &dataset.headers {
-        println!("\n분석 중인 열: {}", header);
+    for column_schema in analyzer.infer_schema(dataset) {
+        let header = &column_schema.name;
+        println!("\n분석 중인 열: {} ({})", header, column_schema.semantic_type);
 
-        // 숫자 데이터인지 확인하고 기초통계량 계산
-        if let Ok(stats) = analyzer.analyze_column(&dataset, header) {
+        // 숫자 열로 추론된 경우 기초통계량 계산
+        if let Some(numeric_data) = analyzer.numeric_values(dataset, &column_schema) {
+            let mut stats = analyzer.calculate_basic_stats(&numeric_data)?;
+            let report = dataset.missing_value_report(header)?;
+            stats.missing = report.missing;
+            stats.invalid = report.invalid;
             analyzer.print_basic_stats(&stats, header);
+            if report.invalid > 0 {
+                analyzer.print_missing_value_report(&report, header);
+            }
 
             // 그래프 생성
-            if let Ok(numeric_data) = dataset.get_numeric_column(header) {
-                let safe_header = header.replace(" ", "_").replace("/", "_");
+            let safe_header = header.replace(" ", "_").replace("/", "_");
 
-                // Box Plot 생성
-                let box_plot_path = format!("boxplot_{}.png", safe_header);
-                if let Err(e) = analyzer.create_box_plot(&numeric_data, 
-                    &format!("Box Plot - {}", header), &box_plot_path) {
-                    println!("Box plot 생성 실패: {}", e);
-                }
+            // Box Plot 생성
+            let box_plot_path = format!("boxplot_{}.png", safe_header);
+            if let Err(e) = analyzer.create_box_plot(&numeric_data, 
+                &format!("Box Plot - {}", header), &box_plot_path) {
+                println!("Box plot 생성 실패: {}", e);
+            }
 
-                // QQ Plot 생성
-                let qq_plot_path = format!("qqplot_{}.png", safe_header);
-                if let Err(e) = analyzer.create_qq_plot(&numeric_data, 
-                    &format!("QQ Plot - {}", header), &qq_plot_path) {
-                    println!("QQ plot 생성 실패: {}", e);
-                }
+            // QQ Plot 생성
+            let qq_plot_path = format!("qqplot_{}.png", safe_header);
+            if let Err(e) = analyzer.create_qq_plot(&numeric_data, 
+                &format!("QQ Plot - {}", header), &qq_plot_path) {
+                println!("QQ plot 생성 실패: {}", e);
+            }
 
-                // Histogram 생성
-                let histogram_path = format!("histogram_{}.png", safe_header);
-                if let Err(e) = analyzer.create_histogram(&numeric_data, 
-                    &format!("Histogram - {}", header), &histogram_path, 20) {
-                    println!("Histogram 생성 실패: {}", e);
-                }
+            // Histogram 생성
+            let histogram_path = format!("histogram_{}.png", safe_header);
+            if let Err(e) = analyzer.create_histogram(&numeric_data, 
+                &format!("Histogram - {}", header), &histogram_path, 20) {
+                println!("Histogram 생성 실패: {}", e);
             }
+        } else if matches!(dataset.column(header)?.column_type(), ColumnType::Date | ColumnType::DateTime)
+            && let Ok(stats) = analyzer.analyze_date_column(dataset, header)
+        {
+            analyzer.print_date_stats(&stats, header);
+        } else if matches!(column_schema.semantic_type, SemanticType::Identifier | SemanticType::Text) {
+            // 값이 대부분 서로 다른 열은 빈도표가 의미 없으므로 고유값 개수만 표시
+            println!("고유값 {}개 / 값 {}개 - 빈도 분석을 생략합니다", column_schema.unique, column_schema.non_missing);
         } else {
             // 문자열 데이터의 경우 빈도 분석
-            if let Ok(freq_data) = analyzer.analyze_column_frequency(&dataset, header) {
+            if let Ok(freq_data) = analyzer.analyze_column_frequency(dataset, header) {
                 analyzer.print_frequency_data(&freq_data, header);
             }
         }
@@ -135,11 +997,10 @@ fn analyze_file(file_path: &str) -> Result<()> {
         println!("\n=== 표본 추출 예시 ===");
         let sample_size = (dataset.row_count() / 2).min(100);
 
-        if let Ok(sample) = analyzer.random_sample(&dataset, sample_size) {
+        if let Ok(sample) = analyzer.random_sample(dataset, sample_size) {
             println!("무작위 표본 추출 완료: {} 행", sample.row_count());
-            let sample_path = format!("{}_random_sample.csv", 
-                Path::new(file_path).file_stem().unwrap().to_str().unwrap());
-            if let Err(e) = save_dataset_to_csv(&sample, &sample_path) {
+            let sample_path = format!("{}_random_sample.csv", output_stem(file_path));
+            if let Err(e) = save_dataset_to_csv_with_options(&sample, &sample_path, &options.csv_write) {
                 println!("표본 저장 실패: {}", e);
             } else {
                 println!("표본이 {}에 저장되었습니다.", sample_path);
@@ -153,11 +1014,11 @@ fn analyze_file(file_path: &str) -> Result<()> {
         let first_column = &dataset.headers[0];
         let columns_to_extract = vec![first_column.clone()];
 
-        if let Ok(subset) = extract_subset(&dataset, None, Some(columns_to_extract)) {
+        if let Ok(subset) = extract_subset(dataset, None, Some(columns_to_extract)) {
             let subset_path = format!("{}_column_{}.csv", 
-                Path::new(file_path).file_stem().unwrap().to_str().unwrap(),
+                output_stem(file_path),
                 first_column.replace(" ", "_"));
-            if let Err(e) = save_dataset_to_csv(&subset, &subset_path) {
+            if let Err(e) = save_dataset_to_csv_with_options(&subset, &subset_path, &options.csv_write) {
                 println!("열 추출 파일 저장 실패: {}", e);
             } else {
                 println!("'{}' 열이 {}에 저장되었습니다.", first_column, subset_path);
@@ -165,9 +1026,159 @@ fn analyze_file(file_path: &str) -> Result<()> {
         }
     }
 
+    if let Some(output) = &options.output {
+        let summaries = analyzer.summarize_columns(dataset)?;
+        options.save(&analyzer.summary_table(&dataset.headers, &summaries), output)?;
+        println!("\n열별 통계 요약이 {}에 저장되었습니다.", output);
+    }
+
     Ok(())
 }
 
+/// 결과 파일 이름에 쓸 입력 파일 이름 (표준 입력은 "stdin")
+fn output_stem(file_path: &str) -> String {
+    if file_path == STDIO_PATH {
+        return "stdin".to_string();
+    }
+    // logs/2024-*.csv → 2024-
+    let stem: String = Path::new(file_path)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("data")
+        .chars()
+        .filter(|c| !matches!(c, '*' | '?' | '[' | ']'))
+        .collect();
+    if stem.is_empty() {
+        return "combined".to_string();
+    }
+    stem
+}
+
+/// 큰 CSV 파일을 배치 단위로 읽으며 열별 통계/빈도만 계산 (그래프, 표본 추출 없음)
+fn analyze_file_streaming(file_path: &str, options: &AnalyzeOptions, batch_size: usize) -> Result<()> {
+    if multi_file::is_multi_file_path(file_path) {
+        return Err(anyhow!("--stream은 파일 하나에만 사용할 수 있습니다: {}", file_path));
+    }
+    if options.date_range.is_some() {
+        return Err(anyhow!("--date-range는 --stream과 함께 사용할 수 없습니다"));
+    }
+    let batches = read_csv_batches(file_path, &options.read.csv, batch_size)?
+        .with_number_format(options.read.numbers.clone())
+        .with_date_options(options.read.dates.clone());
+    options.schema.validate(batches.headers())?;
+    let analyzer = DataAnalyzer::with_schema_options(options.schema.clone())
+        .with_distinct_limit(options.distinct_limit.unwrap_or(DEFAULT_DISTINCT_LIMIT));
+    let summary = analyzer.analyze_batches(batches)?;
+
+    if options.writes_to_stdout() {
+        let table = analyzer.summary_table(&summary.headers, &summary.columns);
+        return options.save(&table, STDIO_PATH);
+    }
+
+    println!("파일 분석 중 (배치 {}행씩): {}", batch_size, file_path);
+    analyzer.print_streaming_summary(&summary);
+
+    for (header, column) in summary.headers.iter().zip(&summary.columns) {
+        println!("\n분석 중인 열: {}", header);
+        match column {
+            ColumnSummary::Numeric(stats) => analyzer.print_basic_stats(stats, header),
+            ColumnSummary::Categorical(freq_data) => analyzer.print_frequency_data(freq_data, header),
+            ColumnSummary::Empty => println!("모든 값이 결측입니다."),
+        }
+    }
+
+    println!("\n중앙값과 사분위수는 근사값이며, 열 종류는 값이 처음 나온 배치로 정했습니다.");
+
+    if let Some(output) = &options.output {
+        options.save(&analyzer.summary_table(&summary.headers, &summary.columns), output)?;
+        println!("열별 통계 요약이 {}에 저장되었습니다.", output);
+    }
+    Ok(())
+}
+
+/// 명령행 인자로 받은 명령어 하나를 실행
+fn run_command_line(args: &[String]) -> Result<()> {
+    match args[0].as_str() {
+        "analyze" if args.len() >= 2 => {
+            let options = parse_analyze_options(&args[2..])?;
+            analyze_file(&args[1], &options)
+        }
+        "filter" if args.len() >= 3 => {
+            let options = parse_analyze_options(&args[3..])?;
+            let filter = Filter::parse(&args[2])?;
+            let dataset = filter::filter_dataset(&load_dataset(&args[1], &options)?, &filter)?;
+            let output = options.output.as_deref().unwrap_or(STDIO_PATH);
+            options.save(&dataset, output)
+        }
+        "sort" if args.len() >= 3 => {
+            let (nulls, rest) = take_option(&args[3..], "--nulls")?;
+            let options = parse_analyze_options(&rest)?;
+            let sort_options = sort_options(&args[2], nulls)?;
+            let dataset = sort::sort_dataset(&load_dataset(&args[1], &options)?, &sort_options)?;
+            let output = options.output.as_deref().unwrap_or(STDIO_PATH);
+            options.save(&dataset, output)
+        }
+        "groupby" if args.len() >= 3 => {
+            // Aggregations are a single quoted argument here, followed by --stats and the usual read options
+            let (aggregations, rest) = match args.get(3) {
+                Some(arg) if !arg.starts_with("--") => (vec![arg.clone()], &args[4..]),
+                _ => (Vec::new(), &args[3..]),
+            };
+            let (stats_column, rest) = take_option(rest, "--stats")?;
+            let options = parse_analyze_options(&rest)?;
+            let dataset = load_dataset(&args[1], &options)?;
+
+            let mut groupby_args = aggregations;
+            groupby_args.extend(stats_column.map(|column| ["--stats".to_string(), column]).into_iter().flatten());
+            match run_groupby(&dataset, &args[2], &groupby_args)? {
+                Some(grouped) => options.save(&grouped, options.output.as_deref().unwrap_or(STDIO_PATH)),
+                None => Ok(()),
+            }
+        }
+        "join" if args.len() >= 4 => {
+            let (join_options, rest) = parse_join_options(&args[3], &args[4..])?;
+            let (left_args, right_args) = split_join_read_args(&rest);
+            let options = parse_analyze_options(&left_args)?;
+            let left = load_dataset(&args[1], &options)?;
+            let right = load_dataset(&args[2], &parse_analyze_options(&right_args)?)?;
+            let result = join::join(&left, &right, &join_options)?;
+            // The report goes to stderr so the joined rows can be piped
+            for line in join_report_lines(&result.report) {
+                eprintln!("{}", line);
+            }
+            options.save(&result.dataset, options.output.as_deref().unwrap_or(STDIO_PATH))
+        }
+        "pivot" if args.len() >= 5 => {
+            let (aggregation, rest) = take_option(&args[5..], "--agg")?;
+            let mut pivot_args = args[2..5].to_vec();
+            pivot_args.extend(aggregation.map(|name| ["--agg".to_string(), name]).into_iter().flatten());
+            let options = parse_analyze_options(&rest)?;
+            let pivoted = reshape::pivot(&load_dataset(&args[1], &options)?, &parse_pivot_options(&pivot_args)?)?;
+            options.save(&pivoted, options.output.as_deref().unwrap_or(STDIO_PATH))
+        }
+        "melt" | "unpivot" if args.len() >= 2 => {
+            let (melt_options, rest) = parse_melt_args(&args[2..])?;
+            let options = parse_analyze_options(&rest)?;
+            let melted = reshape::melt(&load_dataset(&args[1], &options)?, &melt_options)?;
+            options.save(&melted, options.output.as_deref().unwrap_or(STDIO_PATH))
+        }
+        "sheets" if args.len() >= 2 => print_sheets(&args[1]),
+        "demo" => run_demo(),
+        "help" | "--help" | "-h" => {
+            print_usage();
+            Ok(())
+        }
+        "analyze" | "sheets" => Err(anyhow!("{} 명령어에는 파일 경로가 필요합니다", args[0])),
+        "filter" => Err(anyhow!("filter 명령어에는 파일 경로와 식이 필요합니다")),
+        "sort" => Err(anyhow!("sort 명령어에는 파일 경로와 정렬 기준이 필요합니다")),
+        "groupby" => Err(anyhow!("groupby 명령어에는 파일 경로와 키 열이 필요합니다")),
+        "join" => Err(anyhow!("join 명령어에는 두 파일 경로와 키 열이 필요합니다")),
+        "pivot" => Err(anyhow!("pivot 명령어에는 파일 경로, 키 열, 열 이름이 될 열, 값 열이 필요합니다")),
+        "melt" | "unpivot" => Err(anyhow!("{} 명령어에는 파일 경로가 필요합니다", args[0])),
+        _ => Err(anyhow!("알 수 없는 명령어입니다: {}", args[0])),
+    }
+}
+
 fn run_demo() -> Result<()> {
     println!("=== 데이터 분석기 데모 실행 ===");
 
@@ -175,7 +1186,7 @@ fn run_demo() -> Result<()> {
     create_sample_data()?;
 
     // 생성된 샘플 데이터 분석
-    analyze_file("sample_data.csv")?;
+    analyze_file("sample_data.csv", &AnalyzeOptions::default())?;
 
     println!("\n데모가 완료되었습니다!");
     println!("생성된 파일들:");
@@ -0,0 +1,223 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use anyhow::{Result, anyhow};
+use crate::read_file::{self, DataSet, FileFormat, ReadOptions};
+
+/// 여러 파일의 열 구성이 다를 때 처리 방식
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SchemaMode {
+    /// 모든 열을 합치고 열이 없는 파일의 행은 결측으로 채움
+    #[default]
+    Union,
+    /// 열 구성이 첫 파일과 다르면 오류
+    Strict,
+}
+
+impl fmt::Display for SchemaMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            SchemaMode::Union => "union",
+            SchemaMode::Strict => "strict",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for SchemaMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "union" => Ok(SchemaMode::Union),
+            "strict" => Ok(SchemaMode::Strict),
+            _ => Err(anyhow!("Unknown schema mode: {} (use union or strict)", s)),
+        }
+    }
+}
+
+/// 경로가 디렉터리이거나 와일드카드(*, ?, [)가 들어 있는 패턴인지 확인
+pub fn is_multi_file_path<P: AsRef<Path>>(path: P) -> bool {
+    let path = path.as_ref();
+    if path.is_dir() {
+        return true;
+    }
+    !path.exists() && path.to_string_lossy().contains(['*', '?', '['])
+}
+
+/// 디렉터리 안의 데이터 파일, 또는 글롭 패턴에 맞는 파일 목록 (이름순)
+pub fn expand_input_paths<P: AsRef<Path>>(path: P) -> Result<Vec<PathBuf>> {
+    let path = path.as_ref();
+
+    let mut paths: Vec<PathBuf> = if path.is_dir() {
+        std::fs::read_dir(path)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<std::io::Result<Vec<_>>>()?
+            .into_iter()
+            .filter(|path| path.is_file() && FileFormat::from_path(path).is_some())
+            .collect()
+    } else {
+        let pattern = path.to_string_lossy();
+        glob::glob(&pattern)
+            .map_err(|e| anyhow!("Invalid file pattern '{}': {}", pattern, e))?
+            .collect::<std::result::Result<Vec<_>, _>>()?
+            .into_iter()
+            .filter(|path| path.is_file())
+            .collect()
+    };
+
+    if paths.is_empty() {
+        return Err(anyhow!("No data files match '{}'", path.display()));
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+/// 디렉터리 또는 글롭 패턴에 맞는 파일을 모두 읽어 하나로 합침. 서식 있는 숫자와 날짜는 read_file_with_options가 합친 뒤에 한 번 처리
+pub fn read_multiple_files<P: AsRef<Path>>(pattern: P, options: &ReadOptions) -> Result<DataSet> {
+    let pattern = pattern.as_ref();
+    let paths = expand_input_paths(pattern)?;
+
+    // Columns are selected after combining, since not every file has to contain them. Number and date
+    // formats are left to read_file_with_options so they run once on the combined dataset
+    let file_options = ReadOptions {
+        columns: None,
+        source_column: None,
+        ..options.clone()
+    };
+
+    let mut datasets = Vec::with_capacity(paths.len());
+    for path in &paths {
+        let dataset = read_file::read_path(path, &file_options)
+            .map_err(|e| anyhow!("Error reading '{}': {}", path.display(), e))?;
+        datasets.push(dataset);
+    }
+
+    let names = source_names(&paths);
+    let parts: Vec<(&str, &DataSet)> = names.iter().map(String::as_str).zip(&datasets).collect();
+
+    if let Some(source_column) = &options.source_column
+        && datasets.iter().any(|dataset| dataset.headers.contains(source_column))
+    {
+        return Err(anyhow!("Column '{}' already exists in the input files", source_column));
+    }
+
+    let file_path = pattern.to_string_lossy().to_string();
+    let source_column = options.source_column.as_deref();
+    let dataset = match options.schema_mode {
+        SchemaMode::Union => read_file::union_datasets(&parts, source_column, file_path)?,
+        SchemaMode::Strict => {
+            check_same_columns(&parts)?;
+            read_file::union_datasets(&parts, source_column, file_path)?
+        }
+    };
+
+    match &options.columns {
+        Some(columns) => dataset.select_columns(columns),
+        None => Ok(dataset),
+    }
+}
+
+/// 출처 열에 넣을 이름. 파일 이름이 겹치지 않으면 파일 이름만 사용
+fn source_names(paths: &[PathBuf]) -> Vec<String> {
+    let file_names: Vec<String> = paths
+        .iter()
+        .map(|path| path.file_name().unwrap_or_default().to_string_lossy().to_string())
+        .collect();
+
+    let unique: HashSet<&String> = file_names.iter().collect();
+    if unique.len() == file_names.len() {
+        file_names
+    } else {
+        paths.iter().map(|path| path.to_string_lossy().to_string()).collect()
+    }
+}
+
+/// 모든 파일의 열 구성이 첫 파일과 같은지 확인 (순서는 달라도 됨)
+fn check_same_columns(parts: &[(&str, &DataSet)]) -> Result<()> {
+    let Some((first_name, first)) = parts.first() else {
+        return Ok(());
+    };
+
+    let mismatches: Vec<String> = parts[1..]
+        .iter()
+        .filter_map(|(name, dataset)| {
+            let missing: Vec<&str> = first
+                .headers
+                .iter()
+                .filter(|header| !dataset.headers.contains(header))
+                .map(String::as_str)
+                .collect();
+            let extra: Vec<&str> = dataset
+                .headers
+                .iter()
+                .filter(|header| !first.headers.contains(header))
+                .map(String::as_str)
+                .collect();
+
+            if missing.is_empty() && extra.is_empty() {
+                return None;
+            }
+            let mut problems = Vec::new();
+            if !missing.is_empty() {
+                problems.push(format!("missing {}", missing.join(", ")));
+            }
+            if !extra.is_empty() {
+                problems.push(format!("extra {}", extra.join(", ")));
+            }
+            Some(format!("  {}: {}", name, problems.join("; ")))
+        })
+        .collect();
+
+    if mismatches.is_empty() {
+        return Ok(());
+    }
+
+    Err(anyhow!(
+        "{} file(s) have different columns than '{}':\n{}",
+        mismatches.len(),
+        first_name,
+        mismatches.join("\n")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn number_formats_are_reported_once_for_combined_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.csv"), "금액\n\"1,000\"\n\"2,000\"\n\"3,000\"\n미정\n").unwrap();
+        std::fs::write(dir.path().join("b.csv"), "금액\n\"4,000\"\n미정\n없음\n").unwrap();
+
+        let dataset = read_file::read_file_with_options(dir.path(), &ReadOptions::default()).unwrap();
+        let reports = &dataset.load_report.number_formats;
+        assert_eq!(reports.len(), 1);
+        assert_eq!((reports[0].converted, reports[0].unparsed), (4, 3));
+        assert_eq!(dataset.column("금액").unwrap().get_string(4), "4000");
+    }
+
+    #[test]
+    fn union_fills_missing_columns_and_strict_reports_them() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.csv"), "id,name\n1,가\n").unwrap();
+        std::fs::write(dir.path().join("b.csv"), "name,id,score\n나,2,90\n").unwrap();
+
+        let options = ReadOptions { source_column: Some("파일".to_string()), ..ReadOptions::default() };
+        let dataset = read_multiple_files(dir.path(), &options).unwrap();
+        assert_eq!(dataset.headers, vec!["파일", "id", "name", "score"]);
+        let rows: Vec<Vec<String>> = (0..dataset.row_count())
+            .map(|i| dataset.columns.iter().map(|column| column.get_string(i)).collect())
+            .collect();
+        assert_eq!(rows, vec![vec!["a.csv", "1", "가", ""], vec!["b.csv", "2", "나", "90"]]);
+
+        let strict = ReadOptions { schema_mode: SchemaMode::Strict, ..ReadOptions::default() };
+        let error = read_multiple_files(dir.path(), &strict).unwrap_err().to_string();
+        assert!(error.contains("b.csv: extra score"), "{}", error);
+
+        let clashing = ReadOptions { source_column: Some("name".to_string()), ..ReadOptions::default() };
+        assert!(read_multiple_files(dir.path(), &clashing).is_err());
+    }
+}
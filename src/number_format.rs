@@ -0,0 +1,397 @@
+use std::fmt;
+use crate::column::{Column, ColumnData, NullValues, Value};
+use crate::read_file::DataSet;
+
+const CURRENCY_SYMBOLS: &[&str] = &["₩", "￦", "$", "€", "£", "¥", "원", "KRW", "USD", "EUR", "JPY"];
+
+/// 서식이 있는 숫자 해석 규칙
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NumberFormat {
+    /// 소수점 문자. ','이면 '.'과 공백을 천 단위 구분자로 봄 (1.234,5)
+    pub decimal_separator: char,
+    /// 천 단위 구분자 (1,234,567)
+    pub thousands_separators: bool,
+    /// 앞뒤 통화 기호 (₩12,000, 12,000원, $5)
+    pub currency_symbols: bool,
+    /// 퍼센트 (15.3%)
+    pub percent: bool,
+    /// 괄호 음수 (1,200) 와 △1,200
+    pub accounting_negatives: bool,
+    /// 퍼센트 값을 100으로 나눈 비율로 저장 (15.3% → 0.153). false면 15.3
+    pub percent_as_fraction: bool,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self {
+            decimal_separator: '.',
+            thousands_separators: true,
+            currency_symbols: true,
+            percent: true,
+            accounting_negatives: true,
+            percent_as_fraction: false,
+        }
+    }
+}
+
+impl NumberFormat {
+    /// 서식을 해석하지 않음 (숫자 모양 그대로인 값만 숫자)
+    pub fn plain() -> Self {
+        Self {
+            decimal_separator: '.',
+            thousands_separators: false,
+            currency_symbols: false,
+            percent: false,
+            accounting_negatives: false,
+            percent_as_fraction: false,
+        }
+    }
+
+    /// 소수점이 쉼표인 형식 (1.234,5)
+    pub fn decimal_comma() -> Self {
+        Self {
+            decimal_separator: ',',
+            ..Self::default()
+        }
+    }
+
+    fn is_plain(&self) -> bool {
+        self.decimal_separator == '.'
+            && !self.thousands_separators
+            && !self.currency_symbols
+            && !self.percent
+            && !self.accounting_negatives
+    }
+
+    fn thousands_separators_chars(&self) -> &'static [char] {
+        if self.decimal_separator == ',' {
+            &['.', ' ', '\u{a0}']
+        } else {
+            &[',']
+        }
+    }
+
+    /// 셀 하나를 숫자로 해석. 적용한 규칙과 함께 반환하며 숫자가 아니면 None
+    pub fn parse(&self, text: &str) -> Option<(f64, NumberRules)> {
+        let mut rules = NumberRules::default();
+        let mut text = text.trim();
+        let mut negative = false;
+
+        if self.accounting_negatives {
+            if let Some(inner) = text.strip_prefix('(').and_then(|t| t.strip_suffix(')')) {
+                text = inner.trim();
+                negative = true;
+                rules.accounting_negative = true;
+            } else if let Some(inner) = text.strip_prefix('△') {
+                text = inner.trim();
+                negative = true;
+                rules.accounting_negative = true;
+            }
+        }
+
+        // The sign may come before or after the currency symbol (-₩1,200 or ₩-1,200)
+        let (rest, sign) = strip_sign(text);
+        text = rest;
+        negative ^= sign;
+
+        if self.currency_symbols
+            && let Some(rest) = strip_currency(text)
+        {
+            text = rest;
+            rules.currency = true;
+            let (rest, sign) = strip_sign(text);
+            text = rest;
+            negative ^= sign;
+        }
+
+        let mut scale = 1.0;
+        if self.percent
+            && let Some(rest) = text.strip_suffix('%')
+        {
+            text = rest.trim_end();
+            rules.percent = true;
+            if self.percent_as_fraction {
+                scale = 0.01;
+            }
+        }
+
+        let (integer, fraction) = match text.rsplit_once(self.decimal_separator) {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (text, None),
+        };
+        if fraction.is_some() && self.decimal_separator == ',' {
+            rules.decimal_comma = true;
+        }
+
+        let integer = if self.thousands_separators && integer.contains(self.thousands_separators_chars()) {
+            rules.thousands_separator = true;
+            ungroup(integer, self.thousands_separators_chars())?
+        } else {
+            integer.to_string()
+        };
+
+        if integer.is_empty() || !integer.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        if let Some(fraction) = fraction
+            && (fraction.is_empty() || !fraction.chars().all(|c| c.is_ascii_digit()))
+        {
+            return None;
+        }
+
+        let number: f64 = match fraction {
+            Some(fraction) => format!("{}.{}", integer, fraction).parse().ok()?,
+            None => integer.parse().ok()?,
+        };
+        let number = number * scale;
+        Some((if negative { -number } else { number }, rules))
+    }
+}
+
+fn strip_sign(text: &str) -> (&str, bool) {
+    match text.strip_prefix(['-', '−']) {
+        Some(rest) => (rest.trim_start(), true),
+        None => (text.strip_prefix('+').unwrap_or(text).trim_start(), false),
+    }
+}
+
+fn strip_currency(text: &str) -> Option<&str> {
+    CURRENCY_SYMBOLS.iter().find_map(|symbol| {
+        text.strip_prefix(symbol)
+            .or_else(|| text.strip_suffix(symbol))
+            .map(str::trim)
+    })
+}
+
+/// 천 단위 구분자를 뺀 정수 부분. 첫 묶음은 1~3자리, 나머지는 3자리여야 함
+fn ungroup(integer: &str, separators: &[char]) -> Option<String> {
+    let groups: Vec<&str> = integer.split(separators).collect();
+    let (first, rest) = groups.split_first()?;
+    if first.is_empty() || first.len() > 3 || rest.iter().any(|group| group.len() != 3) {
+        return None;
+    }
+    Some(groups.concat())
+}
+
+/// 셀에 적용한 해석 규칙
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NumberRules {
+    pub thousands_separator: bool,
+    pub decimal_comma: bool,
+    pub currency: bool,
+    pub percent: bool,
+    pub accounting_negative: bool,
+}
+
+impl NumberRules {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    fn merge(&mut self, other: NumberRules) {
+        self.thousands_separator |= other.thousands_separator;
+        self.decimal_comma |= other.decimal_comma;
+        self.currency |= other.currency;
+        self.percent |= other.percent;
+        self.accounting_negative |= other.accounting_negative;
+    }
+}
+
+impl fmt::Display for NumberRules {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<&str> = [
+            (self.thousands_separator, "thousands separators"),
+            (self.decimal_comma, "decimal comma"),
+            (self.currency, "currency symbols"),
+            (self.percent, "percent"),
+            (self.accounting_negative, "accounting negatives"),
+        ]
+        .into_iter()
+        .filter_map(|(applied, name)| applied.then_some(name))
+        .collect();
+        write!(f, "{}", names.join(", "))
+    }
+}
+
+/// 서식 있는 숫자로 읽은 열과 적용한 규칙
+#[derive(Debug, Clone)]
+pub struct NumberFormatReport {
+    pub column: String,
+    pub rules: NumberRules,
+    /// 규칙을 적용해 숫자로 바꾼 셀 수
+    pub converted: usize,
+    /// 숫자로 해석하지 못해 그대로 둔 셀 수
+    pub unparsed: usize,
+}
+
+/// 문자열 열 가운데 결측이 아닌 값의 과반이 숫자로 해석되고 서식 규칙이 한 번이라도 쓰인 열을
+/// 숫자로 바꿈. 해석하지 못한 셀은 원래 문자열로 남겨 오류 값으로 보고되게 함
+pub fn apply_number_format(dataset: &mut DataSet, format: &NumberFormat) -> Vec<NumberFormatReport> {
+    if format.is_plain() {
+        return Vec::new();
+    }
+
+    let mut reports = Vec::new();
+    for (header, column) in dataset.headers.iter().zip(dataset.columns.iter_mut()) {
+        let ColumnData::String(cells) = column.data() else {
+            continue;
+        };
+
+        let mut rules = NumberRules::default();
+        let mut converted = 0;
+        let mut parsed = 0;
+        let mut present = 0;
+        let values: Vec<Value> = cells
+            .iter()
+            .enumerate()
+            .map(|(row, cell)| {
+                if column.is_null(row) {
+                    return Value::Null;
+                }
+                present += 1;
+                match format.parse(cell) {
+                    Some((number, cell_rules)) => {
+                        parsed += 1;
+                        // Plain numbers keep their text so codes such as "007" stay unchanged
+                        if cell_rules.is_empty() {
+                            return Value::String(cell.clone());
+                        }
+                        converted += 1;
+                        rules.merge(cell_rules);
+                        Value::String(canonical_number(number))
+                    }
+                    None => Value::String(cell.clone()),
+                }
+            })
+            .collect();
+
+        if converted == 0 || parsed * 2 <= present {
+            continue;
+        }
+
+        *column = Column::from_cells(values, &NullValues::new::<&str>(&[]));
+        reports.push(NumberFormatReport {
+            column: header.clone(),
+            rules,
+            converted,
+            unparsed: present - parsed,
+        });
+    }
+    reports
+}
+
+/// 같은 열의 보고를 하나로 합침 (여러 파일이나 배치를 읽은 경우)
+pub fn merge_reports(reports: &mut Vec<NumberFormatReport>, others: &[NumberFormatReport]) {
+    for other in others {
+        match reports.iter_mut().find(|report| report.column == other.column) {
+            Some(report) => {
+                report.rules.merge(other.rules);
+                report.converted += other.converted;
+                report.unparsed += other.unparsed;
+            }
+            None => reports.push(other.clone()),
+        }
+    }
+}
+
+// Whole numbers are written without a fraction so the column can be stored as integers
+fn canonical_number(number: f64) -> String {
+    if number.fract() == 0.0 && number.abs() < 1e15 {
+        format!("{}", number as i64)
+    } else {
+        format!("{}", number)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn number(format: &NumberFormat, text: &str) -> Option<f64> {
+        format.parse(text).map(|(number, _)| number)
+    }
+
+    #[test]
+    fn parses_formatted_numbers() {
+        let format = NumberFormat::default();
+
+        assert_eq!(number(&format, "1,234,567"), Some(1_234_567.0));
+        assert_eq!(number(&format, "₩12,000"), Some(12_000.0));
+        assert_eq!(number(&format, "12,000원"), Some(12_000.0));
+        assert_eq!(number(&format, "-₩1,200"), Some(-1_200.0));
+        assert_eq!(number(&format, "₩-1,200"), Some(-1_200.0));
+        assert_eq!(number(&format, "15.3%"), Some(15.3));
+        assert_eq!(number(&format, "(1,200)"), Some(-1_200.0));
+        assert_eq!(number(&format, "△500"), Some(-500.0));
+        assert_eq!(number(&format, " 42 "), Some(42.0));
+
+        let (_, rules) = format.parse("(₩1,200)").unwrap();
+        assert!(rules.accounting_negative && rules.currency && rules.thousands_separator);
+        assert!(!rules.percent && !rules.decimal_comma);
+        assert!(format.parse("3.5").unwrap().1.is_empty());
+    }
+
+    #[test]
+    fn rejects_text_and_misplaced_separators() {
+        let format = NumberFormat::default();
+
+        for text in ["", "abc", "12,34", "1,2345", ",123", "1.2.3", "12.", "₩", "%", "1,234 개"] {
+            assert_eq!(number(&format, text), None, "{}", text);
+        }
+    }
+
+    #[test]
+    fn percent_as_fraction_divides_by_100() {
+        let format = NumberFormat { percent_as_fraction: true, ..NumberFormat::default() };
+
+        assert!((number(&format, "15.3%").unwrap() - 0.153).abs() < 1e-12);
+        assert_eq!(number(&format, "15.3"), Some(15.3));
+    }
+
+    #[test]
+    fn decimal_comma_swaps_separators() {
+        let format = NumberFormat::decimal_comma();
+
+        assert_eq!(number(&format, "1.234,5"), Some(1234.5));
+        assert_eq!(number(&format, "1 234,5"), Some(1234.5));
+        assert_eq!(number(&format, "12,5%"), Some(12.5));
+        assert_eq!(number(&format, "1,234.5"), None);
+        assert!(format.parse("0,5").unwrap().1.decimal_comma);
+    }
+
+    #[test]
+    fn plain_format_reads_only_bare_numbers() {
+        let format = NumberFormat::plain();
+
+        assert_eq!(number(&format, "1234.5"), Some(1234.5));
+        assert_eq!(number(&format, "-7"), Some(-7.0));
+        for text in ["1,234", "₩500", "15%", "(1,200)", "△500"] {
+            assert_eq!(number(&format, text), None, "{}", text);
+        }
+    }
+
+    #[test]
+    fn converts_mostly_formatted_columns() {
+        let mut dataset = DataSet::new(
+            vec!["금액".to_string(), "코드".to_string()],
+            vec![
+                vec!["₩12,000".to_string(), "007".to_string()],
+                vec!["3,500원".to_string(), "010".to_string()],
+                vec!["미정".to_string(), "A1".to_string()],
+            ],
+            "test".to_string(),
+        );
+
+        let reports = apply_number_format(&mut dataset, &NumberFormat::default());
+
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].column, "금액");
+        assert_eq!((reports[0].converted, reports[0].unparsed), (2, 1));
+        assert!(reports[0].rules.currency && reports[0].rules.thousands_separator);
+        // The unparsed cell keeps its text so it is reported as an invalid value
+        let amounts = dataset.column("금액").unwrap();
+        let cells: Vec<String> = (0..3).map(|row| amounts.get_string(row)).collect();
+        assert_eq!(cells, ["12000", "3500", "미정"]);
+        assert_eq!(dataset.column("코드").unwrap().get_string(0), "007");
+    }
+}
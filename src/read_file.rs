@@ -1,45 +1,137 @@
 use std::path::Path;
 use anyhow::{Result, anyhow};
-use csv::Reader;
-use calamine::{Reader as ExcelReader, open_workbook, Xlsx, DataType};
+use std::io::Write;
+use csv::{ByteRecord, ReaderBuilder};
+use std::fmt;
+use std::fs::File;
+use std::io::{BufReader, Cursor, Read};
+use std::str::FromStr;
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use calamine::{Reader as ExcelReader, open_workbook, Data, Ods, Range, Sheets, Xls, Xlsb, Xlsx};
+use crate::csv_sniffer;
+use crate::json_io;
+use crate::columnar_io;
+use crate::compression::{self, Compression, CompressedWriter, DecompressedFile};
+pub use crate::compression::STDIO_PATH;
+use crate::sqlite_io::{self, SqliteSource};
+use crate::multi_file::{self, SchemaMode};
+use crate::fixed_width::{self, FixedWidthOptions};
+use crate::number_format::{self, NumberFormat, NumberFormatReport};
+use crate::dates::{self, DateFormatReport, DateOptions};
+use crate::encoding::{self, TextEncoding};
+use crate::column::{parse_number_text, Column, ColumnType, MissingValueReport, NullValues, Value};
+
+/// 읽는 중 결측으로 처리한 오류 셀 (#N/A, #DIV/0! 등)
+#[derive(Debug, Clone)]
+pub struct CellError {
+    pub row: usize,
+    pub column: String,
+    pub error: String,
+}
+
+/// 필드 개수가 헤더와 달라 채우거나 잘라낸 행
+#[derive(Debug, Clone)]
+pub struct MalformedRow {
+    /// 데이터셋의 행 번호, 0부터
+    pub row: usize,
+    /// 파일에서 행이 시작하는 줄 번호, 1부터
+    pub line: usize,
+    pub fields: usize,
+    pub expected: usize,
+}
+
+/// 파일을 읽으면서 발견한 문제 목록
+#[derive(Debug, Clone, Default)]
+pub struct LoadReport {
+    pub cell_errors: Vec<CellError>,
+    pub malformed_rows: Vec<MalformedRow>,
+    /// 천 단위 구분자, 통화 기호 등을 해석해 숫자로 읽은 열
+    pub number_formats: Vec<NumberFormatReport>,
+    /// 지정한 형식, 시간대가 붙은 값, Unix 시각을 해석해 날짜/시간으로 읽은 열
+    pub date_formats: Vec<DateFormatReport>,
+}
+
+impl LoadReport {
+    pub fn is_empty(&self) -> bool {
+        self.cell_errors.is_empty()
+            && self.malformed_rows.is_empty()
+            && self.number_formats.is_empty()
+            && self.date_formats.is_empty()
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct DataSet {
     pub headers: Vec<String>,
-    pub data: Vec<Vec<String>>,
+    pub columns: Vec<Column>,
     pub file_path: String,
+    pub load_report: LoadReport,
 }
 
 impl DataSet {
+    /// 문자열 행 데이터로부터 열별 타입을 추론해 생성
     pub fn new(headers: Vec<String>, data: Vec<Vec<String>>, file_path: String) -> Self {
+        Self::with_null_values(headers, data, file_path, &NullValues::default())
+    }
+
+    /// 지정한 결측 표기를 사용해 생성
+    pub fn with_null_values(
+        headers: Vec<String>,
+        data: Vec<Vec<String>>,
+        file_path: String,
+        null_values: &NullValues,
+    ) -> Self {
+        let columns = (0..headers.len())
+            .map(|col_idx| {
+                let cells: Vec<&str> = data
+                    .iter()
+                    .map(|row| row.get(col_idx).map(String::as_str).unwrap_or(""))
+                    .collect();
+                Column::from_strings(&cells, null_values)
+            })
+            .collect();
+
+        Self::from_columns(headers, columns, file_path)
+    }
+
+    pub fn from_columns(headers: Vec<String>, columns: Vec<Column>, file_path: String) -> Self {
         Self {
             headers,
-            data,
+            columns,
             file_path,
+            load_report: LoadReport::default(),
         }
     }
 
-    pub fn get_column(&self, column_name: &str) -> Result<Vec<String>> {
-        let column_index = self.headers
+    pub fn column_index(&self, column_name: &str) -> Result<usize> {
+        self.headers
             .iter()
             .position(|h| h == column_name)
-            .ok_or_else(|| anyhow!("Column '{}' not found", column_name))?;
+            .ok_or_else(|| anyhow!("Column '{}' not found", column_name))
+    }
 
-        Ok(self.data
-            .iter()
-            .map(|row| row.get(column_index).unwrap_or(&String::new()).clone())
-            .collect())
+    pub fn column(&self, column_name: &str) -> Result<&Column> {
+        Ok(&self.columns[self.column_index(column_name)?])
     }
 
-    pub fn get_numeric_column(&self, column_name: &str) -> Result<Vec<f64>> {
-        let column_data = self.get_column(column_name)?;
-        let mut numeric_data = Vec::new();
+    pub fn get_column(&self, column_name: &str) -> Result<Vec<String>> {
+        let column = self.column(column_name)?;
+        Ok((0..column.len()).map(|i| column.get_string(i)).collect())
+    }
 
-        for value in column_data {
-            if let Ok(num) = value.parse::<f64>() {
-                numeric_data.push(num);
-            }
-        }
+    pub fn get_numeric_column(&self, column_name: &str) -> Result<Vec<f64>> {
+        let column = self.column(column_name)?;
+        let numeric_data = match column.numeric_values() {
+            Some(values) => values,
+            None if column.column_type() == ColumnType::String => column
+                .values()
+                .filter_map(|value| match value {
+                    Value::String(s) => parse_number_text(&s),
+                    _ => None,
+                })
+                .collect(),
+            None => Vec::new(),
+        };
 
         if numeric_data.is_empty() {
             return Err(anyhow!("No numeric data found in column '{}'", column_name));
@@ -48,12 +140,40 @@ impl DataSet {
         Ok(numeric_data)
     }
 
-    pub fn get_row(&self, index: usize) -> Option<&Vec<String>> {
-        self.data.get(index)
+    pub fn missing_value_report(&self, column_name: &str) -> Result<MissingValueReport> {
+        Ok(self.column(column_name)?.missing_value_report())
+    }
+
+    pub fn get_row(&self, index: usize) -> Option<Vec<String>> {
+        if index >= self.row_count() {
+            return None;
+        }
+        Some(self.columns.iter().map(|column| column.get_string(index)).collect())
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = Vec<String>> + '_ {
+        (0..self.row_count()).filter_map(move |i| self.get_row(i))
+    }
+
+    /// 지정한 열만 지정한 순서로 남긴 데이터셋 생성
+    pub fn select_columns(&self, column_names: &[String]) -> Result<DataSet> {
+        let mut columns = Vec::with_capacity(column_names.len());
+        for name in column_names {
+            columns.push(self.column(name)?.clone());
+        }
+        let mut dataset = DataSet::from_columns(column_names.to_vec(), columns, self.file_path.clone());
+        dataset.load_report = self.load_report.clone();
+        Ok(dataset)
+    }
+
+    /// 지정한 행만 뽑아 새 데이터셋 생성
+    pub fn take_rows(&self, indices: &[usize], file_path: String) -> DataSet {
+        let columns = self.columns.iter().map(|column| column.take(indices)).collect();
+        DataSet::from_columns(self.headers.clone(), columns, file_path)
     }
 
     pub fn row_count(&self) -> usize {
-        self.data.len()
+        self.columns.first().map(Column::len).unwrap_or(0)
     }
 
     pub fn column_count(&self) -> usize {
@@ -61,111 +181,1092 @@ impl DataSet {
     }
 }
 
-pub fn read_csv_file<P: AsRef<Path>>(file_path: P) -> Result<DataSet> {
-    let path = file_path.as_ref();
-    let mut reader = Reader::from_path(path)?;
+/// CSV 읽기 옵션. None인 항목은 파일 앞부분을 보고 자동으로 추정
+#[derive(Debug, Clone, Default)]
+pub struct CsvOptions {
+    pub delimiter: Option<u8>,
+    pub quote: Option<u8>,
+    pub has_headers: Option<bool>,
+    pub encoding: Option<TextEncoding>,
+    pub null_values: NullValues,
+    /// 필드 개수가 헤더와 다른 행 처리 방식
+    pub ragged_rows: RaggedRows,
+}
+
+/// 필드 개수가 헤더와 다른 행 처리 방식
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RaggedRows {
+    /// 문제 행을 모두 모아 오류로 반환
+    #[default]
+    Strict,
+    /// 모자란 칸은 결측으로 채우고 넘치는 칸은 버린 뒤 LoadReport에 기록
+    Lenient,
+}
+
+impl fmt::Display for RaggedRows {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            RaggedRows::Strict => "strict",
+            RaggedRows::Lenient => "lenient",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for RaggedRows {
+    type Err = anyhow::Error;
 
-    // Read headers
-    let headers = reader.headers()?
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "strict" => Ok(RaggedRows::Strict),
+            "lenient" => Ok(RaggedRows::Lenient),
+            _ => Err(anyhow!("Unknown ragged row mode: {} (use strict or lenient)", s)),
+        }
+    }
+}
+
+/// 행마다 필드 개수를 헤더 개수에 맞춤. lines는 각 행이 시작하는 줄 번호, first_row는 첫 행의 데이터셋 행 번호.
+/// Strict는 맞지 않는 행을 모두 나열한 오류를, Lenient는 채우거나 잘라낸 행 목록을 반환
+pub(crate) fn fit_rows(
+    rows: &mut [Vec<String>],
+    lines: &[usize],
+    width: usize,
+    mode: RaggedRows,
+    first_row: usize,
+    file_path: &str,
+) -> Result<Vec<MalformedRow>> {
+    let malformed: Vec<MalformedRow> = rows
         .iter()
-        .map(|h| h.to_string())
-        .collect::<Vec<String>>();
+        .zip(lines)
+        .enumerate()
+        .filter(|(_, (row, _))| row.len() != width)
+        .map(|(row_idx, (row, &line))| MalformedRow {
+            row: first_row + row_idx,
+            line,
+            fields: row.len(),
+            expected: width,
+        })
+        .collect();
+
+    if malformed.is_empty() {
+        return Ok(malformed);
+    }
 
-    // Read data
+    if mode == RaggedRows::Strict {
+        let problems: Vec<String> = malformed
+            .iter()
+            .map(|m| format!("  line {}: {} fields", m.line, m.fields))
+            .collect();
+        return Err(anyhow!(
+            "{} row(s) in '{}' have the wrong number of fields (expected {}):\n{}",
+            malformed.len(),
+            file_path,
+            width,
+            problems.join("\n")
+        ));
+    }
+
+    for row in rows.iter_mut() {
+        row.resize(width, String::new());
+    }
+    Ok(malformed)
+}
+
+/// CSV 저장 옵션
+#[derive(Debug, Clone, Default)]
+pub struct CsvWriteOptions {
+    /// 한글 Excel이 UTF-8로 인식하도록 BOM을 붙임
+    pub utf8_bom: bool,
+    /// 출력 압축 형식. None이면 저장 경로의 확장자(.gz, .zst, .bz2, .zip)를 따름
+    pub compression: Option<Compression>,
+}
+
+const SNIFF_SAMPLE_SIZE: usize = 64 * 1024;
+
+pub fn read_csv_file<P: AsRef<Path>>(file_path: P, options: &CsvOptions) -> Result<DataSet> {
+    let path = file_path.as_ref();
+    read_csv_reader(BufReader::new(File::open(path)?), path.to_string_lossy().to_string(), options)
+}
+
+/// 읽는 대로 CSV를 해석 (압축을 풀면서 읽는 파일, 표준 입력 등). 내용 전체를 한 번에 메모리에 올리지 않으며
+/// UTF-16만 필드 단위로 디코딩할 수 없어 끝까지 읽은 뒤 해석. file_path는 데이터셋에 기록할 이름
+pub fn read_csv_reader<R: Read + 'static>(reader: R, file_path: String, options: &CsvOptions) -> Result<DataSet> {
+    let mut stream: Box<dyn Read> = Box::new(reader);
+    let mut sample = Vec::with_capacity(SNIFF_SAMPLE_SIZE);
+    stream.by_ref().take(SNIFF_SAMPLE_SIZE as u64).read_to_end(&mut sample)?;
+    let encoding = options.encoding.unwrap_or_else(|| encoding::detect_encoding(&sample));
+    if matches!(encoding, TextEncoding::Utf16Le | TextEncoding::Utf16Be) {
+        let mut bytes = sample;
+        stream.read_to_end(&mut bytes)?;
+        return read_csv_bytes(&bytes, file_path, options);
+    }
+
+    let mut csv = CsvStream::open(sample, stream, encoding, options);
     let mut data = Vec::new();
+    let mut lines = Vec::new();
+    while let Some((line, row)) = csv.next_row()? {
+        lines.push(line);
+        data.push(row);
+    }
+    csv_dataset(data, lines, csv.has_headers, file_path, options)
+}
+
+/// 앞부분(sample)으로 인코딩에 맞춰 구분자, 따옴표, 헤더 여부를 판별한 CSV 리더. sample은 나머지 내용 앞에 다시 붙여 읽음
+pub(crate) struct CsvStream {
+    reader: csv::Reader<Box<dyn Read>>,
+    encoding: TextEncoding,
+    pub(crate) has_headers: bool,
+}
+
+impl CsvStream {
+    /// encoding은 UTF-16이 아니어야 함
+    pub(crate) fn open(mut sample: Vec<u8>, rest: Box<dyn Read>, encoding: TextEncoding, options: &CsvOptions) -> Self {
+        if sample.starts_with(b"\xEF\xBB\xBF") {
+            sample.drain(..3);
+        }
+        let (sample_text, _) = encoding::decode(&sample, Some(encoding));
+        let dialect = csv_sniffer::sniff(sample_text.as_bytes());
+
+        let reader = ReaderBuilder::new()
+            .delimiter(options.delimiter.unwrap_or(dialect.delimiter))
+            .quote(options.quote.unwrap_or(dialect.quote))
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(Box::new(Cursor::new(sample).chain(rest)) as Box<dyn Read>);
+        Self {
+            reader,
+            encoding,
+            has_headers: options.has_headers.unwrap_or(dialect.has_headers),
+        }
+    }
+
+    /// 다음 행과 그 행이 시작하는 줄 번호
+    pub(crate) fn next_row(&mut self) -> Result<Option<(usize, Vec<String>)>> {
+        let mut record = ByteRecord::new();
+        if !self.reader.read_byte_record(&mut record)? {
+            return Ok(None);
+        }
+        let line = record.position().map(|p| p.line() as usize).unwrap_or(0);
+        Ok(Some((line, record.iter().map(|field| encoding::decode_field(field, self.encoding)).collect())))
+    }
+}
+
+/// 메모리에 있는 CSV 내용을 읽음 (zip에서 꺼낸 파일 등). file_path는 데이터셋에 기록할 이름
+pub fn read_csv_bytes(bytes: &[u8], file_path: String, options: &CsvOptions) -> Result<DataSet> {
+    let (text, _) = encoding::decode(bytes, options.encoding);
+
+    // Sniff the dialect from the first chunk of the file
+    let sample = &text.as_bytes()[..text.len().min(SNIFF_SAMPLE_SIZE)];
+    let dialect = csv_sniffer::sniff(sample);
+
+    let has_headers = options.has_headers.unwrap_or(dialect.has_headers);
+    let mut reader = ReaderBuilder::new()
+        .delimiter(options.delimiter.unwrap_or(dialect.delimiter))
+        .quote(options.quote.unwrap_or(dialect.quote))
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(text.as_bytes());
+
+    // Read data, remembering where each record starts for the load report
+    let mut data = Vec::new();
+    let mut lines = Vec::new();
     for result in reader.records() {
         let record = result?;
-        let row: Vec<String> = record.iter().map(|field| field.to_string()).collect();
-        data.push(row);
+        lines.push(record.position().map(|p| p.line() as usize).unwrap_or(0));
+        data.push(record.iter().map(|field| field.to_string()).collect::<Vec<String>>());
     }
+    csv_dataset(data, lines, has_headers, file_path, options)
+}
 
-    Ok(DataSet::new(
-        headers,
-        data,
-        path.to_string_lossy().to_string(),
-    ))
+fn csv_dataset(
+    mut data: Vec<Vec<String>>,
+    mut lines: Vec<usize>,
+    has_headers: bool,
+    file_path: String,
+    options: &CsvOptions,
+) -> Result<DataSet> {
+    // Read headers, or generate column names from the width of the first row
+    let headers = if has_headers && !data.is_empty() {
+        lines.remove(0);
+        data.remove(0)
+    } else {
+        let width = data.first().map(Vec::len).unwrap_or(0);
+        (1..=width).map(|i| format!("column_{}", i)).collect()
+    };
+
+    let malformed_rows = fit_rows(&mut data, &lines, headers.len(), options.ragged_rows, 0, &file_path)?;
+    let mut dataset = DataSet::with_null_values(headers, data, file_path, &options.null_values);
+    dataset.load_report.malformed_rows = malformed_rows;
+    Ok(dataset)
 }
 
-pub fn read_excel_file<P: AsRef<Path>>(file_path: P) -> Result<DataSet> {
-    let path = file_path.as_ref();
-    let mut workbook: Xlsx<_> = open_workbook(path)?;
+/// 파일 내용으로 판별한 스프레드시트 형식
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkbookFormat {
+    Xls,
+    Xlsx,
+    Xlsb,
+    Ods,
+    Zip,
+    Unknown,
+}
+
+impl fmt::Display for WorkbookFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            WorkbookFormat::Xls => "XLS (Excel 97-2003)",
+            WorkbookFormat::Xlsx => "XLSX (Office Open XML)",
+            WorkbookFormat::Xlsb => "XLSB (Excel binary)",
+            WorkbookFormat::Ods => "ODS (OpenDocument)",
+            WorkbookFormat::Zip => "ZIP archive without a workbook",
+            WorkbookFormat::Unknown => "unknown (not a spreadsheet)",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+const OLE_MAGIC: &[u8] = b"\xD0\xCF\x11\xE0\xA1\xB1\x1A\xE1";
+const ZIP_MAGIC: &[u8] = b"PK\x03\x04";
+
+/// 확장자 대신 파일 시그니처로 스프레드시트 형식 판별
+pub fn detect_workbook_format<P: AsRef<Path>>(file_path: P) -> Result<WorkbookFormat> {
+    let bytes = std::fs::read(file_path.as_ref())?;
+    Ok(workbook_format_from_bytes(&bytes))
+}
+
+fn workbook_format_from_bytes(bytes: &[u8]) -> WorkbookFormat {
+    if bytes.starts_with(OLE_MAGIC) {
+        return WorkbookFormat::Xls;
+    }
+    if !bytes.starts_with(ZIP_MAGIC) {
+        return WorkbookFormat::Unknown;
+    }
+
+    // Entry names are stored uncompressed in the zip headers
+    let contains = |needle: &[u8]| bytes.windows(needle.len()).any(|window| window == needle);
+    if contains(b"xl/workbook.bin") {
+        WorkbookFormat::Xlsb
+    } else if contains(b"xl/workbook.xml") {
+        WorkbookFormat::Xlsx
+    } else if contains(b"application/vnd.oasis.opendocument.spreadsheet") {
+        WorkbookFormat::Ods
+    } else {
+        WorkbookFormat::Zip
+    }
+}
+
+fn open_workbook_detected(path: &Path) -> Result<Sheets<BufReader<File>>> {
+    let format = detect_workbook_format(path)?;
+    let opened = match format {
+        WorkbookFormat::Xls => open_workbook::<Xls<_>, _>(path).map(Sheets::Xls).map_err(anyhow::Error::from),
+        WorkbookFormat::Xlsx => open_workbook::<Xlsx<_>, _>(path).map(Sheets::Xlsx).map_err(anyhow::Error::from),
+        WorkbookFormat::Xlsb => open_workbook::<Xlsb<_>, _>(path).map(Sheets::Xlsb).map_err(anyhow::Error::from),
+        WorkbookFormat::Ods => open_workbook::<Ods<_>, _>(path).map(Sheets::Ods).map_err(anyhow::Error::from),
+        WorkbookFormat::Zip | WorkbookFormat::Unknown => {
+            return Err(anyhow!("Unsupported workbook format: detected {}", format));
+        }
+    };
+
+    opened.map_err(|e| anyhow!("Error reading {} workbook: {}", format, e))
+}
 
-    // Get the first worksheet
+/// 읽을 시트 지정 (이름 또는 0부터 시작하는 순번)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SheetSelector {
+    Index(usize),
+    Name(String),
+}
+
+/// Excel 읽기 옵션
+#[derive(Debug, Clone)]
+pub struct ExcelOptions {
+    pub sheet: SheetSelector,
+    /// 헤더가 있는 행 (읽을 범위 기준, 0부터). None이면 열 이름을 자동 생성
+    pub header_row: Option<usize>,
+    /// 헤더 다음에 건너뛸 행 수 (단위 표기 행 등)
+    pub skip_rows: usize,
+    /// A1 형식 범위 (예: "B4:F20", 끝을 생략한 "B4")
+    pub range: Option<String>,
+    pub null_values: NullValues,
+}
+
+impl Default for ExcelOptions {
+    fn default() -> Self {
+        Self {
+            sheet: SheetSelector::Index(0),
+            header_row: Some(0),
+            skip_rows: 0,
+            range: None,
+            null_values: NullValues::default(),
+        }
+    }
+}
+
+/// 시트 이름과 사용 중인 영역 크기
+#[derive(Debug, Clone)]
+pub struct SheetInfo {
+    pub name: String,
+    pub rows: usize,
+    pub columns: usize,
+    pub used_range: Option<String>,
+}
+
+/// 통합 문서의 시트 목록과 크기 조회
+pub fn list_sheets<P: AsRef<Path>>(file_path: P) -> Result<Vec<SheetInfo>> {
+    let mut workbook = open_workbook_detected(file_path.as_ref())?;
+    let mut sheets = Vec::new();
+
+    for name in workbook.sheet_names().to_owned() {
+        let range = read_worksheet_range(&mut workbook, &name)?;
+        let (rows, columns) = range.get_size();
+        let used_range = match (range.start(), range.end()) {
+            (Some(start), Some(end)) => Some(format!("{}:{}", cell_reference(start), cell_reference(end))),
+            _ => None,
+        };
+        sheets.push(SheetInfo {
+            name,
+            rows,
+            columns,
+            used_range,
+        });
+    }
+
+    Ok(sheets)
+}
+
+fn read_worksheet_range(workbook: &mut Sheets<BufReader<File>>, name: &str) -> Result<Range<Data>> {
+    workbook
+        .worksheet_range(name)
+        .map_err(|e| anyhow!("Error reading worksheet '{}': {}", name, e))
+}
+
+fn select_sheet_name(workbook: &Sheets<BufReader<File>>, selector: &SheetSelector) -> Result<String> {
     let worksheet_names = workbook.sheet_names();
     if worksheet_names.is_empty() {
         return Err(anyhow!("No worksheets found in Excel file"));
     }
 
-    let worksheet_name = &worksheet_names[0];
-    let range = workbook
-        .worksheet_range(worksheet_name)
-        .ok_or_else(|| anyhow!("Worksheet '{}' not found", worksheet_name))?
-        .map_err(|e| anyhow!("Error reading worksheet '{}': {}", worksheet_name, e))?;
+    match selector {
+        SheetSelector::Index(index) => worksheet_names.get(*index).cloned().ok_or_else(|| {
+            anyhow!("Worksheet index {} out of range ({} sheets)", index, worksheet_names.len())
+        }),
+        SheetSelector::Name(name) => worksheet_names
+            .iter()
+            .find(|n| *n == name)
+            .cloned()
+            .ok_or_else(|| anyhow!("Worksheet '{}' not found (available: {})", name, worksheet_names.join(", "))),
+    }
+}
+
+pub fn read_excel_file<P: AsRef<Path>>(file_path: P, options: &ExcelOptions) -> Result<DataSet> {
+    let path = file_path.as_ref();
+    let mut workbook = open_workbook_detected(path)?;
+
+    let worksheet_name = select_sheet_name(&workbook, &options.sheet)?;
+    read_sheet(&mut workbook, &worksheet_name, path, options)
+}
 
-    let mut headers = Vec::new();
-    let mut data = Vec::new();
+fn read_sheet(
+    workbook: &mut Sheets<BufReader<File>>,
+    worksheet_name: &str,
+    path: &Path,
+    options: &ExcelOptions,
+) -> Result<DataSet> {
+    let range = read_worksheet_range(workbook, worksheet_name)?;
+
+    // Resolve the requested cell range against the sheet's used area
+    let (Some(used_start), Some(used_end)) = (range.start(), range.end()) else {
+        return Ok(DataSet::new(Vec::new(), Vec::new(), path.to_string_lossy().to_string()));
+    };
+    let (top_left, bottom_right) = match &options.range {
+        Some(a1) => {
+            let (start, end) = parse_a1_range(a1)?;
+            (start, end.unwrap_or(used_end))
+        }
+        None => (used_start, used_end),
+    };
 
     // Read data from the range
-    for (row_idx, row) in range.rows().enumerate() {
-        let row_data: Vec<String> = row
+    let mut rows: Vec<Vec<Data>> = (top_left.0..=bottom_right.0)
+        .map(|row| {
+            (top_left.1..=bottom_right.1)
+                .map(|col| range.get_value((row, col)).cloned().unwrap_or_default())
+                .collect()
+        })
+        .collect();
+
+    let headers: Vec<String> = match options.header_row {
+        Some(header_row) => {
+            if header_row >= rows.len() {
+                return Err(anyhow!("Header row {} is outside the selected range ({} rows)", header_row, rows.len()));
+            }
+            let headers = rows[header_row].iter().map(|cell| cell.to_string()).collect();
+            rows.drain(..=header_row);
+            headers
+        }
+        None => {
+            let width = rows.first().map(Vec::len).unwrap_or(0);
+            (1..=width).map(|i| format!("column_{}", i)).collect()
+        }
+    };
+    rows.drain(..options.skip_rows.min(rows.len()));
+
+    // Build typed columns, turning error cells into missing values
+    let mut load_report = LoadReport::default();
+    let columns = headers
+        .iter()
+        .enumerate()
+        .map(|(col_idx, header)| {
+            let values = rows
+                .iter()
+                .enumerate()
+                .map(|(row_idx, row)| {
+                    let cell = row.get(col_idx).unwrap_or(&Data::Empty);
+                    if let Data::Error(e) = cell {
+                        load_report.cell_errors.push(CellError {
+                            row: row_idx,
+                            column: header.clone(),
+                            error: e.to_string(),
+                        });
+                    }
+                    cell_to_value(cell)
+                })
+                .collect();
+            Column::from_cells(values, &options.null_values)
+        })
+        .collect();
+
+    let mut dataset = DataSet::from_columns(headers, columns, path.to_string_lossy().to_string());
+    dataset.load_report = load_report;
+    Ok(dataset)
+}
+
+/// Excel 셀을 값으로 변환. 날짜는 통합 문서의 1900/1904 날짜 체계에 맞춰 변환하고
+/// 기간(duration)은 초 단위 실수로 변환
+fn cell_to_value(cell: &Data) -> Value {
+    match cell {
+        Data::Empty | Data::Error(_) => Value::Null,
+        Data::String(s) => Value::String(s.clone()),
+        // Excel stores every number as a float; keep whole numbers as integers
+        Data::Float(f) if f.fract() == 0.0 && f.abs() < i64::MAX as f64 => Value::Integer(*f as i64),
+        Data::Float(f) => Value::Float(*f),
+        Data::Int(i) => Value::Integer(*i),
+        Data::Bool(b) => Value::Boolean(*b),
+        Data::DateTime(dt) if dt.is_duration() => dt
+            .as_duration()
+            .map(|d| Value::Float(d.num_milliseconds() as f64 / 1000.0))
+            .unwrap_or(Value::Null),
+        Data::DateTime(dt) => dt.as_datetime().map(datetime_value).unwrap_or(Value::Null),
+        Data::DateTimeIso(s) => NaiveDateTime::from_str(s)
+            .map(datetime_value)
+            .or_else(|_| NaiveDate::from_str(s).map(Value::Date))
+            .unwrap_or_else(|_| Value::String(s.clone())),
+        Data::DurationIso(s) => Value::String(s.clone()),
+    }
+}
+
+fn datetime_value(datetime: NaiveDateTime) -> Value {
+    if datetime.time() == NaiveTime::MIN {
+        Value::Date(datetime.date())
+    } else {
+        Value::DateTime(datetime)
+    }
+}
+
+/// 시트 안의 (행, 열) 좌표, 0부터 시작
+pub type CellPosition = (u32, u32);
+
+/// "B4" 형식의 셀 주소를 (행, 열) 0부터 시작하는 좌표로 변환
+pub fn parse_cell_reference(reference: &str) -> Result<CellPosition> {
+    let reference = reference.trim().to_uppercase();
+    let split = reference
+        .find(|c: char| c.is_ascii_digit())
+        .ok_or_else(|| anyhow!("Invalid cell reference: {}", reference))?;
+    let (letters, digits) = reference.split_at(split);
+
+    if letters.is_empty() || !letters.chars().all(|c| c.is_ascii_uppercase()) {
+        return Err(anyhow!("Invalid cell reference: {}", reference));
+    }
+
+    let col = letters.bytes().fold(0u32, |acc, b| acc * 26 + (b - b'A' + 1) as u32) - 1;
+    let row = digits
+        .parse::<u32>()
+        .ok()
+        .filter(|&row| row > 0)
+        .ok_or_else(|| anyhow!("Invalid cell reference: {}", reference))?
+        - 1;
+
+    Ok((row, col))
+}
+
+/// "B4:F20" 또는 "B4" 형식의 범위를 시작/끝 좌표로 변환
+pub fn parse_a1_range(range: &str) -> Result<(CellPosition, Option<CellPosition>)> {
+    match range.split_once(':') {
+        Some((start, end)) => {
+            let start = parse_cell_reference(start)?;
+            let end = parse_cell_reference(end)?;
+            if end.0 < start.0 || end.1 < start.1 {
+                return Err(anyhow!("Invalid range: {}", range));
+            }
+            Ok((start, Some(end)))
+        }
+        None => Ok((parse_cell_reference(range)?, None)),
+    }
+}
+
+fn cell_reference((row, col): CellPosition) -> String {
+    let mut letters = String::new();
+    let mut col = col + 1;
+    while col > 0 {
+        let rem = (col - 1) % 26;
+        letters.insert(0, (b'A' + rem as u8) as char);
+        col = (col - 1) / 26;
+    }
+    format!("{}{}", letters, row + 1)
+}
+
+/// 시트 순서를 유지하는 시트 이름별 데이터셋 모음
+#[derive(Debug, Clone, Default)]
+pub struct Workbook {
+    pub sheets: Vec<(String, DataSet)>,
+}
+
+impl Workbook {
+    pub fn get(&self, sheet_name: &str) -> Option<&DataSet> {
+        self.sheets
+            .iter()
+            .find(|(name, _)| name == sheet_name)
+            .map(|(_, dataset)| dataset)
+    }
+
+    pub fn sheet_names(&self) -> Vec<&str> {
+        self.sheets.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    /// 가장 많은 시트가 공유하는 헤더를 가진 시트들을 하나로 합치고 원본 시트 이름 열을 추가.
+    /// 합치지 않은 시트 이름도 함께 반환
+    pub fn stack(&self, source_column: &str) -> Result<(DataSet, Vec<String>)> {
+        let non_empty: Vec<&(String, DataSet)> = self
+            .sheets
+            .iter()
+            .filter(|(_, dataset)| !dataset.headers.is_empty())
+            .collect();
+
+        let shared_headers = non_empty
+            .iter()
+            .map(|(_, dataset)| &dataset.headers)
+            .max_by_key(|headers| {
+                let count = non_empty.iter().filter(|(_, d)| &d.headers == *headers).count();
+                // Prefer the earliest sheet on ties
+                let first = non_empty.iter().position(|(_, d)| &d.headers == *headers).unwrap_or(0);
+                (count, std::cmp::Reverse(first))
+            })
+            .ok_or_else(|| anyhow!("No worksheets with data to stack"))?;
+
+        if shared_headers.iter().any(|h| h == source_column) {
+            return Err(anyhow!("Column '{}' already exists in the worksheets", source_column));
+        }
+
+        let (stacked, skipped): (Vec<_>, Vec<_>) = self
+            .sheets
+            .iter()
+            .partition(|(_, dataset)| &dataset.headers == shared_headers);
+
+        let parts: Vec<(&str, &DataSet)> = stacked.iter().map(|(name, dataset)| (name.as_str(), dataset)).collect();
+        let file_path = stacked[0].1.file_path.clone();
+        let dataset = concat_datasets(&parts, Some(source_column), file_path)?;
+
+        Ok((dataset, skipped.into_iter().map(|(name, _)| name.clone()).collect()))
+    }
+}
+
+/// 통합 문서의 모든 시트를 읽음 (options.sheet는 무시하고 나머지 옵션을 각 시트에 적용)
+pub fn read_workbook<P: AsRef<Path>>(file_path: P, options: &ExcelOptions) -> Result<Workbook> {
+    let path = file_path.as_ref();
+    let mut workbook = open_workbook_detected(path)?;
+    let mut sheets = Vec::new();
+
+    for name in workbook.sheet_names().to_owned() {
+        let dataset = read_sheet(&mut workbook, &name, path, options)
+            .map_err(|e| anyhow!("Error reading worksheet '{}': {}", name, e))?;
+        sheets.push((name, dataset));
+    }
+
+    Ok(Workbook { sheets })
+}
+
+/// 헤더가 같은 데이터셋들을 위아래로 이어 붙임.
+/// source_column을 지정하면 각 행의 출처 이름을 담은 열을 맨 앞에 추가
+pub fn concat_datasets(parts: &[(&str, &DataSet)], source_column: Option<&str>, file_path: String) -> Result<DataSet> {
+    let Some((_, first)) = parts.first() else {
+        return Err(anyhow!("No datasets to concatenate"));
+    };
+
+    for (name, dataset) in parts {
+        if dataset.headers != first.headers {
+            return Err(anyhow!("Headers of '{}' do not match: {:?} vs {:?}", name, dataset.headers, first.headers));
+        }
+    }
+
+    Ok(stack_columns(parts, &first.headers, source_column, file_path))
+}
+
+/// 헤더가 다른 데이터셋들을 위아래로 이어 붙임. 열은 처음 나온 순서대로 모두 합치고
+/// 해당 열이 없는 데이터셋의 행은 결측으로 채움
+pub fn union_datasets(parts: &[(&str, &DataSet)], source_column: Option<&str>, file_path: String) -> Result<DataSet> {
+    if parts.is_empty() {
+        return Err(anyhow!("No datasets to concatenate"));
+    }
+
+    let mut headers: Vec<String> = Vec::new();
+    for (_, dataset) in parts {
+        for header in &dataset.headers {
+            if !headers.contains(header) {
+                headers.push(header.clone());
+            }
+        }
+    }
+
+    Ok(stack_columns(parts, &headers, source_column, file_path))
+}
+
+fn stack_columns(parts: &[(&str, &DataSet)], headers: &[String], source_column: Option<&str>, file_path: String) -> DataSet {
+    let mut combined_headers = Vec::new();
+    let mut columns = Vec::new();
+
+    if let Some(source_column) = source_column {
+        let sources = parts
+            .iter()
+            .flat_map(|(name, dataset)| std::iter::repeat_n(Value::String(name.to_string()), dataset.row_count()))
+            .collect();
+        combined_headers.push(source_column.to_string());
+        columns.push(Column::from_values(sources));
+    }
+
+    for header in headers {
+        let values = parts
             .iter()
-            .map(|cell| match cell {
-                DataType::Empty => String::new(),
-                DataType::String(s) => s.clone(),
-                DataType::Float(f) => f.to_string(),
-                DataType::Int(i) => i.to_string(),
-                DataType::Bool(b) => b.to_string(),
-                DataType::Error(e) => format!("Error: {:?}", e),
-                DataType::DateTime(dt) => dt.to_string(),
-                DataType::DateTimeIso(dt) => dt.clone(),
-                DataType::DurationIso(d) => d.clone(),
-                DataType::Duration(d) => d.to_string(),
+            .flat_map(|(_, dataset)| match dataset.column_index(header).ok() {
+                Some(col_idx) => dataset.columns[col_idx].values().collect::<Vec<_>>(),
+                None => vec![Value::Null; dataset.row_count()],
             })
             .collect();
+        combined_headers.push(header.clone());
+        columns.push(Column::from_values(values));
+    }
+
+    let mut dataset = DataSet::from_columns(combined_headers, columns, file_path);
+
+    // Carry over load problems with row numbers shifted into the combined dataset
+    let mut row_offset = 0;
+    for (_, part) in parts {
+        dataset.load_report.cell_errors.extend(part.load_report.cell_errors.iter().map(|e| CellError {
+            row: e.row + row_offset,
+            ..e.clone()
+        }));
+        dataset.load_report.malformed_rows.extend(part.load_report.malformed_rows.iter().map(|m| MalformedRow {
+            row: m.row + row_offset,
+            ..m.clone()
+        }));
+        number_format::merge_reports(&mut dataset.load_report.number_formats, &part.load_report.number_formats);
+        dates::merge_reports(&mut dataset.load_report.date_formats, &part.load_report.date_formats);
+        row_offset += part.row_count();
+    }
+
+    dataset
+}
+
+/// 데이터 파일 형식
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileFormat {
+    Csv,
+    Excel,
+    Json,
+    Ndjson,
+    Parquet,
+    Arrow,
+    Sqlite,
+    FixedWidth,
+}
 
-        if row_idx == 0 {
-            headers = row_data;
-        } else {
-            data.push(row_data);
+impl FileFormat {
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        let format = match extension.to_lowercase().as_str() {
+            "csv" | "tsv" | "txt" => FileFormat::Csv,
+            "xlsx" | "xlsm" | "xls" | "xlsb" | "ods" => FileFormat::Excel,
+            "json" => FileFormat::Json,
+            "ndjson" | "jsonl" => FileFormat::Ndjson,
+            "parquet" | "pq" => FileFormat::Parquet,
+            "arrow" | "feather" | "ipc" | "arrows" => FileFormat::Arrow,
+            "db" | "sqlite" | "sqlite3" => FileFormat::Sqlite,
+            "fwf" | "dat" => FileFormat::FixedWidth,
+            _ => return None,
+        };
+        Some(format)
+    }
+
+    /// 경로의 확장자로 판별. 압축 확장자는 건너뜀 (sales.csv.gz → Csv)
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Option<Self> {
+        let path = path.as_ref();
+        let path = match Compression::from_path(path) {
+            Some(_) => Path::new(path.file_stem()?),
+            None => path,
+        };
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(FileFormat::from_extension)
+    }
+
+    /// 내용의 앞부분으로 형식 추정 (확장자가 없는 표준 입력 등). 알 수 없으면 CSV
+    pub fn sniff(bytes: &[u8]) -> Self {
+        if !matches!(workbook_format_from_bytes(bytes), WorkbookFormat::Zip | WorkbookFormat::Unknown) {
+            return FileFormat::Excel;
+        }
+        if bytes.starts_with(b"PAR1") {
+            return FileFormat::Parquet;
+        }
+        // Arrow IPC files start with ARROW1; streams start with a continuation marker
+        if bytes.starts_with(b"ARROW1") || bytes.starts_with(b"\xFF\xFF\xFF\xFF") {
+            return FileFormat::Arrow;
+        }
+        if bytes.starts_with(b"SQLite format 3\0") {
+            return FileFormat::Sqlite;
+        }
+
+        let text = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+        match text.iter().find(|b| !b.is_ascii_whitespace()) {
+            Some(b'[') => FileFormat::Json,
+            Some(b'{') => {
+                // One object per line is NDJSON; a single document may span many lines
+                let first_line = text.split(|&b| b == b'\n').find(|line| !line.trim_ascii().is_empty());
+                match first_line.map(serde_json::from_slice::<serde_json::Value>) {
+                    Some(Ok(serde_json::Value::Object(_))) => FileFormat::Ndjson,
+                    _ => FileFormat::Json,
+                }
+            }
+            _ => FileFormat::Csv,
         }
     }
 
-    Ok(DataSet::new(
-        headers,
-        data,
-        path.to_string_lossy().to_string(),
-    ))
+    fn extension(&self) -> &'static str {
+        match self {
+            FileFormat::Csv => "csv",
+            FileFormat::Excel => "xlsx",
+            FileFormat::Json => "json",
+            FileFormat::Ndjson => "ndjson",
+            FileFormat::Parquet => "parquet",
+            FileFormat::Arrow => "arrow",
+            FileFormat::Sqlite => "db",
+            FileFormat::FixedWidth => "fwf",
+        }
+    }
+}
+
+impl fmt::Display for FileFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            FileFormat::Csv => "CSV",
+            FileFormat::Excel => "Excel",
+            FileFormat::Json => "JSON",
+            FileFormat::Ndjson => "NDJSON",
+            FileFormat::Parquet => "Parquet",
+            FileFormat::Arrow => "Arrow IPC",
+            FileFormat::Sqlite => "SQLite",
+            FileFormat::FixedWidth => "fixed-width text",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for FileFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "excel" => Ok(FileFormat::Excel),
+            "fixed" | "fixed-width" => Ok(FileFormat::FixedWidth),
+            other => FileFormat::from_extension(other).ok_or_else(|| anyhow!("Unsupported file format: {}", s)),
+        }
+    }
+}
+
+/// 파일 형식별 읽기 옵션
+#[derive(Debug, Clone, Default)]
+pub struct ReadOptions {
+    /// 파일 형식. None이면 확장자로, 표준 입력은 내용으로 판별
+    pub format: Option<FileFormat>,
+    pub csv: CsvOptions,
+    pub excel: ExcelOptions,
+    /// SQLite 테이블 또는 쿼리. None이면 테이블이 하나뿐일 때 그 테이블을 읽음
+    pub sqlite: Option<SqliteSource>,
+    /// 고정폭 파일의 열 정의. 지정하면 형식을 주지 않아도 고정폭으로 읽음
+    pub fixed_width: Option<FixedWidthOptions>,
+    /// 읽을 열 목록. Parquet/Arrow는 이 열만 디코딩하고 다른 형식은 읽은 뒤 선택
+    pub columns: Option<Vec<String>>,
+    /// 여러 파일을 읽을 때 각 행의 출처 파일 이름을 담을 열
+    pub source_column: Option<String>,
+    /// 여러 파일의 열 구성이 다를 때 처리 방식
+    pub schema_mode: SchemaMode,
+    /// 문자열로 읽힌 서식 있는 숫자(1,234 / ₩12,000 / 15.3% / (1,200))의 해석 규칙
+    pub numbers: NumberFormat,
+    /// 열별 날짜 형식과 시간대
+    pub dates: DateOptions,
+}
+
+impl ReadOptions {
+    /// 지정한 형식. 고정폭 열 정의가 있으면 고정폭
+    fn requested_format(&self) -> Option<FileFormat> {
+        self.format.or_else(|| self.fixed_width.as_ref().map(|_| FileFormat::FixedWidth))
+    }
 }
 
 pub fn read_file<P: AsRef<Path>>(file_path: P) -> Result<DataSet> {
-    let path = file_path.as_ref();
-    let extension = path
-        .extension()
-        .and_then(|ext| ext.to_str())
-        .ok_or_else(|| anyhow!("Unable to determine file extension"))?
-        .to_lowercase();
+    read_file_with_options(file_path, &ReadOptions::default())
+}
 
-    match extension.as_str() {
-        "csv" => read_csv_file(path),
-        "xlsx" | "xls" => read_excel_file(path),
-        _ => Err(anyhow!("Unsupported file format: {}", extension)),
+/// 파일 읽기. 경로가 "-"이면 표준 입력에서 읽고, 디렉터리나 글롭 패턴(data/*.csv)이면 맞는 파일을 모두 합침.
+/// 서식 있는 숫자 열은 options.numbers 규칙으로 숫자로, 날짜 열은 options.dates에 따라 날짜로 바꾸고 LoadReport에 기록
+pub fn read_file_with_options<P: AsRef<Path>>(file_path: P, options: &ReadOptions) -> Result<DataSet> {
+    let mut dataset = read_path(file_path.as_ref(), options)?;
+    apply_value_formats(&mut dataset, options)?;
+    Ok(dataset)
+}
+
+/// 서식 있는 숫자와 날짜 형식 규칙을 적용하고 결과를 불러오기 보고에 더함
+pub fn apply_value_formats(dataset: &mut DataSet, options: &ReadOptions) -> Result<()> {
+    let reports = number_format::apply_number_format(dataset, &options.numbers);
+    number_format::merge_reports(&mut dataset.load_report.number_formats, &reports);
+    let reports = dates::apply_date_options(dataset, &options.dates)?;
+    dates::merge_reports(&mut dataset.load_report.date_formats, &reports);
+    Ok(())
+}
+
+/// 파일을 읽고 열만 골라냄. 서식 있는 숫자와 날짜 형식은 apply_value_formats가 처리
+pub(crate) fn read_path(path: &Path, options: &ReadOptions) -> Result<DataSet> {
+    if path == Path::new(STDIO_PATH) {
+        return read_stdin(options);
+    }
+    if multi_file::is_multi_file_path(path) {
+        return multi_file::read_multiple_files(path, options);
+    }
+
+    match compression::detect_compression(path)? {
+        // Zip entries borrow the archive, so the single entry is read into memory
+        Some(Compression::Zip) => {
+            let decompressed = compression::decompress_file(path, Compression::Zip)?;
+            let format = options
+                .requested_format()
+                .or_else(|| FileFormat::from_path(&decompressed.name))
+                .unwrap_or_else(|| FileFormat::sniff(&decompressed.bytes));
+            return read_bytes(&decompressed.bytes, format, &path.to_string_lossy(), options);
+        }
+        Some(compression) => {
+            let stream = compression::open_decompressed(path, compression)?;
+            return read_stream(stream, &compression::inner_name(path), &path.to_string_lossy(), options);
+        }
+        None => {}
+    }
+
+    let format = match options.requested_format() {
+        Some(format) => format,
+        None => {
+            let extension = path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .ok_or_else(|| anyhow!("Unable to determine file extension"))?;
+            FileFormat::from_extension(extension)
+                .ok_or_else(|| anyhow!("Unsupported file format: {}", extension.to_lowercase()))?
+        }
+    };
+
+    let columns = options.columns.as_deref();
+    let dataset = match format {
+        FileFormat::Csv => read_csv_file(path, &options.csv),
+        FileFormat::Excel => read_excel_file(path, &options.excel),
+        FileFormat::Json => json_io::read_json_file(path),
+        FileFormat::Ndjson => json_io::read_ndjson_file(path),
+        FileFormat::Parquet => return columnar_io::read_parquet_file(path, columns),
+        FileFormat::Arrow => return columnar_io::read_arrow_file(path, columns),
+        FileFormat::Sqlite => sqlite_io::read_sqlite_file(path, options.sqlite.as_ref()),
+        FileFormat::FixedWidth => fixed_width::read_fixed_width_file(path, fixed_width_options(options)?),
+    }?;
+
+    match columns {
+        Some(columns) => dataset.select_columns(columns),
+        None => Ok(dataset),
+    }
+}
+
+/// 표준 입력을 읽음. gzip/zstd/bzip2는 풀면서 읽고, 형식을 지정하지 않으면 내용으로 판별
+fn read_stdin(options: &ReadOptions) -> Result<DataSet> {
+    // Peek at the magic bytes, then put them back in front of the rest of stdin
+    let mut magic = Vec::new();
+    std::io::stdin().take(4).read_to_end(&mut magic)?;
+    let stdin = Cursor::new(magic.clone()).chain(std::io::stdin());
+
+    match Compression::from_magic(&magic) {
+        // Workbooks are zip containers too; only other zip archives are unpacked
+        Some(Compression::Zip) => {
+            let mut bytes = Vec::new();
+            BufReader::new(stdin).read_to_end(&mut bytes)?;
+            let file = match workbook_format_from_bytes(&bytes) {
+                WorkbookFormat::Zip => compression::decompress(Cursor::new(bytes), Compression::Zip, STDIO_PATH)?,
+                _ => DecompressedFile {
+                    name: STDIO_PATH.to_string(),
+                    bytes,
+                },
+            };
+            let format = options
+                .requested_format()
+                .or_else(|| FileFormat::from_path(&file.name))
+                .unwrap_or_else(|| FileFormat::sniff(&file.bytes));
+            read_bytes(&file.bytes, format, STDIO_PATH, options)
+        }
+        Some(compression) => read_stream(compression::decoder(stdin, compression)?, STDIO_PATH, STDIO_PATH, options),
+        None => read_stream(Box::new(stdin), STDIO_PATH, STDIO_PATH, options),
+    }
+}
+
+/// 압축을 풀면서 읽는 내용이나 표준 입력을 읽음. CSV/JSON/NDJSON은 읽는 대로 해석하고, 임의 위치를 읽어야 하는
+/// 형식(Excel, Parquet, Arrow, SQLite)과 고정폭 파일만 끝까지 읽어 read_bytes로 넘김. name은 형식 판별에 쓸 이름
+fn read_stream(mut stream: Box<dyn Read>, name: &str, file_path: &str, options: &ReadOptions) -> Result<DataSet> {
+    let mut sample = Vec::with_capacity(SNIFF_SAMPLE_SIZE);
+    stream.by_ref().take(SNIFF_SAMPLE_SIZE as u64).read_to_end(&mut sample)?;
+    let format = options.requested_format().or_else(|| FileFormat::from_path(name));
+    // A workbook is only recognised from the zip directory at its end, so it is sniffed from the whole content
+    let format = match format {
+        Some(format) => Some(format),
+        None if sample.starts_with(ZIP_MAGIC) => None,
+        None => Some(FileFormat::sniff(&sample)),
+    };
+    let stream: Box<dyn Read> = Box::new(Cursor::new(sample).chain(stream));
+
+    let dataset = match format {
+        Some(FileFormat::Csv) => read_csv_reader(stream, file_path.to_string(), &options.csv)?,
+        Some(FileFormat::Json) => json_io::read_json_reader(stream, file_path)?,
+        Some(FileFormat::Ndjson) => json_io::read_ndjson_reader(stream, file_path)?,
+        _ => {
+            let mut bytes = Vec::new();
+            BufReader::new(stream).read_to_end(&mut bytes)?;
+            let format = format.unwrap_or_else(|| FileFormat::sniff(&bytes));
+            return read_bytes(&bytes, format, file_path, options);
+        }
+    };
+
+    match &options.columns {
+        Some(columns) => dataset.select_columns(columns),
+        None => Ok(dataset),
+    }
+}
+
+fn fixed_width_options(options: &ReadOptions) -> Result<&FixedWidthOptions> {
+    options
+        .fixed_width
+        .as_ref()
+        .ok_or_else(|| anyhow!("Fixed-width files need a column spec"))
+}
+
+/// 메모리에 있는 파일 내용을 읽음. CSV/JSON은 바로 읽고, 파일이 필요한 형식은 임시 파일에 써서 읽음
+fn read_bytes(bytes: &[u8], format: FileFormat, file_path: &str, options: &ReadOptions) -> Result<DataSet> {
+    let dataset = match format {
+        FileFormat::Csv => read_csv_bytes(bytes, file_path.to_string(), &options.csv)?,
+        FileFormat::FixedWidth => {
+            fixed_width::read_fixed_width_bytes(bytes, file_path.to_string(), fixed_width_options(options)?)?
+        }
+        FileFormat::Json | FileFormat::Ndjson => {
+            let text = std::str::from_utf8(bytes).map_err(|_| anyhow!("'{}' is not valid UTF-8 JSON", file_path))?;
+            if format == FileFormat::Json {
+                json_io::read_json_str(text, file_path)?
+            } else {
+                json_io::read_ndjson_str(text, file_path)?
+            }
+        }
+        FileFormat::Excel | FileFormat::Parquet | FileFormat::Arrow | FileFormat::Sqlite => {
+            let mut temp = tempfile::Builder::new()
+                .suffix(&format!(".{}", format.extension()))
+                .tempfile()?;
+            temp.write_all(bytes)?;
+            temp.flush()?;
+
+            let options = ReadOptions {
+                format: Some(format),
+                ..options.clone()
+            };
+            // read_path, not read_file_with_options: the caller applies number and date rules once
+            let mut dataset = read_path(temp.path(), &options)?;
+            dataset.file_path = file_path.to_string();
+            return Ok(dataset);
+        }
+    };
+
+    match &options.columns {
+        Some(columns) => dataset.select_columns(columns),
+        None => Ok(dataset),
+    }
+}
+
+/// 데이터셋을 형식에 맞게 저장. format이 None이면 확장자로 정하고, 경로가 "-"이면 표준 출력에 CSV로 씀
+pub fn save_dataset<P: AsRef<Path>>(dataset: &DataSet, output_path: P, format: Option<FileFormat>) -> Result<()> {
+    save_dataset_with_options(dataset, output_path, format, &CsvWriteOptions::default())
+}
+
+/// save_dataset과 같으며 CSV로 저장할 때 csv_options를 씀
+pub fn save_dataset_with_options<P: AsRef<Path>>(
+    dataset: &DataSet,
+    output_path: P,
+    format: Option<FileFormat>,
+    csv_options: &CsvWriteOptions,
+) -> Result<()> {
+    let path = output_path.as_ref();
+    let format = match format {
+        Some(format) => format,
+        None if path == Path::new(STDIO_PATH) => FileFormat::Csv,
+        None => FileFormat::from_path(path)
+            .ok_or_else(|| anyhow!("Unable to determine output format: {}", path.display()))?,
+    };
+
+    match format {
+        FileFormat::Csv => save_dataset_to_csv_with_options(dataset, path, csv_options),
+        FileFormat::Json => json_io::save_dataset_to_json(dataset, path, json_io::JsonLayout::Records),
+        FileFormat::Ndjson => json_io::save_dataset_to_json(dataset, path, json_io::JsonLayout::Lines),
+        FileFormat::Parquet => columnar_io::save_dataset_to_parquet(dataset, path),
+        FileFormat::Arrow => columnar_io::save_dataset_to_arrow(dataset, path),
+        FileFormat::Sqlite => {
+            let table = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or("data");
+            sqlite_io::save_dataset_to_sqlite(dataset, path, table)
+        }
+        FileFormat::Excel => Err(anyhow!("Writing Excel files is not supported")),
+        FileFormat::FixedWidth => Err(anyhow!("Writing fixed-width files is not supported")),
     }
 }
 
 pub fn save_dataset_to_csv<P: AsRef<Path>>(dataset: &DataSet, output_path: P) -> Result<()> {
+    save_dataset_to_csv_with_options(dataset, output_path, &CsvWriteOptions::default())
+}
+
+pub fn save_dataset_to_csv_with_options<P: AsRef<Path>>(
+    dataset: &DataSet,
+    output_path: P,
+    options: &CsvWriteOptions,
+) -> Result<()> {
     let path = output_path.as_ref();
-    let mut writer = csv::Writer::from_path(path)?;
+    let compression = options.compression.or_else(|| Compression::from_path(path));
+    let mut file = CompressedWriter::create(path, compression, "csv")?;
+    if options.utf8_bom {
+        file.write_all(b"\xEF\xBB\xBF")?;
+    }
+    let mut writer = csv::Writer::from_writer(file);
 
     // Write headers
     writer.write_record(&dataset.headers)?;
 
     // Write data
-    for row in &dataset.data {
-        writer.write_record(row)?;
+    for row in dataset.rows() {
+        writer.write_record(&row)?;
     }
 
-    writer.flush()?;
-    Ok(())
+    let file = writer.into_inner().map_err(|e| anyhow!("Failed to write CSV: {}", e.error()))?;
+    file.finish()
 }
 
 pub fn extract_subset(dataset: &DataSet, row_indices: Option<Vec<usize>>, column_names: Option<Vec<String>>) -> Result<DataSet> {
@@ -190,25 +1291,223 @@ pub fn extract_subset(dataset: &DataSet, row_indices: Option<Vec<usize>>, column
     };
 
     let selected_rows = if let Some(rows) = row_indices {
-        rows
+        rows.into_iter().filter(|&row_idx| row_idx < dataset.row_count()).collect()
     } else {
-        (0..dataset.data.len()).collect()
+        (0..dataset.row_count()).collect::<Vec<_>>()
     };
 
-    let mut new_data = Vec::new();
-    for &row_idx in &selected_rows {
-        if let Some(row) = dataset.data.get(row_idx) {
-            let new_row: Vec<String> = column_indices
-                .iter()
-                .map(|&col_idx| row.get(col_idx).unwrap_or(&String::new()).clone())
-                .collect();
-            new_data.push(new_row);
-        }
-    }
+    let new_columns = column_indices
+        .iter()
+        .map(|&col_idx| dataset.columns[col_idx].take(&selected_rows))
+        .collect();
 
-    Ok(DataSet::new(
+    Ok(DataSet::from_columns(
         new_headers,
-        new_data,
+        new_columns,
         format!("{}_subset", dataset.file_path),
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zip::write::{SimpleFileOptions, ZipWriter};
+
+    const SHEET_NS: &str = "http://schemas.openxmlformats.org/spreadsheetml/2006/main";
+    const REL_NS: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
+
+    /// 테스트용 xlsx 작성. 숫자로 읽히는 칸은 숫자, "date:<일련번호>"는 날짜 서식 숫자, 빈 칸은 생략, 나머지는 문자열
+    fn write_xlsx(path: &Path, sheets: &[(&str, &[&[&str]])]) {
+        let mut zip = ZipWriter::new(File::create(path).unwrap());
+        let mut add = |name: &str, body: String| {
+            zip.start_file(name, SimpleFileOptions::default()).unwrap();
+            zip.write_all(body.as_bytes()).unwrap();
+        };
+
+        let overrides: String = (1..=sheets.len())
+            .map(|i| format!(r#"<Override PartName="/xl/worksheets/sheet{i}.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"/>"#))
+            .collect();
+        add("[Content_Types].xml", format!(
+            r#"<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/><Override PartName="/xl/workbook.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"/><Override PartName="/xl/styles.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.styles+xml"/>{overrides}</Types>"#
+        ));
+        add("_rels/.rels", format!(
+            r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="{REL_NS}/officeDocument" Target="xl/workbook.xml"/></Relationships>"#
+        ));
+
+        let entries: String = sheets
+            .iter()
+            .enumerate()
+            .map(|(i, (name, _))| format!(r#"<sheet name="{name}" sheetId="{id}" r:id="rId{id}"/>"#, id = i + 1))
+            .collect();
+        add("xl/workbook.xml", format!(r#"<workbook xmlns="{SHEET_NS}" xmlns:r="{REL_NS}"><sheets>{entries}</sheets></workbook>"#));
+        let relations: String = (1..=sheets.len())
+            .map(|i| format!(r#"<Relationship Id="rId{i}" Type="{REL_NS}/worksheet" Target="worksheets/sheet{i}.xml"/>"#))
+            .chain([format!(r#"<Relationship Id="rIdStyles" Type="{REL_NS}/styles" Target="styles.xml"/>"#)])
+            .collect();
+        add("xl/_rels/workbook.xml.rels", format!(
+            r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">{relations}</Relationships>"#
+        ));
+        add("xl/styles.xml", format!(
+            r#"<styleSheet xmlns="{SHEET_NS}"><cellXfs count="2"><xf numFmtId="0"/><xf numFmtId="14" applyNumberFormat="1"/></cellXfs></styleSheet>"#
+        ));
+
+        for (i, (_, rows)) in sheets.iter().enumerate() {
+            let mut data = String::new();
+            for (row, cells) in rows.iter().enumerate() {
+                data.push_str(&format!(r#"<row r="{}">"#, row + 1));
+                for (col, cell) in cells.iter().enumerate() {
+                    let at = cell_reference((row as u32, col as u32));
+                    let xml = if cell.is_empty() {
+                        continue;
+                    } else if let Some(serial) = cell.strip_prefix("date:") {
+                        format!(r#"<c r="{at}" s="1"><v>{serial}</v></c>"#)
+                    } else if cell.parse::<f64>().is_ok() {
+                        format!(r#"<c r="{at}"><v>{cell}</v></c>"#)
+                    } else {
+                        format!(r#"<c r="{at}" t="inlineStr"><is><t>{cell}</t></is></c>"#)
+                    };
+                    data.push_str(&xml);
+                }
+                data.push_str("</row>");
+            }
+            add(&format!("xl/worksheets/sheet{}.xml", i + 1), format!(r#"<worksheet xmlns="{SHEET_NS}"><sheetData>{data}</sheetData></worksheet>"#));
+        }
+        zip.finish().unwrap();
+    }
+
+    #[test]
+    fn workbooks_are_recognised_by_signature() {
+        let dir = tempfile::tempdir().unwrap();
+        let rows: &[&[&str]] = &[&["이름", "점수"], &["가", "90"], &["나", "85"]];
+        // Saved with the wrong extension, as older exports often are
+        let path = dir.path().join("scores.xls");
+        write_xlsx(&path, &[("점수", rows)]);
+
+        assert_eq!(detect_workbook_format(&path).unwrap(), WorkbookFormat::Xlsx);
+        let dataset = read_file(&path).unwrap();
+        assert_eq!(dataset.headers, ["이름", "점수"]);
+        assert_eq!(dataset.column("점수").unwrap().column_type(), ColumnType::Integer);
+
+        let not_a_workbook = dir.path().join("data.xlsx");
+        save_dataset_to_csv(&dataset, dir.path().join("data.csv.zip")).unwrap();
+        std::fs::rename(dir.path().join("data.csv.zip"), &not_a_workbook).unwrap();
+        let error = read_file(&not_a_workbook).unwrap_err().to_string();
+        assert!(error.contains("ZIP archive without a workbook"), "{}", error);
+    }
+
+    #[test]
+    fn reads_the_selected_sheet_header_row_and_range() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("report.xlsx");
+        let report: &[&[&str]] = &[
+            &["3월 실적 보고"],
+            &[],
+            &["", "지점", "매출", "비고"],
+            &["", "(단위)", "원", ""],
+            &["", "강남", "100", "x"],
+            &["", "종로", "200", ""],
+            &["", "합계", "300", ""],
+        ];
+        write_xlsx(&path, &[("요약", &[&["목차"]]), ("3월", report)]);
+
+        let options = ExcelOptions {
+            sheet: SheetSelector::Name("3월".to_string()),
+            header_row: Some(2),
+            skip_rows: 1,
+            ..ExcelOptions::default()
+        };
+        let dataset = read_excel_file(&path, &options).unwrap();
+        assert_eq!(dataset.headers[1..], ["지점", "매출", "비고"]);
+        assert_eq!(dataset.row_count(), 3);
+
+        // The range cuts off the first column and the totals row
+        let options = ExcelOptions {
+            sheet: SheetSelector::Index(1),
+            header_row: Some(0),
+            skip_rows: 1,
+            range: Some("B3:C6".to_string()),
+            ..ExcelOptions::default()
+        };
+        let dataset = read_excel_file(&path, &options).unwrap();
+        assert_eq!(dataset.headers, ["지점", "매출"]);
+        assert_eq!(dataset.rows().collect::<Vec<_>>(), [["강남", "100"], ["종로", "200"]]);
+
+        let missing = ExcelOptions { sheet: SheetSelector::Name("4월".to_string()), ..ExcelOptions::default() };
+        assert!(read_excel_file(&path, &missing).is_err());
+        assert!(parse_a1_range("C3:B2").is_err());
+    }
+
+    #[test]
+    fn stacks_sheets_that_share_headers() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("months.xlsx");
+        write_xlsx(&path, &[
+            ("요약", &[&["메모"], &["월별 시트 참고"]]),
+            ("1월", &[&["이름", "점수"], &["가", "10"], &["나", "20"]]),
+            ("2월", &[&["이름", "점수"], &["다", "30.5"]]),
+        ]);
+
+        let workbook = read_workbook(&path, &ExcelOptions::default()).unwrap();
+        assert_eq!(workbook.sheet_names(), ["요약", "1월", "2월"]);
+        assert_eq!(workbook.get("2월").unwrap().row_count(), 1);
+
+        let (stacked, skipped) = workbook.stack("sheet").unwrap();
+        assert_eq!(skipped, ["요약"]);
+        assert_eq!(stacked.headers, ["sheet", "이름", "점수"]);
+        let sheets: Vec<String> = stacked.column("sheet").unwrap().values().map(|v| v.to_string()).collect();
+        assert_eq!(sheets, ["1월", "1월", "2월"]);
+        assert_eq!(stacked.column("점수").unwrap().column_type(), ColumnType::Float);
+        assert!(workbook.stack("이름").is_err());
+    }
+
+    #[test]
+    fn keeps_excel_cell_types_and_dates() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("typed.xlsx");
+        let rows: &[&[&str]] = &[
+            &["일자", "시각", "수량", "단가", "코드"],
+            &["date:45352", "date:45352.5", "3", "1.5", "A1"],
+            &["date:45353", "date:45353.25", "4", "2", "007"],
+        ];
+        write_xlsx(&path, &[("data", rows)]);
+
+        let dataset = read_file(&path).unwrap();
+        let types: Vec<ColumnType> = dataset.columns.iter().map(Column::column_type).collect();
+        assert_eq!(types, [ColumnType::Date, ColumnType::DateTime, ColumnType::Integer, ColumnType::Float, ColumnType::String]);
+        assert_eq!(dataset.column("일자").unwrap().get_string(0), "2024-03-01");
+        assert_eq!(dataset.column("시각").unwrap().get_string(1), "2024-03-02 06:00:00");
+    }
+
+    #[test]
+    fn ragged_rows_are_reported_with_their_lines() {
+        let text = "a,b,c\n1,2,3\n4,5\n\"multi\nline\",6,7,8\n9,10,11\n";
+        let error = read_csv_bytes(text.as_bytes(), "ragged.csv".to_string(), &CsvOptions::default()).unwrap_err();
+        let message = error.to_string();
+        for expected in ["2 row(s)", "line 3: 2 fields", "line 4: 4 fields"] {
+            assert!(message.contains(expected), "{}", message);
+        }
+
+        let options = CsvOptions { ragged_rows: RaggedRows::Lenient, ..CsvOptions::default() };
+        let dataset = read_csv_bytes(text.as_bytes(), "ragged.csv".to_string(), &options).unwrap();
+        assert_eq!(dataset.get_row(1).unwrap(), ["4", "5", ""]);
+        assert_eq!(dataset.get_row(2).unwrap(), ["multi\nline", "6", "7"]);
+        let malformed: Vec<(usize, usize, usize)> =
+            dataset.load_report.malformed_rows.iter().map(|m| (m.row, m.line, m.fields)).collect();
+        assert_eq!(malformed, [(1, 3, 2), (2, 4, 4)]);
+    }
+
+    #[test]
+    fn streamed_csv_matches_in_memory_csv() {
+        let text = "이름;점수;비고\n김철수;90;\"세미콜론; 포함\"\n이영희;80;\n";
+        for bytes in [text.as_bytes().to_vec(), encoding_rs::EUC_KR.encode(text).0.into_owned()] {
+            let options = CsvOptions::default();
+            let streamed = read_csv_reader(Cursor::new(bytes.clone()), "test".to_string(), &options).unwrap();
+            let in_memory = read_csv_bytes(&bytes, "test".to_string(), &options).unwrap();
+
+            assert_eq!(streamed.headers, ["이름", "점수", "비고"]);
+            assert_eq!(streamed.headers, in_memory.headers);
+            assert_eq!(streamed.rows().collect::<Vec<_>>(), in_memory.rows().collect::<Vec<_>>());
+            assert_eq!(streamed.get_row(0).unwrap()[2], "세미콜론; 포함");
+        }
+    }
+}
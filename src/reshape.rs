@@ -0,0 +1,292 @@
+use std::collections::HashMap;
+use anyhow::{Result, anyhow};
+use crate::column::{Column, ColumnType, Value};
+use crate::group_by::{self, Aggregation, AggregationSpec};
+use crate::read_file::DataSet;
+use crate::sort;
+
+/// 긴 형식 → 넓은 형식 변환 옵션
+#[derive(Debug, Clone)]
+pub struct PivotOptions {
+    /// 결과의 한 행이 될 키 열
+    pub index: Vec<String>,
+    /// 값이 결과의 열 이름이 될 열
+    pub columns: String,
+    /// 셀에 들어갈 값 열
+    pub values: String,
+    /// 같은 칸에 값이 여러 개일 때의 집계. None이면 중복을 오류로 처리
+    pub aggregation: Option<Aggregation>,
+}
+
+impl PivotOptions {
+    pub fn new(index: &[&str], columns: &str, values: &str) -> Self {
+        Self {
+            index: index.iter().map(|name| name.to_string()).collect(),
+            columns: columns.to_string(),
+            values: values.to_string(),
+            aggregation: None,
+        }
+    }
+}
+
+/// columns 열의 값마다 열을 하나씩 만들어 index 열로 묶은 표. 결과 열은 index 열 다음에
+/// columns 열 값 순서(숫자/날짜는 값 순서, 문자열은 가나다순)로 오며 값이 없는 칸은 결측(count, nunique는 0).
+/// columns 열이 결측인 행은 건너뜀
+pub fn pivot(dataset: &DataSet, options: &PivotOptions) -> Result<DataSet> {
+    if options.index.is_empty() {
+        return Err(anyhow!("No index columns given for pivot"));
+    }
+    let pivot_column = dataset.column(&options.columns)?;
+    let spec = AggregationSpec::new(&options.values, options.aggregation.unwrap_or(Aggregation::First));
+    group_by::check_aggregations(dataset, std::slice::from_ref(&spec))?;
+
+    let mut names: Vec<Value> = Vec::new();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    for value in pivot_column.values().filter(|value| !value.is_null()) {
+        positions.entry(value.to_string()).or_insert_with(|| {
+            names.push(value);
+            names.len() - 1
+        });
+    }
+    names.sort_by(sort::compare_values);
+    let names: Vec<String> = names.iter().map(Value::to_string).collect();
+    let positions: HashMap<&str, usize> = names.iter().enumerate().map(|(i, name)| (name.as_str(), i)).collect();
+
+    if let Some(clash) = names.iter().find(|name| options.index.contains(name)) {
+        return Err(anyhow!("Pivot column value '{}' clashes with an index column name", clash));
+    }
+
+    let groups = group_by::group_rows(dataset, &options.index)?;
+    let mut cells: Vec<Vec<Value>> = vec![Vec::with_capacity(groups.len()); names.len()];
+    let mut duplicates = Vec::new();
+    // Counts of an empty cell are 0; every other aggregation of no rows is missing
+    let counts = matches!(spec.aggregation, Aggregation::Count | Aggregation::DistinctCount);
+    for group in &groups {
+        let mut rows_by_name: Vec<Vec<usize>> = vec![Vec::new(); names.len()];
+        for &row in &group.rows {
+            if !pivot_column.is_null(row) {
+                rows_by_name[positions[pivot_column.get_string(row).as_str()]].push(row);
+            }
+        }
+        for (name, rows) in names.iter().zip(&rows_by_name) {
+            if rows.len() > 1 && options.aggregation.is_none() {
+                duplicates.push(format!("{} / {}", group.label(), name));
+            }
+        }
+        for (column_cells, rows) in cells.iter_mut().zip(&rows_by_name) {
+            let value = if rows.is_empty() && !counts { Value::Null } else { group_by::aggregate(dataset, &spec, rows)? };
+            column_cells.push(value);
+        }
+    }
+    if !duplicates.is_empty() {
+        let shown: Vec<&str> = duplicates.iter().take(5).map(String::as_str).collect();
+        return Err(anyhow!(
+            "{} pivot cells have more than one value (e.g. {}); choose an aggregation",
+            duplicates.len(),
+            shown.join(", ")
+        ));
+    }
+
+    let first_rows: Vec<usize> = groups.iter().map(|group| group.rows[0]).collect();
+    let mut headers = options.index.clone();
+    let mut columns: Vec<Column> = options
+        .index
+        .iter()
+        .map(|key| Ok(dataset.column(key)?.take(&first_rows)))
+        .collect::<Result<_>>()?;
+
+    // Cells copied as they are keep the value column's type even when a whole column is missing
+    let value_type = match options.aggregation.unwrap_or(Aggregation::First) {
+        Aggregation::First | Aggregation::Last | Aggregation::Min | Aggregation::Max => {
+            Some(dataset.column(&options.values)?.column_type())
+        }
+        _ => None,
+    };
+    for (name, values) in names.into_iter().zip(cells) {
+        headers.push(name);
+        columns.push(match value_type {
+            Some(column_type) => Column::from_values_as(values, column_type),
+            None => Column::from_values(values),
+        });
+    }
+
+    Ok(DataSet::from_columns(headers, columns, dataset.file_path.clone()))
+}
+
+/// 넓은 형식 → 긴 형식 변환 옵션
+#[derive(Debug, Clone)]
+pub struct MeltOptions {
+    /// 그대로 반복할 열
+    pub id_columns: Vec<String>,
+    /// 행으로 펼칠 열. 비어 있으면 id 열이 아닌 모든 열
+    pub value_columns: Vec<String>,
+    /// 원래 열 이름을 담을 열 이름
+    pub variable_name: String,
+    /// 값을 담을 열 이름
+    pub value_name: String,
+    /// 값이 결측인 행을 뺄지
+    pub drop_nulls: bool,
+}
+
+impl Default for MeltOptions {
+    fn default() -> Self {
+        Self {
+            id_columns: Vec::new(),
+            value_columns: Vec::new(),
+            variable_name: "variable".to_string(),
+            value_name: "value".to_string(),
+            drop_nulls: false,
+        }
+    }
+}
+
+/// 값 열 여러 개를 (열 이름, 값) 두 열로 펼친 표. 원래 행마다 값 열 순서대로 한 행씩 생기며,
+/// 값 열의 타입이 서로 다르면 값 열은 공통 타입(정수와 실수는 실수, 그 밖에는 문자열)이 됨
+pub fn melt(dataset: &DataSet, options: &MeltOptions) -> Result<DataSet> {
+    let value_columns: Vec<String> = if options.value_columns.is_empty() {
+        dataset.headers.iter().filter(|header| !options.id_columns.contains(header)).cloned().collect()
+    } else {
+        options.value_columns.clone()
+    };
+    if value_columns.is_empty() {
+        return Err(anyhow!("No columns left to melt"));
+    }
+    if let Some(column) = value_columns.iter().find(|column| options.id_columns.contains(column)) {
+        return Err(anyhow!("Column '{}' is both an id column and a value column", column));
+    }
+    for name in [&options.variable_name, &options.value_name] {
+        if options.id_columns.contains(name) {
+            return Err(anyhow!("Output column '{}' clashes with an id column", name));
+        }
+    }
+    if options.variable_name == options.value_name {
+        return Err(anyhow!("Variable and value columns need different names"));
+    }
+
+    let ids: Vec<&Column> = options.id_columns.iter().map(|name| dataset.column(name)).collect::<Result<_>>()?;
+    let sources: Vec<&Column> = value_columns.iter().map(|name| dataset.column(name)).collect::<Result<_>>()?;
+
+    let mut source_rows = Vec::new();
+    let mut variables = Vec::new();
+    let mut values = Vec::new();
+    for row in 0..dataset.row_count() {
+        for (name, column) in value_columns.iter().zip(&sources) {
+            if options.drop_nulls && column.is_null(row) {
+                continue;
+            }
+            source_rows.push(row);
+            variables.push(Value::String(name.clone()));
+            values.push(column.get(row));
+        }
+    }
+
+    let mut headers = options.id_columns.clone();
+    let mut columns: Vec<Column> = ids.iter().map(|column| column.take(&source_rows)).collect();
+    headers.push(options.variable_name.clone());
+    columns.push(Column::from_values_as(variables, ColumnType::String));
+    headers.push(options.value_name.clone());
+    columns.push(Column::from_values_as(values, common_type(&sources)));
+
+    Ok(DataSet::from_columns(headers, columns, dataset.file_path.clone()))
+}
+
+fn common_type(columns: &[&Column]) -> ColumnType {
+    let mut types = columns.iter().map(|column| column.column_type());
+    let first = types.next().unwrap_or(ColumnType::String);
+    types.fold(first, |common, column_type| match (common, column_type) {
+        (a, b) if a == b => a,
+        (ColumnType::Integer | ColumnType::Float, ColumnType::Integer | ColumnType::Float) => ColumnType::Float,
+        (ColumnType::Date | ColumnType::DateTime, ColumnType::Date | ColumnType::DateTime) => ColumnType::DateTime,
+        _ => ColumnType::String,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dataset(headers: &[&str], rows: &[&[&str]]) -> DataSet {
+        DataSet::new(
+            headers.iter().map(|h| h.to_string()).collect(),
+            rows.iter().map(|row| row.iter().map(|cell| cell.to_string()).collect()).collect(),
+            "test".to_string(),
+        )
+    }
+
+    fn column(dataset: &DataSet, name: &str) -> Vec<String> {
+        let column = dataset.column(name).unwrap();
+        (0..dataset.row_count()).map(|i| column.get_string(i)).collect()
+    }
+
+    fn long() -> DataSet {
+        dataset(
+            &["학생", "과목", "점수"],
+            &[&["가", "수학", "90"], &["가", "국어", "80"], &["나", "수학", "70"], &["나", "수학", "75"], &["다", "", "60"]],
+        )
+    }
+
+    #[test]
+    fn pivot_spreads_values_and_reports_duplicate_cells() {
+        let error = pivot(&long(), &PivotOptions::new(&["학생"], "과목", "점수")).unwrap_err().to_string();
+        assert!(error.contains("나 / 수학"), "{}", error);
+
+        let options = PivotOptions { aggregation: Some(Aggregation::Mean), ..PivotOptions::new(&["학생"], "과목", "점수") };
+        let wide = pivot(&long(), &options).unwrap();
+        assert_eq!(wide.headers, ["학생", "국어", "수학"]);
+        assert_eq!(column(&wide, "학생"), ["가", "나", "다"]);
+        assert_eq!(column(&wide, "국어"), ["80", "", ""]);
+        assert_eq!(column(&wide, "수학"), ["90", "72.5", ""]);
+
+        let options = PivotOptions { aggregation: Some(Aggregation::Count), ..PivotOptions::new(&["학생"], "과목", "점수") };
+        assert_eq!(column(&pivot(&long(), &options).unwrap(), "수학"), ["1", "2", "0"]);
+    }
+
+    #[test]
+    fn melt_unpivots_value_columns() {
+        let wide = dataset(&["학생", "수학", "국어"], &[&["가", "90", "80"], &["나", "70", ""]]);
+        let options = MeltOptions { id_columns: vec!["학생".to_string()], ..MeltOptions::default() };
+        let melted = melt(&wide, &options).unwrap();
+
+        assert_eq!(melted.headers, ["학생", "variable", "value"]);
+        assert_eq!(column(&melted, "학생"), ["가", "가", "나", "나"]);
+        assert_eq!(column(&melted, "variable"), ["수학", "국어", "수학", "국어"]);
+        assert_eq!(melted.column("value").unwrap().column_type(), ColumnType::Integer);
+
+        let options = MeltOptions { drop_nulls: true, ..options };
+        assert_eq!(column(&melt(&wide, &options).unwrap(), "value"), ["90", "80", "70"]);
+    }
+
+    #[test]
+    fn melt_and_pivot_round_trip() {
+        let wide = dataset(&["학생", "수학", "국어"], &[&["가", "90", "80"], &["나", "70", "65"]]);
+        let options = MeltOptions {
+            id_columns: vec!["학생".to_string()],
+            variable_name: "과목".to_string(),
+            value_name: "점수".to_string(),
+            ..MeltOptions::default()
+        };
+        let back = pivot(&melt(&wide, &options).unwrap(), &PivotOptions::new(&["학생"], "과목", "점수")).unwrap();
+
+        assert_eq!(back.headers, ["학생", "국어", "수학"]);
+        assert_eq!(column(&back, "수학"), column(&wide, "수학"));
+        assert_eq!(column(&back, "국어"), column(&wide, "국어"));
+    }
+
+    #[test]
+    fn melt_rejects_clashing_names() {
+        let wide = dataset(&["id", "a"], &[&["1", "2"]]);
+        let options = MeltOptions {
+            id_columns: vec!["id".to_string(), "a".to_string()],
+            value_columns: vec!["a".to_string()],
+            ..MeltOptions::default()
+        };
+        assert!(melt(&wide, &options).is_err());
+
+        let options = MeltOptions {
+            id_columns: vec!["id".to_string()],
+            value_name: "variable".to_string(),
+            ..MeltOptions::default()
+        };
+        assert!(melt(&wide, &options).is_err());
+    }
+}
@@ -0,0 +1,415 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+use anyhow::{Result, anyhow};
+use crate::column::{self, Column, ColumnData, ColumnType, Value};
+use crate::dates::{self, DateFormatReport};
+use crate::encoding;
+use crate::read_file::DataSet;
+
+/// 타입으로 인정할 최소 비율 기본값 (결측이 아닌 값 중 해당 타입으로 읽히는 값의 비율)
+pub const DEFAULT_TYPE_THRESHOLD: f64 = 0.9;
+
+/// 텍스트 값이 이 길이를 넘으면 자유 텍스트로 봄
+const FREE_TEXT_MIN_CHARS: usize = 40;
+
+/// 분석 관점의 열 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SemanticType {
+    Integer,
+    Float,
+    Boolean,
+    Date,
+    DateTime,
+    /// 값이 반복되는 범주형
+    Categorical,
+    /// 문장처럼 대부분 서로 다른 텍스트
+    Text,
+    /// 행마다 다른 코드/번호 (고객번호, 우편번호 등)
+    Identifier,
+}
+
+impl SemanticType {
+    pub fn is_numeric(&self) -> bool {
+        matches!(self, SemanticType::Integer | SemanticType::Float)
+    }
+
+    fn from_column_type(column_type: ColumnType) -> Self {
+        match column_type {
+            ColumnType::Integer => SemanticType::Integer,
+            ColumnType::Float => SemanticType::Float,
+            ColumnType::Boolean => SemanticType::Boolean,
+            ColumnType::Date => SemanticType::Date,
+            ColumnType::DateTime => SemanticType::DateTime,
+            ColumnType::String => SemanticType::Categorical,
+        }
+    }
+}
+
+impl fmt::Display for SemanticType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            SemanticType::Integer => "integer",
+            SemanticType::Float => "float",
+            SemanticType::Boolean => "boolean",
+            SemanticType::Date => "date",
+            SemanticType::DateTime => "datetime",
+            SemanticType::Categorical => "categorical",
+            SemanticType::Text => "text",
+            SemanticType::Identifier => "identifier",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for SemanticType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "integer" | "int" => Ok(SemanticType::Integer),
+            "float" | "double" | "number" | "numeric" => Ok(SemanticType::Float),
+            "boolean" | "bool" => Ok(SemanticType::Boolean),
+            "date" => Ok(SemanticType::Date),
+            "datetime" | "timestamp" => Ok(SemanticType::DateTime),
+            "categorical" | "category" => Ok(SemanticType::Categorical),
+            "text" | "string" => Ok(SemanticType::Text),
+            "identifier" | "id" => Ok(SemanticType::Identifier),
+            _ => Err(anyhow!("Unknown column type: {}", s)),
+        }
+    }
+}
+
+/// 열 하나의 추론 결과
+#[derive(Debug, Clone)]
+pub struct ColumnSchema {
+    pub name: String,
+    pub semantic_type: SemanticType,
+    /// 결측이 아닌 값 중 이 타입에 맞는 값의 비율 (0~1)
+    pub confidence: f64,
+    pub non_missing: usize,
+    pub unique: usize,
+    /// 스키마 파일로 지정한 타입이면 true
+    pub overridden: bool,
+}
+
+/// 스키마 추론 옵션
+#[derive(Debug, Clone)]
+pub struct SchemaOptions {
+    /// 숫자/불리언/날짜 타입으로 인정할 최소 비율
+    pub threshold: f64,
+    /// 열 이름별로 직접 지정한 타입
+    pub overrides: HashMap<String, SemanticType>,
+}
+
+impl Default for SchemaOptions {
+    fn default() -> Self {
+        Self {
+            threshold: DEFAULT_TYPE_THRESHOLD,
+            overrides: HashMap::new(),
+        }
+    }
+}
+
+impl SchemaOptions {
+    /// 지정한 타입의 열이 모두 데이터셋에 있는지 확인
+    pub fn validate(&self, headers: &[String]) -> Result<()> {
+        let mut unknown: Vec<&str> = self
+            .overrides
+            .keys()
+            .filter(|name| !headers.contains(name))
+            .map(String::as_str)
+            .collect();
+        if unknown.is_empty() {
+            return Ok(());
+        }
+        unknown.sort();
+        Err(anyhow!("Schema refers to columns not in the data: {}", unknown.join(", ")))
+    }
+
+    /// date/datetime으로 지정한 열이 있는지
+    pub fn has_date_overrides(&self) -> bool {
+        self.overrides.values().any(|t| matches!(t, SemanticType::Date | SemanticType::DateTime))
+    }
+}
+
+/// 스키마 파일 읽기. JSON 객체({"열": "타입"}) 또는 한 줄에 "열 타입"을 쉼표, 탭, 콜론 또는 공백으로 구분해 적음.
+/// 빈 줄과 #으로 시작하는 줄은 건너뜀
+pub fn read_schema_file<P: AsRef<Path>>(schema_path: P) -> Result<HashMap<String, SemanticType>> {
+    let path = schema_path.as_ref();
+    let bytes = std::fs::read(path)?;
+    let (text, _) = encoding::decode(&bytes, None);
+
+    if text.trim_start().starts_with('{') {
+        let object: serde_json::Map<String, serde_json::Value> = serde_json::from_str(&text)
+            .map_err(|e| anyhow!("Invalid schema file {}: {}", path.display(), e))?;
+        return object
+            .into_iter()
+            .map(|(name, value)| match value.as_str() {
+                Some(type_name) => Ok((name, type_name.parse()?)),
+                None => Err(anyhow!("Type for column '{}' must be a string", name)),
+            })
+            .collect();
+    }
+
+    let mut overrides = HashMap::new();
+    for (line_idx, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        // The type is the last token, so column names may contain spaces
+        let split = line
+            .rfind([',', '\t', ':'])
+            .or_else(|| line.rfind(char::is_whitespace))
+            .ok_or_else(|| anyhow!("{} line {}: expected 'column type', found '{}'", path.display(), line_idx + 1, line))?;
+        let (name, type_name) = (line[..split].trim(), line[split + 1..].trim());
+        let semantic_type = type_name
+            .parse()
+            .map_err(|e| anyhow!("{} line {}: {}", path.display(), line_idx + 1, e))?;
+        overrides.insert(name.to_string(), semantic_type);
+    }
+    Ok(overrides)
+}
+
+/// 스키마 파일에서 date/datetime으로 지정한 열을 실제 날짜/시간 열로 바꿈 (이미 날짜/시간인 열은 그대로).
+/// 날짜로 읽히지 않는 값은 결측으로 바꾸고, 바꾼 열은 LoadReport의 날짜 열 보고에 기록
+pub fn apply_date_overrides(dataset: &mut DataSet, options: &SchemaOptions) {
+    let mut reports = Vec::new();
+    for (header, column) in dataset.headers.iter().zip(dataset.columns.iter_mut()) {
+        let target = match options.overrides.get(header) {
+            Some(SemanticType::Date) => ColumnType::Date,
+            Some(SemanticType::DateTime) => ColumnType::DateTime,
+            _ => continue,
+        };
+        if matches!(column.column_type(), ColumnType::Date | ColumnType::DateTime) {
+            continue;
+        }
+
+        let mut unparsed = 0;
+        let values: Vec<Value> = column
+            .values()
+            .map(|value| {
+                if value.is_null() {
+                    return Value::Null;
+                }
+                let text = value.to_string();
+                // A date column may still hold a few values with a time, and a datetime column plain dates
+                let parsed = [ColumnType::Date, ColumnType::DateTime]
+                    .into_iter()
+                    .map(|column_type| column::parse_as(&text, column_type))
+                    .find(|parsed| !parsed.is_null())
+                    .unwrap_or(Value::Null);
+                if parsed.is_null() {
+                    unparsed += 1;
+                }
+                parsed
+            })
+            .collect();
+
+        let converted = values.iter().filter(|value| !value.is_null()).count();
+        let column_type = if values.iter().any(|value| matches!(value, Value::DateTime(_))) {
+            ColumnType::DateTime
+        } else {
+            target
+        };
+        *column = Column::from_values_as(values, column_type);
+        reports.push(DateFormatReport {
+            column: header.clone(),
+            format: format!("schema file ({})", options.overrides[header]),
+            converted,
+            unparsed,
+        });
+    }
+    dates::merge_reports(&mut dataset.load_report.date_formats, &reports);
+}
+
+/// 데이터셋의 모든 열 종류 추론
+pub fn infer_schema(dataset: &DataSet, options: &SchemaOptions) -> Vec<ColumnSchema> {
+    dataset
+        .headers
+        .iter()
+        .zip(&dataset.columns)
+        .map(|(name, column)| infer_column(name, column, options))
+        .collect()
+}
+
+/// 열 하나의 종류 추론. 타입이 정해진 열은 그 타입을 따르고, 문자열 열은
+/// 불리언 → 정수 → 실수 → 날짜 → 날짜/시간 순으로 threshold 이상 맞는 첫 타입을 고름.
+/// 맞는 타입이 없으면 값의 반복 정도와 길이로 범주형/자유 텍스트/식별자를 구분
+pub fn infer_column(name: &str, column: &Column, options: &SchemaOptions) -> ColumnSchema {
+    let texts: Vec<String> = match column.data() {
+        ColumnData::String(cells) => cells
+            .iter()
+            .enumerate()
+            .filter(|(row, _)| !column.is_null(*row))
+            .map(|(_, cell)| cell.clone())
+            .collect(),
+        _ => column.values().filter(|value| !value.is_null()).map(|value| value.to_string()).collect(),
+    };
+    let unique = count_unique(texts.iter().cloned());
+
+    let (semantic_type, confidence, overridden) = match options.overrides.get(name) {
+        Some(&override_type) => (override_type, override_ratio(&texts, override_type), true),
+        None if column.column_type() != ColumnType::String => {
+            (SemanticType::from_column_type(column.column_type()), 1.0, false)
+        }
+        None => {
+            let (semantic_type, confidence) = infer_text_type(&texts, unique, options.threshold);
+            (semantic_type, confidence, false)
+        }
+    };
+
+    ColumnSchema {
+        name: name.to_string(),
+        semantic_type,
+        confidence,
+        non_missing: texts.len(),
+        unique,
+        overridden,
+    }
+}
+
+fn infer_text_type(texts: &[String], unique: usize, threshold: f64) -> (SemanticType, f64) {
+    if texts.is_empty() {
+        return (SemanticType::Categorical, 0.0);
+    }
+
+    let typed = [
+        SemanticType::Boolean,
+        SemanticType::Integer,
+        SemanticType::Float,
+        SemanticType::Date,
+        SemanticType::DateTime,
+    ];
+    for semantic_type in typed {
+        let ratio = type_ratio(texts, semantic_type);
+        if ratio >= threshold {
+            return (semantic_type, ratio);
+        }
+    }
+
+    let unique_ratio = unique as f64 / texts.len() as f64;
+    let text_ratio = type_ratio(texts, SemanticType::Text);
+    if unique_ratio > 0.5 && text_ratio >= 0.5 {
+        (SemanticType::Text, text_ratio)
+    } else if unique_ratio >= threshold && texts.len() > 1 {
+        (SemanticType::Identifier, unique_ratio)
+    } else {
+        (SemanticType::Categorical, type_ratio(texts, SemanticType::Categorical))
+    }
+}
+
+/// 결측이 아닌 값 중 지정한 종류에 맞는 값의 비율
+fn type_ratio(texts: &[String], semantic_type: SemanticType) -> f64 {
+    if texts.is_empty() {
+        return 0.0;
+    }
+
+    let matched = match semantic_type {
+        SemanticType::Integer => count_parsed(texts, ColumnType::Integer),
+        SemanticType::Float => count_parsed(texts, ColumnType::Float),
+        SemanticType::Boolean => count_parsed(texts, ColumnType::Boolean),
+        SemanticType::Date => count_parsed(texts, ColumnType::Date),
+        // Plain dates are valid values in a datetime column
+        SemanticType::DateTime => texts
+            .iter()
+            .filter(|text| {
+                !column::parse_as(text, ColumnType::DateTime).is_null() || !column::parse_as(text, ColumnType::Date).is_null()
+            })
+            .count(),
+        // Share of values that occur more than once
+        SemanticType::Categorical => {
+            let mut counts: HashMap<&str, usize> = HashMap::new();
+            for text in texts {
+                *counts.entry(text.as_str()).or_insert(0) += 1;
+            }
+            texts.iter().filter(|text| counts[text.as_str()] > 1).count()
+        }
+        SemanticType::Text => texts
+            .iter()
+            .filter(|text| {
+                let text = text.trim();
+                text.contains(char::is_whitespace) || text.chars().count() > FREE_TEXT_MIN_CHARS
+            })
+            .count(),
+        SemanticType::Identifier => count_unique(texts.iter().cloned()),
+    };
+
+    matched as f64 / texts.len() as f64
+}
+
+/// 직접 지정한 타입에 맞는 값의 비율. 숫자는 분석할 때처럼 앞자리 0이 있어도 숫자로 셈
+fn override_ratio(texts: &[String], semantic_type: SemanticType) -> f64 {
+    if !semantic_type.is_numeric() || texts.is_empty() {
+        return type_ratio(texts, semantic_type);
+    }
+    // Same rule as the loader, so "NaN" and "inf" count against the type rather than for it
+    let parsed = texts.iter().filter(|text| column::parse_number_text(text).is_some()).count();
+    parsed as f64 / texts.len() as f64
+}
+
+fn count_parsed(texts: &[String], column_type: ColumnType) -> usize {
+    texts.iter().filter(|text| !column::parse_as(text, column_type).is_null()).count()
+}
+
+fn count_unique(values: impl Iterator<Item = String>) -> usize {
+    values.collect::<HashSet<_>>().len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::column::NullValues;
+
+    fn text_column(cells: &[&str]) -> Column {
+        Column::from_values_as(cells.iter().map(|cell| Value::String(cell.to_string())).collect(), ColumnType::String)
+    }
+
+    fn infer(cells: &[&str], options: &SchemaOptions) -> ColumnSchema {
+        infer_column("v", &text_column(cells), options)
+    }
+
+    #[test]
+    fn non_finite_values_lower_numeric_confidence() {
+        let options = SchemaOptions::default();
+
+        let one_stray = infer(&["1", "2", "3", "4", "NaN", "6", "7", "8", "9", "10"], &options);
+        assert_eq!(one_stray.semantic_type, SemanticType::Integer);
+        assert!((one_stray.confidence - 0.9).abs() < 1e-9);
+
+        let two_stray = infer(&["1", "2", "3", "4", "NaN", "6", "inf", "8", "9", "10"], &options);
+        assert!(!two_stray.semantic_type.is_numeric(), "{:?}", two_stray.semantic_type);
+
+        let overflow = infer(&["1.5", "2.5", "1e999", "-inf"], &options);
+        assert!(!overflow.semantic_type.is_numeric(), "{:?}", overflow.semantic_type);
+    }
+
+    #[test]
+    fn override_confidence_ignores_non_finite_values() {
+        let mut options = SchemaOptions::default();
+        options.overrides.insert("v".to_string(), SemanticType::Float);
+
+        let schema = infer(&["1.5", "NaN", "2.5", "infinity"], &options);
+        assert!(schema.overridden);
+        assert_eq!(schema.semantic_type, SemanticType::Float);
+        assert!((schema.confidence - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn infers_semantic_types_of_text_columns() {
+        let options = SchemaOptions::default();
+        let kind = |cells: &[&str]| infer(cells, &options).semantic_type;
+
+        assert_eq!(kind(&["true", "false", "true"]), SemanticType::Boolean);
+        assert_eq!(kind(&["1.5", "2", "3.25"]), SemanticType::Float);
+        assert_eq!(kind(&["2024-03-01", "2024-03-02"]), SemanticType::Date);
+        assert_eq!(kind(&["서울", "부산", "서울", "부산"]), SemanticType::Categorical);
+        assert_eq!(kind(&["A-001", "A-002", "A-003"]), SemanticType::Identifier);
+
+        let typed = Column::from_cells(vec![Value::String("3".to_string())], &NullValues::default());
+        assert_eq!(infer_column("n", &typed, &options).semantic_type, SemanticType::Integer);
+    }
+}
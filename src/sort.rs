@@ -0,0 +1,231 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+use anyhow::{Result, anyhow};
+use crate::column::Value;
+use crate::dates;
+use crate::read_file::DataSet;
+
+/// 결측 값을 둘 위치 (정렬 방향과 관계없음)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NullsPlacement {
+    First,
+    #[default]
+    Last,
+}
+
+impl fmt::Display for NullsPlacement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            NullsPlacement::First => "first",
+            NullsPlacement::Last => "last",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for NullsPlacement {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "first" => Ok(NullsPlacement::First),
+            "last" => Ok(NullsPlacement::Last),
+            _ => Err(anyhow!("Unknown nulls placement: {} (use first or last)", s)),
+        }
+    }
+}
+
+/// 정렬 기준 열 하나
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SortKey {
+    pub column: String,
+    pub descending: bool,
+}
+
+impl SortKey {
+    pub fn ascending(column: &str) -> Self {
+        Self { column: column.to_string(), descending: false }
+    }
+
+    pub fn descending(column: &str) -> Self {
+        Self { column: column.to_string(), descending: true }
+    }
+}
+
+/// 쉼표로 구분한 정렬 기준 해석. 각 기준은 "열 [asc|desc]" (예: "도시, 점수 desc").
+/// 열 이름에 공백이 있어도 되며 큰따옴표로 감쌀 수도 있음
+pub fn parse_sort_keys(spec: &str) -> Result<Vec<SortKey>> {
+    let keys: Vec<SortKey> = spec
+        .split(',')
+        .map(str::trim)
+        .filter(|key| !key.is_empty())
+        .map(|key| {
+            let (column, descending) = match key.rsplit_once(char::is_whitespace) {
+                Some((column, direction)) if direction.eq_ignore_ascii_case("desc") => (column, true),
+                Some((column, direction)) if direction.eq_ignore_ascii_case("asc") => (column, false),
+                _ => (key, false),
+            };
+            let column = column.trim();
+            let column = column.strip_prefix('"').and_then(|c| c.strip_suffix('"')).unwrap_or(column);
+            SortKey { column: column.to_string(), descending }
+        })
+        .collect();
+
+    if keys.is_empty() {
+        return Err(anyhow!("No sort keys given"));
+    }
+    Ok(keys)
+}
+
+/// 정렬 옵션
+#[derive(Debug, Clone, Default)]
+pub struct SortOptions {
+    /// 앞의 기준이 같을 때 다음 기준으로 비교
+    pub keys: Vec<SortKey>,
+    pub nulls: NullsPlacement,
+}
+
+/// 기준 열 순서대로 행을 정렬한 데이터셋. 숫자/날짜/불리언 열은 값으로, 문자열 열은 한국어 순서로
+/// (숫자로 읽히는 값은 숫자로) 비교하며 모든 기준이 같은 행은 원래 순서를 유지
+pub fn sort_dataset(dataset: &DataSet, options: &SortOptions) -> Result<DataSet> {
+    if options.keys.is_empty() {
+        return Err(anyhow!("No sort keys given"));
+    }
+
+    // Values are read once up front instead of cloning strings on every comparison
+    let keys: Vec<(Vec<Value>, bool)> = options
+        .keys
+        .iter()
+        .map(|key| Ok((dataset.column(&key.column)?.values().collect(), key.descending)))
+        .collect::<Result<_>>()?;
+
+    let mut indices: Vec<usize> = (0..dataset.row_count()).collect();
+    indices.sort_by(|&a, &b| {
+        keys.iter()
+            .map(|(values, descending)| compare_cells(&values[a], &values[b], *descending, options.nulls))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
+    });
+
+    let mut sorted = dataset.take_rows(&indices, dataset.file_path.clone());
+    sorted.load_report = dataset.load_report.clone();
+    Ok(sorted)
+}
+
+pub(crate) fn compare_cells(a: &Value, b: &Value, descending: bool, nulls: NullsPlacement) -> Ordering {
+    let null_first = match nulls {
+        NullsPlacement::First => Ordering::Less,
+        NullsPlacement::Last => Ordering::Greater,
+    };
+    match (a.is_null(), b.is_null()) {
+        (true, true) => Ordering::Equal,
+        (true, false) => null_first,
+        (false, true) => null_first.reverse(),
+        (false, false) if descending => compare_values(a, b).reverse(),
+        (false, false) => compare_values(a, b),
+    }
+}
+
+/// 결측이 아닌 두 값 비교. 숫자로 읽히는 문자열은 숫자로 비교하고 숫자가 아닌 문자열보다 앞에 둠
+pub fn compare_values(a: &Value, b: &Value) -> Ordering {
+    match (a, b) {
+        (Value::Integer(x), Value::Integer(y)) => x.cmp(y),
+        (Value::Boolean(x), Value::Boolean(y)) => x.cmp(y),
+        // A numeric column with one bad cell is read as text; its numbers still sort by value, before the text
+        (Value::String(x), Value::String(y)) => match (a.as_number(), b.as_number()) {
+            (Some(m), Some(n)) => m.total_cmp(&n).then_with(|| compare_korean(x, y)),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => compare_korean(x, y),
+        },
+        (Value::Date(_) | Value::DateTime(_), Value::Date(_) | Value::DateTime(_)) => {
+            dates::as_datetime(a).cmp(&dates::as_datetime(b))
+        }
+        _ => match (a.as_number(), b.as_number()) {
+            (Some(x), Some(y)) => x.total_cmp(&y),
+            _ => compare_korean(&a.to_string(), &b.to_string()),
+        },
+    }
+}
+
+const HANGUL_SYLLABLES: std::ops::RangeInclusive<u32> = 0xAC00..=0xD7A3;
+/// 한글 호환 자모 자음 (ㄱ ~ ㅎ)
+const JAMO_CONSONANTS: &str = "ㄱㄲㄴㄷㄸㄹㅁㅂㅃㅅㅆㅇㅈㅉㅊㅋㅌㅍㅎ";
+
+/// 한국어 사전 순서로 문자열 비교. 공백/기호 → 숫자 → 영문(대소문자 무시) → 한글 → 그 밖의 문자 순이며,
+/// 한글은 초성 자음만 쓴 글자(ㄱ)를 같은 초성의 음절(가, 각) 앞에 둠. 키가 같으면 원래 문자로 비교
+pub fn compare_korean(a: &str, b: &str) -> Ordering {
+    a.chars()
+        .map(collation_key)
+        .cmp(b.chars().map(collation_key))
+        .then_with(|| a.cmp(b))
+}
+
+fn collation_key(c: char) -> (u8, u32, u32) {
+    let code = c as u32;
+    if HANGUL_SYLLABLES.contains(&code) {
+        let offset = code - HANGUL_SYLLABLES.start();
+        // Syllables sort by initial, then medial and final; a bare initial consonant gets medial 0
+        return (3, offset / (21 * 28), offset % (21 * 28) + 1);
+    }
+    if let Some(initial) = JAMO_CONSONANTS.chars().position(|jamo| jamo == c) {
+        return (3, initial as u32, 0);
+    }
+    if c.is_ascii_digit() {
+        return (1, code, 0);
+    }
+    if c.is_ascii_alphabetic() {
+        return (2, c.to_ascii_lowercase() as u32, 0);
+    }
+    if c.is_whitespace() || c.is_ascii_punctuation() {
+        return (0, code, 0);
+    }
+    (4, code, 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(words: &[&str]) -> Vec<String> {
+        let mut words: Vec<String> = words.iter().map(|word| word.to_string()).collect();
+        words.sort_by(|a, b| compare_korean(a, b));
+        words
+    }
+
+    #[test]
+    fn korean_order_groups_symbols_digits_latin_then_hangul() {
+        assert_eq!(
+            sorted(&["한국", "漢字", "apple", "10", "Banana", "-", "가나", " 공백", "2"]),
+            [" 공백", "-", "10", "2", "apple", "Banana", "가나", "한국", "漢字"]
+        );
+    }
+
+    #[test]
+    fn hangul_follows_dictionary_order() {
+        assert_eq!(
+            sorted(&["나", "까", "갸", "ㄴ", "각", "ㄲ", "가", "ㄱ"]),
+            ["ㄱ", "가", "각", "갸", "ㄲ", "까", "ㄴ", "나"]
+        );
+        assert_eq!(sorted(&["가나다", "가", "가나"]), ["가", "가나", "가나다"]);
+        assert_eq!(sorted(&["김철수", "김영희", "강민수", "이수진"]), ["강민수", "김영희", "김철수", "이수진"]);
+    }
+
+    #[test]
+    fn latin_letters_ignore_case_until_tied() {
+        assert_eq!(sorted(&["b", "a", "B", "A"]), ["A", "a", "B", "b"]);
+        assert_eq!(compare_korean("Seoul", "seoul"), Ordering::Less);
+        assert_eq!(compare_korean("seoul", "Seoul"), Ordering::Greater);
+        assert_eq!(compare_korean("서울", "서울"), Ordering::Equal);
+    }
+
+    #[test]
+    fn numbers_in_text_sort_by_value_before_text() {
+        let mut ordered: Vec<Value> =
+            ["abc", "100", "9", "85", "가"].iter().map(|text| Value::String(text.to_string())).collect();
+        ordered.sort_by(compare_values);
+        let ordered: Vec<String> = ordered.iter().map(Value::to_string).collect();
+        assert_eq!(ordered, ["9", "85", "100", "abc", "가"]);
+    }
+}
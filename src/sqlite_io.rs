@@ -0,0 +1,297 @@
+use std::path::Path;
+use anyhow::{Result, anyhow};
+use chrono::FixedOffset;
+use rusqlite::types::ValueRef;
+use rusqlite::{Connection, OpenFlags, params_from_iter};
+use crate::column::{self, Column, ColumnType, NullValues, Value};
+use crate::compression::STDIO_PATH;
+use crate::dates::{self, DateFormat};
+use crate::read_file::DataSet;
+
+/// SQLite에서 읽을 대상
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SqliteSource {
+    Table(String),
+    Query(String),
+}
+
+/// 데이터베이스의 테이블 이름 목록
+pub fn list_tables<P: AsRef<Path>>(file_path: P) -> Result<Vec<String>> {
+    let connection = Connection::open_with_flags(file_path.as_ref(), OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    table_names(&connection)
+}
+
+fn table_names(connection: &Connection) -> Result<Vec<String>> {
+    let mut statement = connection
+        .prepare("SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name")?;
+    let names = statement
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(names)
+}
+
+/// SQLite 테이블 또는 쿼리 결과를 읽음. source가 None이면 테이블이 하나뿐일 때 그 테이블을 읽음.
+/// 열 타입은 선언된 타입을 따르고(날짜 열의 Unix 시각과 RFC 3339 값은 UTC 날짜·시간으로 변환),
+/// 선언이 없는 쿼리 식이나 선언과 맞지 않는 값이 있는 열은 값으로 추론
+pub fn read_sqlite_file<P: AsRef<Path>>(file_path: P, source: Option<&SqliteSource>) -> Result<DataSet> {
+    let path = file_path.as_ref();
+    let connection = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+
+    let sql = match source {
+        Some(SqliteSource::Table(table)) => format!("SELECT * FROM {}", quote_identifier(table)),
+        Some(SqliteSource::Query(query)) => query.clone(),
+        None => {
+            let tables = table_names(&connection)?;
+            match tables.as_slice() {
+                [table] => format!("SELECT * FROM {}", quote_identifier(table)),
+                [] => return Err(anyhow!("No tables found in SQLite database")),
+                _ => return Err(anyhow!("Specify a table or query (tables: {})", tables.join(", "))),
+            }
+        }
+    };
+
+    let mut statement = connection.prepare(&sql)?;
+    let headers: Vec<String> = statement.column_names().iter().map(|name| name.to_string()).collect();
+    let declared_types: Vec<Option<ColumnType>> = statement
+        .columns()
+        .iter()
+        .map(|column| column.decl_type().and_then(declared_column_type))
+        .collect();
+
+    let mut cells: Vec<Vec<Value>> = vec![Vec::new(); headers.len()];
+    let mut rows = statement.query([])?;
+    while let Some(row) = rows.next()? {
+        for (col_idx, column_cells) in cells.iter_mut().enumerate() {
+            column_cells.push(sqlite_to_value(row.get_ref(col_idx)?));
+        }
+    }
+
+    let null_values = NullValues::new::<&str>(&[]);
+    let columns = cells
+        .into_iter()
+        .zip(&declared_types)
+        .map(|(values, declared)| match declared {
+            Some(column_type) => declared_column(values, *column_type, &null_values),
+            None => Column::from_cells(values, &null_values),
+        })
+        .collect();
+
+    Ok(DataSet::from_columns(headers, columns, path.to_string_lossy().to_string()))
+}
+
+/// 데이터셋을 새 SQLite 테이블로 저장. 같은 이름의 테이블이 있으면 오류
+pub fn save_dataset_to_sqlite<P: AsRef<Path>>(dataset: &DataSet, output_path: P, table: &str) -> Result<()> {
+    let path = output_path.as_ref();
+    if path == Path::new(STDIO_PATH) {
+        return Err(anyhow!("SQLite databases cannot be written to stdout"));
+    }
+    let mut connection = Connection::open(path)?;
+    if table_names(&connection)?.iter().any(|name| name.eq_ignore_ascii_case(table)) {
+        return Err(anyhow!("Table '{}' already exists", table));
+    }
+
+    let column_definitions: Vec<String> = dataset
+        .headers
+        .iter()
+        .zip(&dataset.columns)
+        .map(|(header, column)| format!("{} {}", quote_identifier(header), declared_type_name(column.column_type())))
+        .collect();
+
+    let transaction = connection.transaction()?;
+    transaction.execute(
+        &format!("CREATE TABLE {} ({})", quote_identifier(table), column_definitions.join(", ")),
+        [],
+    )?;
+
+    {
+        let placeholders = vec!["?"; dataset.column_count()].join(", ");
+        let mut insert = transaction.prepare(&format!(
+            "INSERT INTO {} VALUES ({})",
+            quote_identifier(table),
+            placeholders
+        ))?;
+
+        for row_idx in 0..dataset.row_count() {
+            let values = dataset.columns.iter().map(|column| value_to_sqlite(column.get(row_idx)));
+            insert.execute(params_from_iter(values))?;
+        }
+    }
+
+    transaction.commit()?;
+    Ok(())
+}
+
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// SQLite 타입 선호도 규칙에 날짜/불리언 선언을 더해 열 타입 결정
+fn declared_column_type(declared: &str) -> Option<ColumnType> {
+    let declared = declared.to_uppercase();
+    let column_type = if declared.contains("BOOL") {
+        ColumnType::Boolean
+    } else if declared.contains("DATETIME") || declared.contains("TIMESTAMP") {
+        ColumnType::DateTime
+    } else if declared.contains("DATE") {
+        ColumnType::Date
+    } else if declared.contains("INT") {
+        ColumnType::Integer
+    } else if declared.contains("CHAR") || declared.contains("CLOB") || declared.contains("TEXT") {
+        ColumnType::String
+    } else if declared.contains("REAL") || declared.contains("FLOA") || declared.contains("DOUB")
+        || declared.contains("NUMERIC") || declared.contains("DECIMAL")
+    {
+        ColumnType::Float
+    } else {
+        return None;
+    };
+    Some(column_type)
+}
+
+fn declared_type_name(column_type: ColumnType) -> &'static str {
+    match column_type {
+        ColumnType::Integer => "INTEGER",
+        ColumnType::Float => "REAL",
+        ColumnType::Boolean => "BOOLEAN",
+        ColumnType::String => "TEXT",
+        ColumnType::Date => "DATE",
+        ColumnType::DateTime => "DATETIME",
+    }
+}
+
+fn sqlite_to_value(value: ValueRef<'_>) -> Value {
+    match value {
+        ValueRef::Null => Value::Null,
+        ValueRef::Integer(i) => Value::Integer(i),
+        ValueRef::Real(f) => Value::Float(f),
+        ValueRef::Text(bytes) => Value::String(String::from_utf8_lossy(bytes).to_string()),
+        ValueRef::Blob(bytes) => Value::String(bytes.iter().map(|b| format!("{:02x}", b)).collect()),
+    }
+}
+
+/// 선언된 타입으로 열 생성. 타입 선호도상 선언과 다른 값도 저장될 수 있으므로, 맞지 않는 값이 있으면
+/// 타입을 넓히고(정수 → 실수, 날짜 → 날짜·시간) 그래도 맞지 않으면 값으로 추론해 값을 잃지 않음
+fn declared_column(values: Vec<Value>, column_type: ColumnType, null_values: &NullValues) -> Column {
+    let coerced: Vec<Value> = values.iter().map(|value| coerce(value.clone(), column_type)).collect();
+    let fits_all = |target: ColumnType| {
+        values.iter().zip(&coerced).all(|(original, value)| original.is_null() || fits(value, target))
+    };
+
+    let widened = match column_type {
+        ColumnType::Integer => ColumnType::Float,
+        ColumnType::Date => ColumnType::DateTime,
+        other => other,
+    };
+    if fits_all(column_type) {
+        Column::from_values_as(coerced, column_type)
+    } else if fits_all(widened) {
+        Column::from_values_as(coerced, widened)
+    } else {
+        Column::from_cells(values, null_values)
+    }
+}
+
+fn fits(value: &Value, column_type: ColumnType) -> bool {
+    matches!(
+        (value, column_type),
+        (Value::Integer(_), ColumnType::Integer)
+            | (Value::Integer(_) | Value::Float(_), ColumnType::Float)
+            | (Value::Boolean(_), ColumnType::Boolean)
+            | (Value::String(_), ColumnType::String)
+            | (Value::Date(_), ColumnType::Date)
+            | (Value::Date(_) | Value::DateTime(_), ColumnType::DateTime)
+    )
+}
+
+// SQLite stores dates as text or unix time and booleans as integers regardless of the declared type
+fn coerce(value: Value, column_type: ColumnType) -> Value {
+    let utc = FixedOffset::east_opt(0).unwrap();
+    match (value, column_type) {
+        (Value::Integer(seconds), ColumnType::Date | ColumnType::DateTime) => {
+            dates::parse_with_format(&Value::Integer(seconds), &DateFormat::UnixSeconds, utc)
+        }
+        (Value::String(s), ColumnType::Date | ColumnType::DateTime) => [column_type, ColumnType::DateTime]
+            .into_iter()
+            .map(|target| column::parse_as(&s, target))
+            .find(|value| !value.is_null())
+            .unwrap_or_else(|| dates::parse_with_format(&Value::String(s), &DateFormat::Rfc3339, utc)),
+        (Value::Integer(i), ColumnType::Boolean) => Value::Boolean(i != 0),
+        (Value::Integer(i), ColumnType::String) => Value::String(i.to_string()),
+        (Value::Float(f), ColumnType::String) => Value::String(f.to_string()),
+        (Value::Float(f), ColumnType::Integer) if f.fract() == 0.0 => Value::Integer(f as i64),
+        (Value::String(s), column_type) if column_type != ColumnType::String => column::parse_as(&s, column_type),
+        (value, _) => value,
+    }
+}
+
+fn value_to_sqlite(value: Value) -> rusqlite::types::Value {
+    use rusqlite::types::Value as SqlValue;
+    match value {
+        Value::Null => SqlValue::Null,
+        Value::Integer(i) => SqlValue::Integer(i),
+        Value::Float(f) => SqlValue::Real(f),
+        Value::Boolean(b) => SqlValue::Integer(b as i64),
+        other => SqlValue::Text(other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn database(dir: &Path, statements: &str) -> std::path::PathBuf {
+        let path = dir.join("test.db");
+        Connection::open(&path).unwrap().execute_batch(statements).unwrap();
+        path
+    }
+
+    fn types(dataset: &DataSet) -> Vec<ColumnType> {
+        dataset.columns.iter().map(Column::column_type).collect()
+    }
+
+    #[test]
+    fn declared_types_decide_column_types() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = database(
+            dir.path(),
+            "CREATE TABLE runs (id INTEGER, code TEXT, score REAL, ok BOOLEAN, day DATE, at TIMESTAMP);
+             INSERT INTO runs VALUES (1, '007', 0.5, 1, '2024-03-01', 1709251200);
+             INSERT INTO runs VALUES (2, NULL, 2, 0, '2024-03-02', '2024-03-01T09:00:00+09:00');",
+        );
+
+        let dataset = read_sqlite_file(&path, None).unwrap();
+        assert_eq!(
+            types(&dataset),
+            [ColumnType::Integer, ColumnType::String, ColumnType::Float, ColumnType::Boolean, ColumnType::Date, ColumnType::DateTime]
+        );
+        assert_eq!(dataset.column("code").unwrap().get_string(0), "007");
+        assert!(dataset.column("code").unwrap().is_null(1));
+        // Unix seconds and offsets are both read as UTC
+        assert_eq!(dataset.column("at").unwrap().get_string(0), "2024-03-01 00:00:00");
+        assert_eq!(dataset.column("at").unwrap().get_string(1), "2024-03-01 00:00:00");
+    }
+
+    #[test]
+    fn queries_and_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = database(
+            dir.path(),
+            "CREATE TABLE a (x INTEGER); CREATE TABLE b (y TEXT);
+             INSERT INTO a VALUES (1), (2), (3);",
+        );
+        let error = read_sqlite_file(&path, None).unwrap_err().to_string();
+        assert!(error.contains("a, b"), "{}", error);
+
+        let query = SqliteSource::Query("SELECT x * 2 AS doubled FROM a WHERE x > 1".to_string());
+        let dataset = read_sqlite_file(&path, Some(&query)).unwrap();
+        assert_eq!(dataset.headers, ["doubled"]);
+        assert_eq!(types(&dataset), [ColumnType::Integer]);
+        assert_eq!(dataset.row_count(), 2);
+
+        save_dataset_to_sqlite(&dataset, &path, "c").unwrap();
+        assert!(save_dataset_to_sqlite(&dataset, &path, "C").is_err());
+        let copy = read_sqlite_file(&path, Some(&SqliteSource::Table("c".to_string()))).unwrap();
+        assert_eq!(copy.rows().collect::<Vec<_>>(), dataset.rows().collect::<Vec<_>>());
+        assert_eq!(list_tables(&path).unwrap(), ["a", "b", "c"]);
+    }
+}